@@ -0,0 +1,133 @@
+//! Helpers of the `data-account` CLI that do not need a cluster
+
+use std::path::Path;
+
+use miniz_oxide::inflate::decompress_to_vec;
+use solana_data_account_client::{
+    state::{
+        CompressionOption, DataAccountMetadata, DataTypeOption, LogLevel, UpdateDataAccountArgs,
+    },
+    update_data_account_with_args,
+};
+use solana_program::{hash::hash, instruction::Instruction, pubkey::Pubkey};
+
+/// Bytes written per UpdateDataAccount so that each write fits in a single transaction
+/// (along with the signature, accounts, blockhash and the other instruction fields)
+pub const CHUNK_LEN: usize = 800;
+
+/// Parses the name of a data_type as passed on the command line
+pub fn parse_data_type(name: &str) -> Result<DataTypeOption, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "custom" => Ok(DataTypeOption::CUSTOM),
+        "json" => Ok(DataTypeOption::JSON),
+        "img" => Ok(DataTypeOption::IMG),
+        "html" => Ok(DataTypeOption::HTML),
+        "png" => Ok(DataTypeOption::PNG),
+        "svg" => Ok(DataTypeOption::SVG),
+        "borsh" => Ok(DataTypeOption::BORSH),
+        "text" => Ok(DataTypeOption::TEXT),
+        _ => Err(format!(
+            "unknown data type {} (expected custom, json, img, html, png, svg, borsh or text)",
+            name
+        )),
+    }
+}
+
+/// Infers the data_type of a file from its extension
+pub fn data_type_for_path(path: &Path) -> DataTypeOption {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("json") => DataTypeOption::JSON,
+        Some("png") => DataTypeOption::PNG,
+        Some("svg") => DataTypeOption::SVG,
+        Some("jpg" | "jpeg" | "gif" | "webp") => DataTypeOption::IMG,
+        Some("html" | "htm") => DataTypeOption::HTML,
+        Some("txt" | "md") => DataTypeOption::TEXT,
+        _ => DataTypeOption::CUSTOM,
+    }
+}
+
+/// Builds the UpdateDataAccount instructions that upload the content in order, one per transaction
+/// The last write truncates any previous content beyond the upload and stores the content_hash of the entire content
+pub fn upload_instructions(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data_type: DataTypeOption,
+    content: &[u8],
+) -> Vec<Instruction> {
+    let chunk_count = ((content.len() + CHUNK_LEN - 1) / CHUNK_LEN).max(1);
+    let content_hash = hash(content).to_bytes();
+    (0..chunk_count)
+        .map(|i| {
+            let offset = i * CHUNK_LEN;
+            let end = (offset + CHUNK_LEN).min(content.len());
+            let is_last = i + 1 == chunk_count;
+            update_data_account_with_args(
+                program_id,
+                authority,
+                data_account,
+                UpdateDataAccountArgs {
+                    data_type: data_type.clone(),
+                    data: content[offset..end].to_vec(),
+                    offset: offset as u64,
+                    realloc_down: is_last,
+                    truncate: is_last,
+                    commit_flag: false,
+                    verify_flag: false,
+                    compression: CompressionOption::NONE,
+                    content_hash: is_last.then_some(content_hash),
+                    sequence: None,
+                    segments: Vec::new(),
+                    expected_revision: None,
+                    debug: LogLevel::QUIET,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Decodes the content of a data account for display as per its data_type
+/// Deflate compressed content is inflated and JSON is pretty-printed, every other data_type is returned as stored
+pub fn render_content(metadata: &DataAccountMetadata, content: &[u8]) -> Result<Vec<u8>, String> {
+    if *metadata.compression() == CompressionOption::ZSTD {
+        return Err("zstd compressed content can't be rendered yet".to_string());
+    }
+    let content = if *metadata.compression() == CompressionOption::DEFLATE
+        || *metadata.data_type() == DataTypeOption::COMPRESSED
+    {
+        decompress_to_vec(content).map_err(|e| format!("content could not be inflated: {:?}", e))?
+    } else {
+        content.to_vec()
+    };
+
+    if *metadata.data_type() == DataTypeOption::JSON {
+        if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&content) {
+            let mut pretty = serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())?;
+            pretty.push(b'\n');
+            return Ok(pretty);
+        }
+    }
+    Ok(content)
+}
+
+/// Summarizes the metadata of a data account of data_len bytes, one `key: value` per line
+/// The realloc statistics help spot data accounts whose growth_policy reallocs too often or overpays rent
+pub fn describe_metadata(metadata: &DataAccountMetadata, data_len: usize) -> String {
+    let unused_len = data_len.saturating_sub(metadata.content_len() as usize);
+    [
+        format!("authority: {}", metadata.authority()),
+        format!("data_status: {:?}", metadata.data_status()),
+        format!("data_type: {:?}", metadata.data_type()),
+        format!("dynamic: {}", metadata.dynamic()),
+        format!("growth_policy: {:?}", metadata.growth_policy()),
+        format!("content_len: {}", metadata.content_len()),
+        format!("data_len: {} ({} unused)", data_len, unused_len),
+        format!("realloc_count: {}", metadata.realloc_count()),
+        format!("rent_paid: {} lamports", metadata.rent_paid()),
+    ]
+    .join("\n")
+}
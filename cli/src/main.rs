@@ -0,0 +1,242 @@
+//! `data-account` wraps the Data Program for users who do not want to write a client:
+//! `init`, `upload`, `cat`, `inspect` and `close` a data account from the command line
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use clap::{Parser, Subcommand};
+use data_account_cli::{
+    data_type_for_path, describe_metadata, parse_data_type, render_content, upload_instructions,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_data_account_client::{
+    close_data_account_to, donation_refund_accounts, donations_address, initialize_data_account,
+    metadata_address,
+    state::{DataAccountMetadata, DataTypeOption},
+};
+use solana_program::{borsh::try_from_slice_unchecked, instruction::Instruction, pubkey::Pubkey};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[clap(
+    name = "data-account",
+    version,
+    about = "Manage data accounts of the Data Program"
+)]
+struct Cli {
+    /// RPC url of the cluster
+    #[clap(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "https://api.devnet.solana.com"
+    )]
+    url: String,
+    /// Keypair of the authority paying for the transactions (defaults to the Solana CLI keypair)
+    #[clap(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+    /// Address of the deployed Data Program
+    #[clap(long, global = true, env = "DATA_ACCOUNT_PROGRAM_ID")]
+    program_id: Pubkey,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create and initialize a new data account owned by the keypair
+    Init {
+        /// Initial size of the data account in bytes
+        #[clap(long, default_value_t = 0)]
+        space: u64,
+        /// Keep the data account at its initial size instead of growing it as the content grows
+        #[clap(long)]
+        fixed: bool,
+    },
+    /// Upload a file as the content of a data account, split into as many transactions as needed
+    Upload {
+        /// File to upload
+        file: PathBuf,
+        /// Data account to overwrite (a new one is created otherwise)
+        #[clap(long)]
+        data_account: Option<Pubkey>,
+        /// Data type of the content: custom, json, img, html, png, svg, borsh or text (inferred from the file extension otherwise)
+        #[clap(long, value_parser = parse_data_type)]
+        data_type: Option<DataTypeOption>,
+    },
+    /// Print the content of a data account as per its data type
+    Cat {
+        /// Data account to read
+        data_account: Pubkey,
+    },
+    /// Print the metadata of a data account, including how often it was realloc-ed and the rent it paid
+    Inspect {
+        /// Data account to inspect
+        data_account: Pubkey,
+    },
+    /// Close a data account and its metadata account, refunding their rent
+    Close {
+        /// Data account to close
+        data_account: Pubkey,
+        /// Account receiving the rent (defaults to the keypair)
+        #[clap(long)]
+        rent_receiver: Option<Pubkey>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> CliResult {
+    let client = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    match cli.command {
+        Command::Cat { data_account } => cat(&client, &cli.program_id, &data_account),
+        Command::Inspect { data_account } => inspect(&client, &cli.program_id, &data_account),
+        Command::Init { space, fixed } => {
+            let payer = load_keypair(cli.keypair)?;
+            let data_account = init(&client, &cli.program_id, &payer, space, !fixed)?;
+            println!("{}", data_account);
+            Ok(())
+        }
+        Command::Upload {
+            file,
+            data_account,
+            data_type,
+        } => {
+            let payer = load_keypair(cli.keypair)?;
+            let content = fs::read(&file)?;
+            let data_account = match data_account {
+                Some(data_account) => data_account,
+                None => init(&client, &cli.program_id, &payer, 0, true)?,
+            };
+            let data_type = data_type.unwrap_or_else(|| data_type_for_path(&file));
+            let instructions = upload_instructions(
+                &cli.program_id,
+                &payer.pubkey(),
+                &data_account,
+                data_type,
+                &content,
+            );
+            let total = instructions.len();
+            for (i, instruction) in instructions.into_iter().enumerate() {
+                send(&client, &payer, &[], instruction)?;
+                eprintln!("uploaded chunk {}/{}", i + 1, total);
+            }
+            println!("{}", data_account);
+            Ok(())
+        }
+        Command::Close {
+            data_account,
+            rent_receiver,
+        } => {
+            let payer = load_keypair(cli.keypair)?;
+            let rent_receiver = rent_receiver.unwrap_or_else(|| payer.pubkey());
+            let mut instruction = close_data_account_to(
+                &cli.program_id,
+                &payer.pubkey(),
+                &data_account,
+                &rent_receiver,
+                None,
+            );
+            // refund any unspent donations to the top donors
+            let donations = client
+                .get_account_with_commitment(
+                    &donations_address(&cli.program_id, &data_account),
+                    client.commitment(),
+                )?
+                .value;
+            instruction.accounts.extend(donation_refund_accounts(
+                &cli.program_id,
+                &data_account,
+                donations.as_ref().map(|account| &account.data[..]),
+            )?);
+            send(&client, &payer, &[], instruction)?;
+            eprintln!("closed {}", data_account);
+            Ok(())
+        }
+    }
+}
+
+/// Loads the keypair at the path, or the default keypair of the Solana CLI
+fn load_keypair(path: Option<PathBuf>) -> Result<Keypair, Box<dyn std::error::Error>> {
+    let path = match path {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path)
+        .map_err(|e| format!("could not read keypair {}: {}", path.display(), e).into())
+}
+
+/// Sends the instruction paid for and signed by the payer (along with any other signers) and waits for confirmation
+fn send(
+    client: &RpcClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instruction: Instruction,
+) -> CliResult {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &all_signers,
+        client.get_latest_blockhash()?,
+    );
+    client.send_and_confirm_transaction_with_spinner(&transaction)?;
+    Ok(())
+}
+
+/// Creates and initializes a new data account owned by the payer and returns its address
+fn init(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    space: u64,
+    dynamic: bool,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let data_account = Keypair::new();
+    let instruction = initialize_data_account(
+        program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        space,
+        dynamic,
+    );
+    send(client, payer, &[&data_account], instruction)?;
+    Ok(data_account.pubkey())
+}
+
+/// Writes the content of the data account to stdout as per its data type
+fn cat(client: &RpcClient, program_id: &Pubkey, data_account: &Pubkey) -> CliResult {
+    let metadata = client.get_account_data(&metadata_address(program_id, data_account))?;
+    let metadata = try_from_slice_unchecked::<DataAccountMetadata>(&metadata)?;
+    let data = client.get_account_data(data_account)?;
+    // only the first content_len bytes of the data account are meaningful
+    let content_len = (metadata.content_len() as usize).min(data.len());
+    let content = render_content(&metadata, &data[..content_len])?;
+    io::stdout().write_all(&content)?;
+    Ok(())
+}
+
+/// Prints the metadata of the data account
+fn inspect(client: &RpcClient, program_id: &Pubkey, data_account: &Pubkey) -> CliResult {
+    let metadata = client.get_account_data(&metadata_address(program_id, data_account))?;
+    let metadata = try_from_slice_unchecked::<DataAccountMetadata>(&metadata)?;
+    let data = client.get_account_data(data_account)?;
+    println!("{}", describe_metadata(&metadata, data.len()));
+    Ok(())
+}
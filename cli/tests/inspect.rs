@@ -0,0 +1,28 @@
+use data_account_cli::describe_metadata;
+use solana_data_account_client::state::{
+    DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption, DATA_VERSION,
+};
+use solana_program::pubkey::Pubkey;
+
+#[test]
+fn test_describe_metadata_shows_realloc_statistics() {
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        DataTypeOption::JSON,
+        255,
+    );
+    metadata.set_content_len(10);
+    metadata.record_realloc(7_000);
+    metadata.record_realloc(0);
+    metadata.record_realloc(3_000);
+
+    let description = describe_metadata(&metadata, 16);
+    assert!(description.contains("content_len: 10\n"));
+    assert!(description.contains("data_len: 16 (6 unused)\n"));
+    assert!(description.contains("realloc_count: 3\n"));
+    assert!(description.ends_with("rent_paid: 10000 lamports"));
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use borsh::BorshDeserialize;
+use data_account_cli::{
+    data_type_for_path, parse_data_type, render_content, upload_instructions, CHUNK_LEN,
+};
+use solana_data_account_client::{
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption,
+        DATA_VERSION,
+    },
+};
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+fn metadata(data_type: DataTypeOption) -> DataAccountMetadata {
+    DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        data_type,
+        255,
+    )
+}
+
+#[test]
+fn test_upload_instructions_chunk_the_content() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+    let content: Vec<u8> = (0..2 * CHUNK_LEN + 10).map(|i| i as u8).collect();
+
+    let instructions = upload_instructions(
+        &program_id,
+        &authority,
+        &data_account,
+        DataTypeOption::CUSTOM,
+        &content,
+    );
+    assert_eq!(instructions.len(), 3);
+
+    let mut uploaded = Vec::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        let args = match DataAccountInstruction::try_from_slice(&instruction.data).unwrap() {
+            DataAccountInstruction::UpdateDataAccount(args) => args,
+            _ => panic!("expected UpdateDataAccount"),
+        };
+        let is_last = i == 2;
+        assert_eq!(args.offset as usize, uploaded.len());
+        assert_eq!(args.truncate, is_last);
+        assert_eq!(
+            args.content_hash,
+            is_last.then(|| hash(&content).to_bytes())
+        );
+        uploaded.extend_from_slice(&args.data);
+    }
+    assert_eq!(uploaded, content);
+}
+
+#[test]
+fn test_upload_instructions_truncate_empty_content() {
+    let instructions = upload_instructions(
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        DataTypeOption::CUSTOM,
+        &[],
+    );
+    assert_eq!(instructions.len(), 1);
+}
+
+#[test]
+fn test_data_type_for_path() {
+    assert_eq!(
+        data_type_for_path(Path::new("data.JSON")),
+        DataTypeOption::JSON
+    );
+    assert_eq!(
+        data_type_for_path(Path::new("logo.png")),
+        DataTypeOption::PNG
+    );
+    assert_eq!(
+        data_type_for_path(Path::new("photo.jpg")),
+        DataTypeOption::IMG
+    );
+    assert_eq!(
+        data_type_for_path(Path::new("README.md")),
+        DataTypeOption::TEXT
+    );
+    assert_eq!(
+        data_type_for_path(Path::new("index.html")),
+        DataTypeOption::HTML
+    );
+    assert_eq!(
+        data_type_for_path(Path::new("notes")),
+        DataTypeOption::CUSTOM
+    );
+    assert_eq!(parse_data_type("Json"), Ok(DataTypeOption::JSON));
+    assert_eq!(parse_data_type("svg"), Ok(DataTypeOption::SVG));
+    assert!(parse_data_type("crdt").is_err());
+}
+
+#[test]
+fn test_render_content_pretty_prints_json() {
+    assert_eq!(
+        render_content(&metadata(DataTypeOption::JSON), br#"{"a":1}"#).unwrap(),
+        b"{\n  \"a\": 1\n}\n"
+    );
+    // invalid JSON and every other data type is returned as stored
+    assert_eq!(
+        render_content(&metadata(DataTypeOption::JSON), b"{oops").unwrap(),
+        b"{oops"
+    );
+    assert_eq!(
+        render_content(&metadata(DataTypeOption::IMG), &[0x89, b'P', b'N', b'G']).unwrap(),
+        [0x89, b'P', b'N', b'G']
+    );
+}
@@ -0,0 +1,378 @@
+//! Typed instruction builders for the Data Program
+//! Each builder returns a fully-populated `Instruction` with the accounts in the order the program expects
+//! Views (e.g., `get_checksum`) read a field out of account data fetched by the caller
+
+use borsh::BorshSerialize;
+use solana_data_account_interface::{
+    checksum::crc32,
+    instruction::DataAccountInstruction,
+    nft::nft_data_address,
+    state::{
+        CloseDataAccountArgs, CompressionOption, DataAccountMetadata, DataTypeOption,
+        DonationsTable, GrowthPolicyOption, InitializeDataAccountArgs,
+        InitializeDataAccountPdaArgs, InitializeNftDataAccountArgs, LogLevel,
+        ResizeDataAccountArgs, UpdateDataAccountArgs, UpdateDataAccountAuthorityArgs,
+        UpdateNftDataAccountArgs, DATA_PDA_SEED, DONATIONS_SEED, PDA_SEED,
+    },
+};
+use solana_program::{
+    borsh::try_from_slice_unchecked,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+pub use solana_data_account_interface::{checksum, instruction, nft::nft_data_address, state};
+
+/// Get the metadata pda of the data account and its bump seed
+pub fn find_metadata_address(program_id: &Pubkey, data_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], program_id)
+}
+
+/// Get the metadata pda of the data account
+pub fn metadata_address(program_id: &Pubkey, data_account: &Pubkey) -> Pubkey {
+    find_metadata_address(program_id, data_account).0
+}
+
+/// Get the donations pda of the data account
+pub fn donations_address(program_id: &Pubkey, data_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DONATIONS_SEED, data_account.as_ref()], program_id).0
+}
+
+/// Get the data account pda derived from the authority and seed (see `initialize_data_account_pda`)
+pub fn data_account_pda_address(program_id: &Pubkey, authority: &Pubkey, seed: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[DATA_PDA_SEED, authority.as_ref(), seed.as_bytes()],
+        program_id,
+    )
+    .0
+}
+
+fn build(
+    program_id: &Pubkey,
+    accounts: Vec<AccountMeta>,
+    ix: DataAccountInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: ix.try_to_vec().unwrap(),
+    }
+}
+
+/// Initializes a data account created by the instruction (the data account must sign)
+/// The authority pays for the data account and the metadata account
+pub fn initialize_data_account(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    space: u64,
+    dynamic: bool,
+) -> Instruction {
+    initialize_data_account_with_args(
+        program_id,
+        authority,
+        data_account,
+        InitializeDataAccountArgs {
+            authority: *authority,
+            space,
+            is_dynamic: dynamic,
+            is_created: false,
+            growth_policy: GrowthPolicyOption::EXACT,
+            append_only: false,
+            idempotent: false,
+            debug: LogLevel::QUIET,
+        },
+    )
+}
+
+/// Initializes a data account with every argument specified (e.g., a growth policy or a separate feepayer)
+pub fn initialize_data_account_with_args(
+    program_id: &Pubkey,
+    feepayer: &Pubkey,
+    data_account: &Pubkey,
+    args: InitializeDataAccountArgs,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*feepayer, true),
+            AccountMeta::new(*data_account, true),
+            AccountMeta::new(metadata_address(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::InitializeDataAccount(args),
+    )
+}
+
+/// Writes uncompressed data at the offset without committing, verifying or truncating
+pub fn update_data_account(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data_type: DataTypeOption,
+    data: Vec<u8>,
+    offset: u64,
+) -> Instruction {
+    update_data_account_with_args(
+        program_id,
+        authority,
+        data_account,
+        UpdateDataAccountArgs {
+            data_type,
+            data,
+            offset,
+            realloc_down: false,
+            truncate: false,
+            commit_flag: false,
+            verify_flag: false,
+            compression: CompressionOption::NONE,
+            content_hash: None,
+            sequence: None,
+            segments: Vec::new(),
+            expected_revision: None,
+            debug: LogLevel::QUIET,
+        },
+    )
+}
+
+/// Updates the data account with every argument specified
+pub fn update_data_account_with_args(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    args: UpdateDataAccountArgs,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_address(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::UpdateDataAccount(args),
+    )
+}
+
+/// Transfers the data account to the new authority (both authorities must sign)
+/// The user must be passed if the data account requires user consent
+pub fn update_data_account_authority(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    new_authority: &Pubkey,
+    user: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*data_account, false),
+        AccountMeta::new(metadata_address(program_id, data_account), false),
+        AccountMeta::new_readonly(*new_authority, true),
+    ];
+    if let Some(user) = user {
+        accounts.push(AccountMeta::new_readonly(*user, true));
+    }
+    build(
+        program_id,
+        accounts,
+        DataAccountInstruction::UpdateDataAccountAuthority(UpdateDataAccountAuthorityArgs {
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Closes the data account and its metadata account, refunding their lamports to the authority
+/// The user must be passed if the data account requires user consent
+pub fn close_data_account(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    user: Option<&Pubkey>,
+) -> Instruction {
+    close_data_account_to(program_id, authority, data_account, authority, user)
+}
+
+/// Closes the data account and its metadata account, refunding their lamports to the rent_receiver
+/// The user must be passed if the data account requires user consent
+pub fn close_data_account_to(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    rent_receiver: &Pubkey,
+    user: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(*data_account, false),
+        AccountMeta::new(metadata_address(program_id, data_account), false),
+        AccountMeta::new(*rent_receiver, false),
+    ];
+    if let Some(user) = user {
+        accounts.push(AccountMeta::new_readonly(*user, true));
+    }
+    build(
+        program_id,
+        accounts,
+        DataAccountInstruction::CloseDataAccount(CloseDataAccountArgs {
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Get the remaining accounts of a close (e.g., `close_data_account`) refunding the unspent donations
+/// to the top donors from the data of the donations pda, or none if the data account never received a donation
+pub fn donation_refund_accounts(
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+    donations_account_data: Option<&[u8]>,
+) -> Result<Vec<AccountMeta>, std::io::Error> {
+    let donations = match donations_account_data {
+        Some(data) if !data.is_empty() => try_from_slice_unchecked::<DonationsTable>(data)?,
+        _ => return Ok(Vec::new()),
+    };
+    let mut accounts = vec![AccountMeta::new(
+        donations_address(program_id, data_account),
+        false,
+    )];
+    accounts.extend(
+        donations
+            .top
+            .iter()
+            .map(|donation| AccountMeta::new(donation.donor, false)),
+    );
+    Ok(accounts)
+}
+
+/// Grows or shrinks the data account to exactly space bytes, e.g. to pre-allocate before a bulk upload
+/// The authority funds the growth and is refunded the excess lamports on shrink
+pub fn resize_data_account(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    space: u64,
+) -> Instruction {
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_address(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::ResizeDataAccount(ResizeDataAccountArgs {
+            space,
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Initializes the data account pda derived from the authority and seed, without a data account keypair
+/// The authority pays for the data account and the metadata account
+pub fn initialize_data_account_pda(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    seed: &str,
+    space: u64,
+    dynamic: bool,
+) -> Instruction {
+    let data_account = data_account_pda_address(program_id, authority, seed);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_address(program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::InitializeDataAccountPda(InitializeDataAccountPdaArgs {
+            seed: seed.to_string(),
+            space,
+            is_dynamic: dynamic,
+            growth_policy: GrowthPolicyOption::EXACT,
+            append_only: false,
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Initializes the data account bound to the NFT mint, paid for by the holder of the NFT
+/// token_account is the token account of the holder holding the NFT
+pub fn initialize_nft_data_account(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    space: u64,
+    dynamic: bool,
+) -> Instruction {
+    let data_account = nft_data_address(mint, program_id);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_address(program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::InitializeNftDataAccount(InitializeNftDataAccountArgs {
+            space,
+            is_dynamic: dynamic,
+            growth_policy: GrowthPolicyOption::EXACT,
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Writes data at the offset of the data account bound to the NFT mint as its current holder
+pub fn update_nft_data_account(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    data: Vec<u8>,
+    offset: u64,
+    truncate: bool,
+) -> Instruction {
+    let data_account = nft_data_address(mint, program_id);
+    build(
+        program_id,
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_address(program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        DataAccountInstruction::UpdateNftDataAccount(UpdateNftDataAccountArgs {
+            data,
+            offset,
+            truncate,
+            debug: LogLevel::QUIET,
+        }),
+    )
+}
+
+/// Get the crc32 checksum of the content from the data of the metadata account, regardless of the data_type
+/// None if the content has been written by any instruction other than UpdateDataAccount since
+pub fn get_checksum(metadata_account_data: &[u8]) -> Result<Option<u32>, std::io::Error> {
+    Ok(try_from_slice_unchecked::<DataAccountMetadata>(metadata_account_data)?.checksum())
+}
+
+/// Get the revision of the content from the data of the metadata account
+/// A cached copy of the content is stale if the revision has changed since it was read
+pub fn get_revision(metadata_account_data: &[u8]) -> Result<u64, std::io::Error> {
+    Ok(try_from_slice_unchecked::<DataAccountMetadata>(metadata_account_data)?.revision())
+}
+
+/// Verifies the content (e.g., a local copy) against the checksum of the metadata account
+/// Returns None if the metadata account holds no checksum to compare against
+pub fn verify_checksum(
+    metadata_account_data: &[u8],
+    content: &[u8],
+) -> Result<Option<bool>, std::io::Error> {
+    Ok(get_checksum(metadata_account_data)?.map(|checksum| checksum == crc32(content)))
+}
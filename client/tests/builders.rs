@@ -0,0 +1,224 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_data_account_client::{
+    checksum::crc32,
+    close_data_account, close_data_account_to, data_account_pda_address, donation_refund_accounts,
+    donations_address, get_checksum, get_revision, initialize_data_account,
+    initialize_data_account_pda,
+    instruction::DataAccountInstruction,
+    metadata_address,
+    state::{
+        DataAccountMetadata, DataStatusOption, DataTypeOption, DonationsTable,
+        SerializationStatusOption, DATA_VERSION, DONATIONS_SIZE, METADATA_SIZE, PDA_SEED,
+    },
+    update_data_account, update_data_account_authority, verify_checksum,
+};
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey, system_program};
+
+#[test]
+fn test_metadata_address() {
+    let program_id = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+    assert_eq!(
+        metadata_address(&program_id, &data_account),
+        Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], &program_id).0
+    );
+}
+
+#[test]
+fn test_initialize_data_account() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+
+    let ix = initialize_data_account(&program_id, &authority, &data_account, 42, true);
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(data_account, true),
+            AccountMeta::new(metadata_address(&program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+    match DataAccountInstruction::try_from_slice(&ix.data).unwrap() {
+        DataAccountInstruction::InitializeDataAccount(args) => {
+            assert_eq!(args.authority, authority);
+            assert_eq!(args.space, 42);
+            assert!(args.is_dynamic);
+            assert!(!args.is_created);
+        }
+        _ => panic!("expected InitializeDataAccount"),
+    }
+}
+
+#[test]
+fn test_update_data_account() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+
+    let ix = update_data_account(
+        &program_id,
+        &authority,
+        &data_account,
+        DataTypeOption::JSON,
+        b"{}".to_vec(),
+        7,
+    );
+    assert_eq!(ix.accounts[0], AccountMeta::new(authority, true));
+    assert_eq!(ix.accounts[1], AccountMeta::new(data_account, false));
+    match DataAccountInstruction::try_from_slice(&ix.data).unwrap() {
+        DataAccountInstruction::UpdateDataAccount(args) => {
+            assert_eq!(args.data, b"{}");
+            assert_eq!(args.offset, 7);
+            assert!(!args.commit_flag);
+            assert!(args.content_hash.is_none());
+        }
+        _ => panic!("expected UpdateDataAccount"),
+    }
+}
+
+#[test]
+fn test_optional_user_account() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+
+    let ix =
+        update_data_account_authority(&program_id, &authority, &data_account, &new_authority, None);
+    assert_eq!(ix.accounts.len(), 4);
+    assert_eq!(
+        ix.accounts[3],
+        AccountMeta::new_readonly(new_authority, true)
+    );
+
+    let ix = close_data_account(&program_id, &authority, &data_account, Some(&user));
+    assert_eq!(ix.accounts.len(), 5);
+    assert_eq!(ix.accounts[0], AccountMeta::new(authority, true));
+    assert_eq!(ix.accounts[3], AccountMeta::new(authority, false));
+    assert_eq!(ix.accounts[4], AccountMeta::new_readonly(user, true));
+    assert!(matches!(
+        DataAccountInstruction::try_from_slice(&ix.data).unwrap(),
+        DataAccountInstruction::CloseDataAccount(_)
+    ));
+
+    let treasury = Pubkey::new_unique();
+    let ix = close_data_account_to(&program_id, &authority, &data_account, &treasury, None);
+    assert_eq!(ix.accounts.len(), 4);
+    assert_eq!(ix.accounts[3], AccountMeta::new(treasury, false));
+}
+
+#[test]
+fn test_initialize_data_account_pda() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_account = data_account_pda_address(&program_id, &authority, "site");
+    assert_eq!(
+        data_account,
+        Pubkey::find_program_address(
+            &[b"data_account_pda", authority.as_ref(), b"site"],
+            &program_id
+        )
+        .0
+    );
+    assert_ne!(
+        data_account,
+        data_account_pda_address(&program_id, &authority, "other")
+    );
+
+    let ix = initialize_data_account_pda(&program_id, &authority, "site", 42, true);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_address(&program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+    match DataAccountInstruction::try_from_slice(&ix.data).unwrap() {
+        DataAccountInstruction::InitializeDataAccountPda(args) => {
+            assert_eq!(args.seed, "site");
+            assert_eq!(args.space, 42);
+            assert!(args.is_dynamic);
+        }
+        _ => panic!("expected InitializeDataAccountPda"),
+    }
+}
+
+#[test]
+fn test_get_checksum() {
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        DataTypeOption::CUSTOM,
+        255,
+    );
+    let content = b"hello world";
+
+    // the metadata account is zero-padded up to METADATA_SIZE
+    let mut data = vec![0; METADATA_SIZE];
+    metadata.serialize(&mut &mut data[..]).unwrap();
+    assert_eq!(get_checksum(&data).unwrap(), None);
+    assert_eq!(verify_checksum(&data, content).unwrap(), None);
+
+    metadata.set_checksum(Some(crc32(content)));
+    metadata.serialize(&mut &mut data[..]).unwrap();
+    assert_eq!(get_checksum(&data).unwrap(), Some(crc32(content)));
+    assert_eq!(verify_checksum(&data, content).unwrap(), Some(true));
+    assert_eq!(verify_checksum(&data, b"hello there").unwrap(), Some(false));
+}
+
+#[test]
+fn test_get_revision() {
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        DataTypeOption::CUSTOM,
+        255,
+    );
+    let mut data = vec![0; METADATA_SIZE];
+    metadata.serialize(&mut &mut data[..]).unwrap();
+    assert_eq!(get_revision(&data).unwrap(), 0);
+
+    metadata.bump_revision();
+    metadata.bump_revision();
+    metadata.serialize(&mut &mut data[..]).unwrap();
+    assert_eq!(get_revision(&data).unwrap(), 2);
+}
+
+#[test]
+fn test_donation_refund_accounts() {
+    let program_id = Pubkey::new_unique();
+    let data_account = Pubkey::new_unique();
+    assert!(donation_refund_accounts(&program_id, &data_account, None)
+        .unwrap()
+        .is_empty());
+
+    let (small, large) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut donations = DonationsTable::default();
+    donations.credit(small, 10).unwrap();
+    donations.credit(large, 20).unwrap();
+    let mut data = vec![0; DONATIONS_SIZE];
+    donations.serialize(&mut &mut data[..]).unwrap();
+
+    // the donations pda is followed by the top donors in the order of the table
+    assert_eq!(
+        donation_refund_accounts(&program_id, &data_account, Some(&data)).unwrap(),
+        vec![
+            AccountMeta::new(donations_address(&program_id, &data_account), false),
+            AccountMeta::new(large, false),
+            AccountMeta::new(small, false),
+        ]
+    );
+}
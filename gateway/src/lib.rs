@@ -0,0 +1,142 @@
+//! Serves the content of data accounts over HTTP without depending on a web framework
+//! A server fetches the data account and its metadata account (e.g., with getMultipleAccounts) and turns them
+//! into a response with `respond`, which decompresses the content, verifies it against its content_hash and
+//! sets the Content-Type of its data_type along with caching headers
+
+use miniz_oxide::inflate::decompress_to_vec;
+use solana_data_account_interface::state::{
+    CompressionOption, DataAccountMetadata, DataStatusOption, DataTypeOption, PNG_SIGNATURE,
+};
+use solana_program::{borsh::try_from_slice_unchecked, hash::hash};
+
+/// Cache-Control of finalized content, which can never change again
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// Cache-Control of content that can still be updated, revalidated against its ETag
+pub const MUTABLE_CACHE_CONTROL: &str = "no-cache";
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Get the value of the header (if set)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum GatewayError {
+    /// The metadata account does not hold the metadata of a data account
+    InvalidMetadata,
+    /// The data account is not initialized or its content is archived
+    NotFound,
+    /// The content is compressed in a way the gateway cannot decompress (e.g., ZSTD)
+    UnsupportedCompression,
+    /// The content does not match its content_hash
+    ContentHashMismatch,
+}
+
+impl GatewayError {
+    /// HTTP status to answer with
+    pub fn status(&self) -> u16 {
+        match self {
+            GatewayError::NotFound => 404,
+            GatewayError::UnsupportedCompression => 415,
+            GatewayError::InvalidMetadata | GatewayError::ContentHashMismatch => 502,
+        }
+    }
+}
+
+/// Builds the response serving the content of a data account given the data of its metadata account
+/// Answers 304 without a body if if_none_match holds the ETag of the content
+pub fn respond(
+    metadata_data: &[u8],
+    data: &[u8],
+    if_none_match: Option<&str>,
+) -> Result<Response, GatewayError> {
+    let metadata = try_from_slice_unchecked::<DataAccountMetadata>(metadata_data)
+        .map_err(|_| GatewayError::InvalidMetadata)?;
+    if matches!(
+        metadata.data_status(),
+        DataStatusOption::UNINITIALIZED | DataStatusOption::ARCHIVED
+    ) {
+        return Err(GatewayError::NotFound);
+    }
+
+    let stored = &data[..(metadata.content_len() as usize).min(data.len())];
+    let content = match metadata.compression() {
+        CompressionOption::NONE => stored.to_vec(),
+        CompressionOption::DEFLATE => {
+            decompress_to_vec(stored).map_err(|_| GatewayError::UnsupportedCompression)?
+        }
+        CompressionOption::ZSTD => return Err(GatewayError::UnsupportedCompression),
+    };
+    if let Some(content_hash) = metadata.content_hash() {
+        if hash(&content).to_bytes() != *content_hash {
+            return Err(GatewayError::ContentHashMismatch);
+        }
+    }
+
+    let cache_control = if *metadata.data_status() == DataStatusOption::FINALIZED {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        MUTABLE_CACHE_CONTROL
+    };
+    let mut headers = vec![("Cache-Control", cache_control.to_string())];
+    if let Some(content_hash) = metadata.content_hash() {
+        let etag = format!("\"{}\"", hex(content_hash));
+        let not_modified = if_none_match
+            .map(|tags| {
+                tags.split(',')
+                    .any(|tag| tag.trim() == etag || tag.trim() == "*")
+            })
+            .unwrap_or(false);
+        headers.push(("ETag", etag));
+        if not_modified {
+            return Ok(Response {
+                status: 304,
+                headers,
+                body: Vec::new(),
+            });
+        }
+    }
+    headers.push(("Content-Type", content_type(metadata.data_type(), &content)));
+    headers.push(("Content-Length", content.len().to_string()));
+
+    Ok(Response {
+        status: 200,
+        headers,
+        body: content,
+    })
+}
+
+/// Content-Type of the content of the data_type
+/// IMG does not record its format so it is sniffed from the magic bytes
+pub fn content_type(data_type: &DataTypeOption, content: &[u8]) -> String {
+    if *data_type != DataTypeOption::IMG {
+        return data_type.mime_type().to_string();
+    }
+    let mime = if content.starts_with(&PNG_SIGNATURE) {
+        "image/png"
+    } else if content.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else if content.starts_with(b"GIF8") {
+        "image/gif"
+    } else if content.len() >= 12 && &content[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        "application/octet-stream"
+    };
+    mime.to_string()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
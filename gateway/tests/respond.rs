@@ -0,0 +1,75 @@
+use borsh::BorshSerialize;
+use solana_data_account_gateway::{
+    respond, GatewayError, IMMUTABLE_CACHE_CONTROL, MUTABLE_CACHE_CONTROL,
+};
+use solana_data_account_interface::state::{
+    DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption, DATA_VERSION,
+};
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+fn metadata(data_type: DataTypeOption, content: &[u8]) -> DataAccountMetadata {
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        data_type,
+        255,
+    );
+    metadata.set_content_len(content.len() as u64);
+    metadata.set_content_hash(Some(hash(content).to_bytes()));
+    metadata
+}
+
+#[test]
+fn test_respond_serves_verified_content() {
+    let content = br#"{"a":1}"#;
+    // the data account may hold unused space beyond the content
+    let mut data = content.to_vec();
+    data.extend_from_slice(&[0; 9]);
+    let metadata = metadata(DataTypeOption::JSON, content)
+        .try_to_vec()
+        .unwrap();
+
+    let response = respond(&metadata, &data, None).unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, content);
+    assert_eq!(response.header("content-type"), Some("application/json"));
+    assert_eq!(
+        response.header("Cache-Control"),
+        Some(MUTABLE_CACHE_CONTROL)
+    );
+    let etag = response.header("ETag").unwrap().to_string();
+
+    let response = respond(&metadata, &data, Some(&etag)).unwrap();
+    assert_eq!(response.status, 304);
+    assert!(response.body.is_empty());
+}
+
+#[test]
+fn test_respond_caches_finalized_content() {
+    let content = b"hello";
+    let mut metadata = metadata(DataTypeOption::TEXT, content);
+    metadata.set_data_status(DataStatusOption::FINALIZED);
+
+    let response = respond(&metadata.try_to_vec().unwrap(), content, None).unwrap();
+    assert_eq!(
+        response.header("Content-Type"),
+        Some("text/plain; charset=utf-8")
+    );
+    assert_eq!(
+        response.header("Cache-Control"),
+        Some(IMMUTABLE_CACHE_CONTROL)
+    );
+}
+
+#[test]
+fn test_respond_rejects_tampered_content() {
+    let metadata = metadata(DataTypeOption::HTML, b"<p>hi</p>")
+        .try_to_vec()
+        .unwrap();
+    let error = respond(&metadata, b"<p>ho</p>", None).unwrap_err();
+    assert_eq!(error, GatewayError::ContentHashMismatch);
+    assert_eq!(error.status(), 502);
+}
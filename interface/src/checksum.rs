@@ -0,0 +1,41 @@
+//! CRC32 (IEEE) checksum of the content stored in the metadata after every UpdateDataAccount
+//! so that clients can confirm the integrity of a data account without downloading its content
+
+/// Reversed IEEE polynomial (as used by zlib, PNG, etc.)
+const POLYNOMIAL: u32 = 0xedb8_8320;
+
+/// Checksum of every byte value, computed at compile time
+const TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Continues the checksum of the bytes preceding data (0 if there are none) over data
+/// i.e., crc32_update(crc32(a), b) == crc32(a ++ b), so appends do not rehash the content
+pub fn crc32_update(checksum: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!checksum, |crc, byte| {
+        TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Computes the checksum of data
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
@@ -0,0 +1,53 @@
+//! Addresses and leaf hashes of data accounts bound to a compressed NFT (Bubblegum)
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{keccak, pubkey, pubkey::Pubkey};
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const CNFT_SEED: &[u8] = b"data_account_cnft";
+pub const ASSET_SEED: &[u8] = b"asset";
+
+/// Bubblegum leaf schema version hashed into every leaf
+const LEAF_VERSION_V1: u8 = 1;
+
+/// Leaf of a compressed NFT as returned by the DAS getAsset and getAssetProof APIs
+/// The owner is not part of it since it is always the signer claiming the cNFT
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct CnftLeaf {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub delegate: Pubkey,
+}
+
+/// Get the asset id of the cNFT minted into the merkle tree with the nonce
+pub fn cnft_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ASSET_SEED, merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Get the data account pda bound to the asset id
+pub fn cnft_data_address(asset_id: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CNFT_SEED, asset_id.as_ref()], program_id).0
+}
+
+/// Computes the Bubblegum (V1) leaf hash of the cNFT owned by the owner
+pub fn leaf_hash(asset_id: &Pubkey, owner: &Pubkey, leaf: &CnftLeaf) -> [u8; 32] {
+    keccak::hashv(&[
+        &[LEAF_VERSION_V1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        leaf.delegate.as_ref(),
+        &leaf.nonce.to_le_bytes(),
+        &leaf.data_hash,
+        &leaf.creator_hash,
+    ])
+    .to_bytes()
+}
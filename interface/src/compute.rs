@@ -0,0 +1,68 @@
+use solana_program::entrypoint::ProgramResult;
+
+use crate::error::DataAccountError;
+
+/// Compute units kept in reserve for the rest of the instruction (e.g., serializing the metadata)
+pub const COMPUTE_MARGIN: u64 = 5_000;
+/// Base cost of a memory syscall (e.g., memcpy)
+const MEM_OP_BASE_COST: u64 = 10;
+/// Bytes copied per compute unit by a memory syscall
+const MEM_OP_BYTES_PER_UNIT: u64 = 250;
+/// Base cost of the sha256 syscall
+const SHA256_BASE_COST: u64 = 85;
+/// Compute units per byte of the sha256 computed by the program (see sha256.rs), i.e., without the syscall
+const SOFTWARE_SHA256_COST_PER_BYTE: u64 = 50;
+/// Base cost of the crc32 checksum
+const CHECKSUM_BASE_COST: u64 = 10;
+/// Compute units per byte of the crc32 checksum
+const CHECKSUM_COST_PER_BYTE: u64 = 8;
+
+#[cfg(all(target_os = "solana", feature = "remaining-compute-units"))]
+extern "C" {
+    fn sol_remaining_compute_units() -> u64;
+}
+
+/// Get the compute units left in the instruction
+/// The syscall is only linked with the remaining-compute-units feature since runtimes without it reject the program
+#[cfg(all(target_os = "solana", feature = "remaining-compute-units"))]
+pub fn remaining_compute_units() -> Option<u64> {
+    Some(unsafe { sol_remaining_compute_units() })
+}
+
+/// Get the compute units left in the instruction
+/// The syscall is only linked with the remaining-compute-units feature since runtimes without it reject the program
+#[cfg(not(all(target_os = "solana", feature = "remaining-compute-units")))]
+pub fn remaining_compute_units() -> Option<u64> {
+    None
+}
+
+/// Estimated compute units to copy len bytes
+pub fn copy_cost(len: usize) -> u64 {
+    MEM_OP_BASE_COST.max(len as u64 / MEM_OP_BYTES_PER_UNIT)
+}
+
+/// Estimated compute units to sha256 (or keccak256) hash len bytes
+pub fn hash_cost(len: usize) -> u64 {
+    SHA256_BASE_COST.saturating_add(len as u64 / 2)
+}
+
+/// Estimated compute units to sha256 hash len bytes in the program (e.g., a window of VerifyDataStep)
+pub fn software_hash_cost(len: usize) -> u64 {
+    SHA256_BASE_COST.saturating_add((len as u64).saturating_mul(SOFTWARE_SHA256_COST_PER_BYTE))
+}
+
+/// Estimated compute units to crc32 checksum len bytes (a table lookup, shift and xor per byte)
+pub fn checksum_cost(len: usize) -> u64 {
+    CHECKSUM_BASE_COST.saturating_add((len as u64).saturating_mul(CHECKSUM_COST_PER_BYTE))
+}
+
+/// Ensures the instruction has enough compute left for the estimated cost and the margin
+/// Passes if the remaining compute units cannot be read
+pub fn check_compute(estimated_cost: u64) -> ProgramResult {
+    match remaining_compute_units() {
+        Some(remaining) if remaining < estimated_cost.saturating_add(COMPUTE_MARGIN) => {
+            Err(DataAccountError::InsufficientCompute.into())
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,124 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+pub const MAX_CRDT_ELEMENT_LEN: usize = 64;
+
+/// Kind of conflict-free replicated payload stored in a CRDT data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum CrdtKindOption {
+    GSET,
+    PNCOUNTER,
+}
+
+/// Increments and decrements contributed by a single writer of a PN-counter
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct CounterEntry {
+    pub writer: Pubkey,
+    pub increments: u64,
+    pub decrements: u64,
+}
+
+/// Payload of a CRDT data account
+/// Elements and entries are kept sorted so the encoding does not depend on the order of the deltas
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum CrdtState {
+    GSet(Vec<Vec<u8>>),
+    PNCounter(Vec<CounterEntry>),
+}
+
+/// Delta merged into a CRDT payload by ApplyDelta
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum CrdtDelta {
+    /// adds the elements to a grow-only set
+    Add(Vec<Vec<u8>>),
+    /// adds to the increments of the writer in a PN-counter
+    Increment(u64),
+    /// adds to the decrements of the writer in a PN-counter
+    Decrement(u64),
+}
+
+impl CrdtState {
+    pub fn new(kind: &CrdtKindOption) -> Self {
+        match kind {
+            CrdtKindOption::GSET => CrdtState::GSet(vec![]),
+            CrdtKindOption::PNCOUNTER => CrdtState::PNCounter(vec![]),
+        }
+    }
+
+    /// Merges the delta of the writer into the payload
+    /// Returns false if the delta does not match the kind of the payload or overflows
+    pub fn apply(&mut self, writer: &Pubkey, delta: CrdtDelta) -> bool {
+        match (self, delta) {
+            (CrdtState::GSet(elements), CrdtDelta::Add(added)) => {
+                if added
+                    .iter()
+                    .any(|element| element.is_empty() || element.len() > MAX_CRDT_ELEMENT_LEN)
+                {
+                    return false;
+                }
+                for element in added {
+                    if let Err(idx) = elements.binary_search(&element) {
+                        elements.insert(idx, element);
+                    }
+                }
+                true
+            }
+            (CrdtState::PNCounter(entries), CrdtDelta::Increment(amount)) => {
+                let entry = Self::counter_entry(entries, writer);
+                match entry.increments.checked_add(amount) {
+                    Some(increments) => {
+                        entry.increments = increments;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            (CrdtState::PNCounter(entries), CrdtDelta::Decrement(amount)) => {
+                let entry = Self::counter_entry(entries, writer);
+                match entry.decrements.checked_add(amount) {
+                    Some(decrements) => {
+                        entry.decrements = decrements;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Value of a PN-counter i.e., the sum of all increments minus the sum of all decrements
+    pub fn value(&self) -> Option<i128> {
+        match self {
+            CrdtState::PNCounter(entries) => Some(
+                entries
+                    .iter()
+                    .map(|entry| entry.increments as i128 - entry.decrements as i128)
+                    .sum(),
+            ),
+            CrdtState::GSet(_) => None,
+        }
+    }
+
+    /// Returns the entry of the writer, inserting an empty one in order if needed
+    fn counter_entry<'a>(
+        entries: &'a mut Vec<CounterEntry>,
+        writer: &Pubkey,
+    ) -> &'a mut CounterEntry {
+        let idx = match entries.binary_search_by(|entry| entry.writer.cmp(writer)) {
+            Ok(idx) => idx,
+            Err(idx) => {
+                entries.insert(
+                    idx,
+                    CounterEntry {
+                        writer: *writer,
+                        increments: 0,
+                        decrements: 0,
+                    },
+                );
+                idx
+            }
+        };
+        &mut entries[idx]
+    }
+}
@@ -0,0 +1,179 @@
+use num_derive::FromPrimitive;
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Copy, Clone, FromPrimitive, PartialEq)]
+pub enum DataAccountError {
+    #[error("Instruction not implemented.")]
+    NotImplemented,
+    #[error("Account should be writeable")]
+    NotWriteable,
+    #[error("Account should not have 0 length data")]
+    NoAccountLength,
+    #[error("Account should not have non-zero data")]
+    NonZeroData,
+    #[error("Account should be signer")]
+    NotSigner,
+    #[error("Account should be valid system program")]
+    InvalidSysProgram,
+    #[error("Account should be valid owner of data account")]
+    InvalidAuthority,
+    #[error("Account should be PDA of data account")]
+    InvalidPDA,
+    #[error("Cannot reinitialize previously initialized data account")]
+    AlreadyInitialized,
+    #[error("Data account should be initialized")]
+    NotInitialized,
+    #[error("Operation overflowed")]
+    Overflow,
+    #[error("Data account should have sufficient space")]
+    InsufficientSpace,
+    #[error("Data account name should be between 1 and 32 bytes")]
+    InvalidName,
+    #[error("Account should be valid user of data account")]
+    InvalidUser,
+    #[error("Data account should have a matching data_type")]
+    InvalidDataType,
+    #[error("Merkle proof should be valid for the allowlist root")]
+    InvalidProof,
+    #[error("Account should be a supported price feed")]
+    InvalidFeed,
+    #[error("Staging pda should have queued writes")]
+    EmptyStaging,
+    #[error("Hot region should be within the first 128 bytes of the data account")]
+    InvalidHotRegion,
+    #[error("Data account should not be archived")]
+    Archived,
+    #[error("Archive pointer should be at most 96 bytes")]
+    InvalidPointer,
+    #[error("Data account should be archived")]
+    NotArchived,
+    #[error("Content should match the archived hash")]
+    HashMismatch,
+    #[error("Tag label should be between 1 and 32 bytes and not already used")]
+    InvalidTag,
+    #[error("Tags table should have fewer than 32 tags")]
+    TooManyTags,
+    #[error("Authority should be the governance or its native treasury")]
+    InvalidGovernance,
+    #[error("Account should be valid automation delegate of data account")]
+    InvalidDelegate,
+    #[error("Scheduled write should respect the minimum interval")]
+    RateLimited,
+    #[error("Account should be owned by the data program")]
+    InvalidOwner,
+    #[error("Data account should have the expected data_version")]
+    InvalidVersion,
+    #[error("Data account should be committed")]
+    NotCommitted,
+    #[error("Account should be valid successor of data account")]
+    InvalidSuccessor,
+    #[error("Authority should be inactive for the whole inactivity timeout")]
+    NotDormant,
+    #[error(
+        "Drop box should start after the content and messages should be within its max length"
+    )]
+    InvalidDropBox,
+    #[error("Write should not touch the append-only drop box region")]
+    DropBoxRegion,
+    #[error("Donation should be non-zero")]
+    InvalidDonation,
+    #[error("Data account content should not be sealed")]
+    Sealed,
+    #[error("Data account content should be sealed and past its reveal slot")]
+    NotRevealable,
+    #[error("Commitment should be unrevealed and before its reveal deadline")]
+    InvalidCommitment,
+    #[error("Commitment should be unrevealed past its reveal deadline")]
+    NotExpired,
+    #[error("License should be between 1 and 64 bytes")]
+    InvalidLicense,
+    #[error("Metadata tree should have a depth between 1 and 24 and free leaves")]
+    InvalidTree,
+    #[error("Metadata proof should be valid for the latest root of the metadata tree")]
+    InvalidMetadataProof,
+    #[error("Instruction should have enough compute units left for the write")]
+    InsufficientCompute,
+    #[error("Session should have between 1 and 8 ranges, at least one instruction and expire in the future")]
+    InvalidSessionScope,
+    #[error(
+        "Session should be unexpired and scoped to the instruction and byte range of the write"
+    )]
+    InvalidSession,
+    #[error("Delta should match the kind of the CRDT payload and not overflow")]
+    InvalidDelta,
+    #[error("Data account should be unlocked for at least the cool-down of its close lock")]
+    CloseLocked,
+    #[error("Close lock cool-down should be at least 1 slot and not lower than the current one")]
+    InvalidCloseLock,
+    #[error("Data account should not be finalized")]
+    Finalized,
+    #[error("Upload should be in progress with chunks appended in order within its total length")]
+    InvalidUpload,
+    #[error("Uploaded content should match the checksum of the upload")]
+    UploadChecksumMismatch,
+    #[error("Data account should have a content_hash of uncompressed content to verify against")]
+    NotVerifiable,
+    #[error("Data account is append-only so its content should only be extended")]
+    AppendOnly,
+    #[error("Sequenced writer should write with a sequence number greater than its latest one")]
+    StaleSequence,
+    #[error("Data account should have at most MAX_SEQUENCED_WRITERS sequenced writers")]
+    TooManyWriters,
+    #[error("Mint should be an NFT i.e., an SPL token mint with no decimals and a supply of 1")]
+    InvalidNftMint,
+    #[error("Token account should hold the NFT of the data account for the signer")]
+    NotTokenHolder,
+    #[error("Data account should be bound to an NFT")]
+    NotNftBound,
+    #[error("Data account should have at most MAX_WRITERS writers")]
+    WriterListFull,
+    #[error("Writer should not be listed yet when added and should be listed when removed")]
+    InvalidWriter,
+    #[error("Rent receiver should not be the data account or its pda")]
+    InvalidRentReceiver,
+    #[error(
+        "Merkle tree should be an account compression tree holding the cNFT of the data account"
+    )]
+    InvalidMerkleTree,
+    #[error("Data account should have an expiry that has passed")]
+    ExpiryNotReached,
+    #[error("Data account should keep its content and grow by at most MAX_PERMITTED_DATA_INCREASE when resized")]
+    InvalidResize,
+    #[error("Compressed data should be raw deflate expanding to at most max_output_len (up to MAX_DECOMPRESSED_LEN) bytes")]
+    InvalidCompressedData,
+    #[error("Scrubbed range should be non-empty and within the content")]
+    InvalidScrubRange,
+    #[error("Data status should only change along the allowed transitions")]
+    InvalidStatusTransition,
+    #[error("Metadata should be migrated to the current data_version with MigrateMetadata")]
+    OutdatedMetadata,
+    #[error("Attested hash should be the sha256 hash of the content")]
+    AttestationMismatch,
+    #[error("Update segments should be at most MAX_UPDATE_SEGMENTS non-empty writes that do not overlap, totalling at most MAX_PERMITTED_DATA_INCREASE bytes")]
+    InvalidSegments,
+    #[error("Data account should not be frozen by its freeze authority")]
+    Frozen,
+    #[error("Freeze authority should be set and sign to freeze or thaw the data account")]
+    InvalidFreezeAuthority,
+    #[error("Compression dictionary should be a finalized DICTIONARY data account of the same authority")]
+    InvalidDictionary,
+    #[error(
+        "Verification window should be a non-zero multiple of the sha256 block length (64 bytes)"
+    )]
+    InvalidVerificationWindow,
+    #[error("Staged buffer pda should have been written with WriteStaged")]
+    EmptyStagedBuffer,
+    #[error("Expected revision should match the revision of the content")]
+    StaleRevision,
+    #[error("Write delegate should expire after the current slot and be set to be revoked")]
+    InvalidWriteDelegate,
+    #[error("Donations pda and its top donors should be passed to refund the unspent donations")]
+    UnrefundedDonations,
+}
+
+impl From<DataAccountError> for ProgramError {
+    fn from(e: DataAccountError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,53 @@
+//! Structured events emitted via sol_log_data so indexers can key off them instead of msg! strings
+//! Every event is logged as the EVENT_TAG followed by the borsh encoding of the DataAccountEvent,
+//! which off-chain consumers decode with DataAccountEvent::decode from the fields of a "Program data:" log
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+
+pub const EVENT_TAG: &[u8] = b"event";
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum DataAccountEvent {
+    Initialized {
+        data_account: Pubkey,
+        authority: Pubkey,
+        space: u64,
+    },
+    Updated {
+        data_account: Pubkey,
+        offset: u64,
+        len: u64,
+        /// revision of the content after the update (see DataAccountMetadata::revision)
+        revision: u64,
+    },
+    Finalized {
+        data_account: Pubkey,
+    },
+    Closed {
+        data_account: Pubkey,
+        rent_receiver: Pubkey,
+    },
+    AuthorityChanged {
+        data_account: Pubkey,
+        old_authority: Pubkey,
+        new_authority: Pubkey,
+    },
+}
+
+impl DataAccountEvent {
+    /// Logs the event via sol_log_data
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[EVENT_TAG, &self.try_to_vec()?]);
+        Ok(())
+    }
+
+    /// Decodes the event from the fields of a sol_log_data log
+    /// Returns None if the fields are not an event (e.g., an audit event)
+    pub fn decode(fields: &[&[u8]]) -> Option<Self> {
+        match fields {
+            [tag, event] if *tag == EVENT_TAG => Self::try_from_slice(event).ok(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,775 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankInstruction;
+
+use crate::state::{
+    AddWriterArgs, AppendChunkArgs, AppendFramedRecordArgs, ApplyDeltaArgs, ArchiveDataAccountArgs,
+    AttestArgs, AutomatedUpdateArgs, BeginUploadArgs, ClaimDormantAuthorityArgs,
+    CloneDataAccountArgs, CloseCnftDataAccountArgs, CloseCompressedDataAccountArgs,
+    CloseDataAccountArgs, CloseNftDataAccountArgs, CommitStagedArgs, CommitUploadArgs,
+    CommitValueArgs, CreateSessionArgs, DelegateWriteAuthorityArgs, DonateArgs,
+    ExpireCommitmentArgs, ExpireDataAccountArgs, FinalizeDataAccountArgs, FlushStagingArgs,
+    FreezeDataAccountArgs, InitializeCnftDataAccountArgs, InitializeCompressedDataAccountArgs,
+    InitializeCrdtArgs, InitializeDataAccountArgs, InitializeDataAccountPdaArgs,
+    InitializeMetadataTreeArgs, InitializeNftDataAccountArgs, InitializeUserDataAccountArgs,
+    LockCloseArgs, MigrateMetadataArgs, PostToDropBoxArgs, RemoveWriterArgs, ResizeDataAccountArgs,
+    RestoreFromArchiveArgs, RevealContentArgs, RevealValueArgs, RevokeDelegateArgs,
+    RevokeSessionArgs, ScrubRangeArgs, SealContentArgs, SetAllowlistRootArgs, SetAutomationArgs,
+    SetCompressionDictionaryArgs, SetDeadManSwitchArgs, SetDropBoxArgs, SetExpiryArgs,
+    SetFreezeAuthorityArgs, SetGovernanceArgs, SetHotRegionArgs, SetLicenseArgs,
+    SetNotificationHintArgs, SnapshotPriceFeedArgs, StageUpdateArgs, TagRevisionArgs,
+    ThawDataAccountArgs, TopUpRentArgs, UnlockCloseArgs, UpdateCnftDataAccountArgs,
+    UpdateCompressedDataAccountArgs, UpdateDataAccountArgs, UpdateDataAccountAuthorityArgs,
+    UpdateDataAccountCompressedArgs, UpdateNftDataAccountArgs, ValidateDataAccountArgs,
+    VerifyAllowlistInclusionArgs, VerifyDataAccountArgs, VerifyDataStepArgs, WriteStagedArgs,
+};
+
+/// Instructions supported by the Data program.
+#[derive(BorshSerialize, BorshDeserialize, Clone, ShankInstruction)]
+pub enum DataAccountInstruction {
+    /// This instruction initializes a data account that is accessible by the authority.
+    /// This also sets the owner of the data account to be the data program
+    /// If a data account was already initialized for given user, it returns Error
+    #[account(0, signer, writable, name = "feepayer", desc = "Feepayer account")]
+    #[account(1, signer, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitializeDataAccount(InitializeDataAccountArgs),
+
+    /// This instruction updates the data of the data account corresponding to the authority
+    /// Allows user to specify whether the data should be committed or verified
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        optional,
+        name = "session",
+        desc = "Session pda of the signer if it is a session key"
+    )]
+    UpdateDataAccount(UpdateDataAccountArgs),
+
+    /// This instruction updates the authority of the data account
+    /// If the data account requires user consent, the user must co-sign
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "old_authority", desc = "Old Authority")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, signer, name = "new_authority", desc = "New Authority")]
+    #[account(4, optional, signer, name = "user", desc = "User of the data account")]
+    UpdateDataAccountAuthority(UpdateDataAccountAuthorityArgs),
+
+    /// This instruction unlinks the data account corresponding to the authority
+    /// The lamports of the data account and its pda are refunded to the rent_receiver (e.g., the authority itself)
+    /// The user of a per-user data account can also close it in place of the authority
+    /// If the data account requires user consent, the user must co-sign
+    /// If the data account is close locked, it must have been unlocked for at least the cool-down
+    /// If the data account received donations, the donations pda and its top donors follow as remaining accounts
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(
+        3,
+        writable,
+        name = "rent_receiver",
+        desc = "Account refunded the lamports"
+    )]
+    #[account(4, optional, signer, name = "user", desc = "User of the data account")]
+    CloseDataAccount(CloseDataAccountArgs),
+
+    /// This instruction initializes a per-user data account that is a pda derived from [app_authority, user, name]
+    /// The app_authority controls writes while the user can always close it and reclaim the lamports
+    /// If a data account was already initialized for given app_authority, user and name, it returns Error
+    #[account(0, signer, writable, name = "feepayer", desc = "Feepayer account")]
+    #[account(1, signer, name = "app_authority", desc = "App Authority")]
+    #[account(2, writable, name = "data", desc = "Data account pda")]
+    #[account(3, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    InitializeUserDataAccount(InitializeUserDataAccountArgs),
+
+    /// This instruction stores the merkle root of an allowlist in the data account
+    /// It also sets the data_type of the data account to ALLOWLIST
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    SetAllowlistRoot(SetAllowlistRootArgs),
+
+    /// This instruction verifies that an entry is included in the allowlist of the data account
+    /// It returns Error if the merkle proof does not match the stored root so it can be used via CPI
+    /// Requires data account to be an initialized ALLOWLIST
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, name = "pda", desc = "Data account pda")]
+    VerifyAllowlistInclusion(VerifyAllowlistInclusionArgs),
+
+    /// This instruction copies a window of a Pyth or Switchboard feed account into the data account
+    /// The snapshot is prefixed with the current slot, timestamp and feed and written at the given offset
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "feed", desc = "Price feed account")]
+    #[account(4, name = "system_program", desc = "System program")]
+    SnapshotPriceFeed(SnapshotPriceFeedArgs),
+
+    /// This instruction queues a write in the staging pda of the data account without touching the data account
+    /// The staging pda is created (and grown) as needed and funded by the authority
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "staging", desc = "Data account staging pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(
+        5,
+        optional,
+        name = "session",
+        desc = "Session pda of the signer if it is a session key"
+    )]
+    StageUpdate(StageUpdateArgs),
+
+    /// This instruction applies all queued writes of the staging pda to the data account in order
+    /// The staging pda is closed and its lamports are returned to the authority
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "staging", desc = "Data account staging pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    FlushStaging(FlushStagingArgs),
+
+    /// This instruction designates (or clears) the hot region of the data account
+    /// The hot region must lie within the first 128 bytes so consumers can read it with a single small slice
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetHotRegion(SetHotRegionArgs),
+
+    /// This instruction closes the data account but keeps the metadata as an archival stub
+    /// The stub records the hash and size of the content, the archival slot and an optional off-chain pointer
+    /// If the data account requires user consent, the user must co-sign
+    /// If the data account is close locked, it must have been unlocked for at least the cool-down
+    /// If the data account received donations, the donations pda and its top donors follow as remaining accounts
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, signer, name = "user", desc = "User of the data account")]
+    ArchiveDataAccount(ArchiveDataAccountArgs),
+
+    /// This instruction restores an archived data account by re-uploading its content in chunks
+    /// The data account is recreated with the archived size on the first chunk (so it must sign then)
+    /// A data account that is a pda can't sign, so its seeds are passed instead for the program to sign for it
+    /// On finalize, the content must match the archived hash for the data account to become writable again
+    /// Requires data account to be archived previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    RestoreFromArchive(RestoreFromArchiveArgs),
+
+    /// This instruction tags the current content of the data account with a human-readable label
+    /// The tag records the slot, content length and sha256 hash in the tags pda of the data account
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "tags", desc = "Data account tags pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    TagRevision(TagRevisionArgs),
+
+    /// This instruction enables (or disables) the governance-gated mode of the data account
+    /// The authority must be the SPL Governance account or its native treasury so that it can only sign via proposals
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetGovernance(SetGovernanceArgs),
+
+    /// This instruction sets (or clears) the automation delegate allowed to write on a schedule
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetAutomation(SetAutomationArgs),
+
+    /// This instruction lets the automation delegate write within the current length of the data account
+    /// It is rate limited by the minimum interval and records the heartbeat slot
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "delegate", desc = "Automation delegate")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    AutomatedUpdate(AutomatedUpdateArgs),
+
+    /// This instruction only validates the ownership, pda, version and (optionally) commitment of the data account
+    /// It never reads the data and returns the serialized metadata via return data for cheap CPI checks
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, name = "pda", desc = "Data account pda")]
+    ValidateDataAccount(ValidateDataAccountArgs),
+
+    /// This instruction sets (or clears) a successor that can claim the authority after a window of inactivity
+    /// Calling it again also marks the authority as active
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetDeadManSwitch(SetDeadManSwitchArgs),
+
+    /// This instruction lets the successor claim the authority once the authority is dormant
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "successor", desc = "Successor account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    ClaimDormantAuthority(ClaimDormantAuthorityArgs),
+
+    /// This instruction enables (or disables) the public drop box of the data account
+    /// Once enabled, the region from the drop box start can only be appended to via PostToDropBox
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetDropBox(SetDropBoxArgs),
+
+    /// This instruction lets anyone append a message to the drop box region paying the fee to the authority
+    /// Each sender is rate limited via a sender pda derived off of the data account
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "sender", desc = "Sender account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "sender_pda", desc = "Drop box sender pda")]
+    #[account(
+        4,
+        writable,
+        name = "authority",
+        desc = "Authority account receiving the fee"
+    )]
+    #[account(5, name = "system_program", desc = "System program")]
+    PostToDropBox(PostToDropBoxArgs),
+
+    /// This instruction lets anyone donate lamports to the data account toward its future rent and growth
+    /// The total and the top donors are recorded in a donations pda derived off of the data account
+    /// The unspent donated lamports are recorded in the metadata and are refunded to the top donors on close
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "donor", desc = "Donor account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "donations", desc = "Data account donations pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    Donate(DonateArgs),
+
+    /// This instruction seals the current (e.g., encrypted) content until the reveal slot
+    /// Sealed content cannot be written to until it is revealed
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SealContent(SealContentArgs),
+
+    /// This instruction swaps the sealed content with its plaintext after the reveal slot
+    /// Anyone can reveal if the plaintext hash was committed, otherwise only the authority
+    /// Requires data account to be sealed previously
+    #[account(0, signer, writable, name = "revealer", desc = "Revealer account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    RevealContent(RevealContentArgs),
+
+    /// This instruction records a commitment (hash of a salted value) with a reveal deadline
+    /// The commitment is stored in a commitment pda derived off of the data account and the committer
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "committer", desc = "Committer account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "commitment", desc = "Commitment pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    CommitValue(CommitValueArgs),
+
+    /// This instruction verifies the value and salt against the commitment and writes the value to it
+    /// Requires the commitment to be made previously and its reveal deadline to not have passed
+    #[account(0, signer, writable, name = "committer", desc = "Committer account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "commitment", desc = "Commitment pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    RevealValue(RevealValueArgs),
+
+    /// This instruction lets anyone close a commitment that missed its reveal deadline
+    /// The lamports of the commitment pda are forfeited to the authority
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, name = "pda", desc = "Data account pda")]
+    #[account(2, writable, name = "commitment", desc = "Commitment pda")]
+    #[account(3, writable, name = "authority", desc = "Authority account")]
+    ExpireCommitment(ExpireCommitmentArgs),
+
+    /// This instruction sets (or clears) the license (SPDX id or pointer to terms) of the content
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetLicense(SetLicenseArgs),
+
+    /// This instruction creates the shared metadata tree of an operator for compressed data accounts
+    #[account(0, signer, writable, name = "operator", desc = "Operator account")]
+    #[account(1, writable, name = "tree", desc = "Operator metadata tree pda")]
+    #[account(2, name = "system_program", desc = "System program")]
+    InitializeMetadataTree(InitializeMetadataTreeArgs),
+
+    /// This instruction creates a data account whose metadata is a leaf of the operator's metadata tree
+    /// The leaf is logged so that indexers can serve proofs for later updates
+    #[account(0, signer, writable, name = "operator", desc = "Operator account")]
+    #[account(1, signer, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "tree", desc = "Operator metadata tree pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitializeCompressedDataAccount(InitializeCompressedDataAccountArgs),
+
+    /// This instruction updates a compressed data account given its metadata and a proof against the latest root
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "tree", desc = "Operator metadata tree pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    UpdateCompressedDataAccount(UpdateCompressedDataAccountArgs),
+
+    /// This instruction closes a compressed data account and empties its leaf in the metadata tree
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "tree", desc = "Operator metadata tree pda")]
+    CloseCompressedDataAccount(CloseCompressedDataAccountArgs),
+
+    /// This instruction creates a session pda that lets an ephemeral session key sign writes until it expires
+    /// The session is limited to the given byte ranges and instructions (UpdateDataAccount and StageUpdate)
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "session", desc = "Data account session pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    CreateSession(CreateSessionArgs),
+
+    /// This instruction closes a session pda and returns its lamports to the authority
+    /// It can be signed by the authority or the session key, or by anyone once the session has expired
+    #[account(
+        0,
+        signer,
+        name = "signer",
+        desc = "Authority, session key or anyone once expired"
+    )]
+    #[account(1, writable, name = "authority", desc = "Authority account")]
+    #[account(2, name = "data", desc = "Data account data")]
+    #[account(3, name = "pda", desc = "Data account pda")]
+    #[account(4, writable, name = "session", desc = "Data account session pda")]
+    RevokeSession(RevokeSessionArgs),
+
+    /// This instruction (experimental) turns the data account into a CRDT whose payload is an empty grow-only set or PN-counter
+    /// It also sets the data_type of the data account to CRDT and discards the previous content
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitializeCrdt(InitializeCrdtArgs),
+
+    /// This instruction (experimental) merges a delta into the CRDT payload of the data account
+    /// Deltas commute so the authority and its session keys can apply them concurrently without lost updates
+    /// Requires data account to be an initialized CRDT
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "writer",
+        desc = "Authority or session key"
+    )]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        optional,
+        name = "session",
+        desc = "Session pda of the signer if it is a session key"
+    )]
+    ApplyDelta(ApplyDeltaArgs),
+
+    /// This instruction protects the data account from being closed until it is unlocked and the cool-down has passed
+    /// Locking again resets a previous unlock and cannot lower the cool-down
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    LockClose(LockCloseArgs),
+
+    /// This instruction starts the cool-down after which a close locked data account can be closed
+    /// It can be signed by the authority or the user of a per-user data account
+    /// Requires data account to be close locked
+    #[account(0, signer, name = "authority", desc = "Authority or user account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    UnlockClose(UnlockCloseArgs),
+
+    /// This instruction finalizes the data account so that its content can no longer be written to
+    /// Finalizing cannot be undone and requires the content not to be sealed
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    FinalizeDataAccount(FinalizeDataAccountArgs),
+
+    /// This instruction appends a length-prefixed record after the content and increments the record count
+    /// If the data account is set to be dynamic, it grows as per the growth policy
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    AppendFramedRecord(AppendFramedRecordArgs),
+
+    /// This instruction starts a chunked upload of the given total length and sha256 checksum
+    /// Beginning again discards the progress of the previous upload
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    BeginUpload(BeginUploadArgs),
+
+    /// This instruction writes the next chunk of the upload in progress
+    /// If the data account is set to be dynamic, it grows as per the growth policy
+    /// Requires an upload to be in progress
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    AppendChunk(AppendChunkArgs),
+
+    /// This instruction verifies the checksum of the completed upload and makes it the content
+    /// Requires all the chunks of the upload in progress to have been appended
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    CommitUpload(CommitUploadArgs),
+
+    /// This instruction hashes the content on-chain and compares it against the content_hash in the metadata
+    /// It sets the serialization status to VERIFIED if they match and to FAILED otherwise
+    /// Requires data account to have a content_hash of uncompressed content
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, writable, name = "pda", desc = "Data account pda")]
+    VerifyDataAccount(VerifyDataAccountArgs),
+
+    /// This instruction initializes a data account bound to an NFT mint i.e., the pda derived from the mint
+    /// Whoever holds the NFT can write to it with UpdateNftDataAccount
+    /// If a data account was already initialized for the mint, it returns Error
+    #[account(0, signer, writable, name = "holder", desc = "Holder of the NFT")]
+    #[account(1, name = "token_account", desc = "Token account of the holder")]
+    #[account(2, name = "mint", desc = "NFT mint")]
+    #[account(3, writable, name = "data", desc = "Data account pda")]
+    #[account(4, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    InitializeNftDataAccount(InitializeNftDataAccountArgs),
+
+    /// This instruction lets the current holder of the NFT write data starting at a particular offset
+    /// Requires data account to be bound to an NFT
+    #[account(0, signer, writable, name = "holder", desc = "Holder of the NFT")]
+    #[account(1, name = "token_account", desc = "Token account of the holder")]
+    #[account(2, writable, name = "data", desc = "Data account pda")]
+    #[account(3, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    UpdateNftDataAccount(UpdateNftDataAccountArgs),
+
+    /// This instruction lets the authority allow an additional account to sign UpdateDataAccount
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    AddWriter(AddWriterArgs),
+
+    /// This instruction lets the authority revoke the write access of an additional account
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    RemoveWriter(RemoveWriterArgs),
+
+    /// This instruction initializes a data account bound to a compressed NFT i.e., the pda derived from its asset id
+    /// The owner of the cNFT is verified against the merkle tree with the proof nodes passed as remaining accounts
+    /// Whoever owns the cNFT can write to it with UpdateCnftDataAccount
+    /// If a data account was already initialized for the asset id, it returns Error
+    #[account(0, signer, writable, name = "owner", desc = "Owner of the cNFT")]
+    #[account(1, name = "merkle_tree", desc = "Merkle tree of the cNFT")]
+    #[account(2, name = "compression_program", desc = "Account compression program")]
+    #[account(3, writable, name = "data", desc = "Data account pda")]
+    #[account(4, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    InitializeCnftDataAccount(InitializeCnftDataAccountArgs),
+
+    /// This instruction lets the current owner of the cNFT write data starting at a particular offset
+    /// The owner of the cNFT is verified against the merkle tree with the proof nodes passed as remaining accounts
+    /// Requires data account to be bound to a cNFT
+    #[account(0, signer, writable, name = "owner", desc = "Owner of the cNFT")]
+    #[account(1, name = "merkle_tree", desc = "Merkle tree of the cNFT")]
+    #[account(2, name = "compression_program", desc = "Account compression program")]
+    #[account(3, writable, name = "data", desc = "Data account pda")]
+    #[account(4, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    UpdateCnftDataAccount(UpdateCnftDataAccountArgs),
+
+    /// This instruction lets the authority set (or clear) the expiry of the data account
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetExpiry(SetExpiryArgs),
+
+    /// This instruction lets anyone close the data account once its expiry has passed
+    /// The lamports of the data account and its pda are refunded to the pre-configured rent_receiver
+    /// If the data account requires user consent, the user must co-sign
+    /// If the data account received donations, the donations pda and its top donors follow as remaining accounts
+    /// Requires data account to have an expiry
+    #[account(0, writable, name = "data", desc = "Data account data")]
+    #[account(1, writable, name = "pda", desc = "Data account pda")]
+    #[account(
+        2,
+        writable,
+        name = "rent_receiver",
+        desc = "Rent receiver of the expiry"
+    )]
+    #[account(3, optional, signer, name = "user", desc = "User of the data account")]
+    ExpireDataAccount(ExpireDataAccountArgs),
+
+    /// This instruction lets the authority set (or clear) the notification hint (e.g., a URL hash or topic id)
+    /// that off-chain infrastructure uses to route change notifications of the data account
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetNotificationHint(SetNotificationHintArgs),
+
+    /// This instruction lets the authority grow or shrink the data account to an exact space
+    /// The authority funds the growth and is refunded the excess lamports on shrink
+    /// The content (and the hot region and drop box messages) can't be cut off
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    ResizeDataAccount(ResizeDataAccountArgs),
+
+    /// This instruction lets the same signers as UpdateDataAccount (the authority, its writers, its delegate or a
+    /// session key) write a raw deflate payload starting at a particular offset, which is expanded on-chain into at
+    /// most max_output_len bytes
+    /// The data_type is of the expanded content (e.g., JSON) and the content is stored uncompressed
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        optional,
+        name = "session",
+        desc = "Session pda of the signer if it is a session key"
+    )]
+    UpdateDataAccountCompressed(UpdateDataAccountCompressedArgs),
+
+    /// This instruction initializes a data account that is a pda derived from ["data_account_pda", authority, seed]
+    /// so that programs and sites get a deterministic, recoverable address without managing a keypair
+    /// If a data account was already initialized for given authority and seed, it returns Error
+    #[account(0, signer, writable, name = "feepayer", desc = "Feepayer account")]
+    #[account(1, signer, name = "authority", desc = "Authority account")]
+    #[account(2, writable, name = "data", desc = "Data account pda")]
+    #[account(3, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    InitializeDataAccountPda(InitializeDataAccountPdaArgs),
+
+    /// This instruction lets the authority overwrite a range of the content with zeros (or pseudo-random bytes)
+    /// to redact it without closing the data account, clearing the content hashes that no longer cover it
+    /// Emits the same audit event as a write along with a scrub event
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    ScrubRange(ScrubRangeArgs),
+
+    /// This instruction lets the authority rewrite metadata stored by an older release into the current layout,
+    /// growing the metadata pda as needed, so that the data account survives program upgrades
+    /// Does nothing if the metadata is already of the current data_version
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    MigrateMetadata(MigrateMetadataArgs),
+
+    /// This instruction lets the authority (or its automation delegate as a watcher service) re-assert
+    /// the sha256 hash of the content, recording the slot of the attestation so that lapsed attestations can be detected
+    /// Only the authority can set the maximum number of slots between two attestations
+    #[account(
+        0,
+        signer,
+        name = "attester",
+        desc = "Authority or automation delegate account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    Attest(AttestArgs),
+
+    /// This instruction lets the authority set (or clear) the freeze_authority, e.g. an operations key,
+    /// that can pause writes to the data account without being able to modify its content
+    /// Requires data account not to be frozen
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    SetFreezeAuthority(SetFreezeAuthorityArgs),
+
+    /// This instruction lets the freeze_authority freeze the data account so that its content can't be written
+    /// and the data account can't be closed until it is thawed
+    #[account(
+        0,
+        signer,
+        name = "freeze_authority",
+        desc = "Freeze authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    FreezeDataAccount(FreezeDataAccountArgs),
+
+    /// This instruction lets the freeze_authority thaw a frozen data account
+    #[account(
+        0,
+        signer,
+        name = "freeze_authority",
+        desc = "Freeze authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    ThawDataAccount(ThawDataAccountArgs),
+
+    /// This instruction creates and initializes a target data account holding a copy of the content, data_type
+    /// and compression of a source data account (e.g., a template of prefilled HTML or config) along with its
+    /// metadata pda, funded by the feepayer
+    /// Requires the content of the source data account not to be sealed
+    #[account(0, signer, writable, name = "feepayer", desc = "Feepayer account")]
+    #[account(1, name = "source_data", desc = "Source data account data")]
+    #[account(2, name = "source_pda", desc = "Source data account pda")]
+    #[account(3, signer, writable, name = "data", desc = "Target data account data")]
+    #[account(4, writable, name = "pda", desc = "Target data account pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    CloneDataAccount(CloneDataAccountArgs),
+
+    /// This instruction lets the authority set (or clear) the data account holding the shared zstd dictionary
+    /// its ZSTD compressed content is compressed with
+    /// The dictionary must be a finalized DICTIONARY data account of the same authority so it can never change
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(
+        3,
+        optional,
+        name = "dictionary_data",
+        desc = "Dictionary data account data (when setting)"
+    )]
+    #[account(
+        4,
+        optional,
+        name = "dictionary_pda",
+        desc = "Dictionary data account pda (when setting)"
+    )]
+    SetCompressionDictionary(SetCompressionDictionaryArgs),
+
+    /// This instruction hashes the next window of the content and sets the serialization_status once the whole
+    /// content is covered, so content too large for VerifyDataAccount can be verified over many transactions
+    /// The progress is kept in the metadata and dropped by any write of the content
+    /// Requires data account to have a content_hash of uncompressed content
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, writable, name = "pda", desc = "Data account pda")]
+    VerifyDataStep(VerifyDataStepArgs),
+
+    /// This instruction lets anyone transfer lamports into the data account up to its rent-exempt minimum
+    /// It does not grant any write access and does nothing if the data account is already rent-exempt
+    #[account(0, signer, writable, name = "payer", desc = "Payer of the top-up")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    TopUpRent(TopUpRentArgs),
+
+    /// This instruction writes data at offset of the staged buffer pda of the data account without touching the data account
+    /// The staged buffer is created (and resized) as needed and funded by the authority; reset starts it over
+    /// ending at the write, so readers keep seeing the current content until CommitStaged swaps it in
+    /// Requires data account to be initialized previously
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(
+        3,
+        writable,
+        name = "staged_buffer",
+        desc = "Data account staged buffer pda"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    WriteStaged(WriteStagedArgs),
+
+    /// This instruction atomically replaces the content with the staged buffer and bumps the generation
+    /// The staged buffer pda is closed and its lamports are returned to the authority
+    /// Requires data account to have a staged buffer
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(
+        3,
+        writable,
+        name = "staged_buffer",
+        desc = "Data account staged buffer pda"
+    )]
+    #[account(4, name = "system_program", desc = "System program")]
+    CommitStaged(CommitStagedArgs),
+
+    /// This instruction lets the authority allow a delegate to sign UpdateDataAccount until the expiry slot
+    /// It replaces any previous delegate, e.g. to extend the delegation of a bot maintaining a data feed
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    DelegateWriteAuthority(DelegateWriteAuthorityArgs),
+
+    /// This instruction lets the authority revoke the write access of the delegate before it expires
+    /// Requires data account to have a delegate
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    RevokeDelegate(RevokeDelegateArgs),
+
+    /// This instruction lets the current holder of the NFT close the data account bound to it
+    /// (which is its own authority and so cannot be closed with CloseDataAccount) and reclaim the lamports
+    /// If the data account is close locked, it must have been unlocked for at least the cool-down
+    /// If the data account received donations, the donations pda and its top donors follow as remaining accounts
+    /// Requires data account to be bound to an NFT
+    #[account(0, signer, name = "holder", desc = "Holder of the NFT")]
+    #[account(1, name = "token_account", desc = "Token account of the holder")]
+    #[account(2, writable, name = "data", desc = "Data account pda")]
+    #[account(3, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(
+        4,
+        writable,
+        name = "rent_receiver",
+        desc = "Account refunded the lamports"
+    )]
+    CloseNftDataAccount(CloseNftDataAccountArgs),
+
+    /// This instruction lets the current owner of the cNFT close the data account bound to it
+    /// (which is its own authority and so cannot be closed with CloseDataAccount) and reclaim the lamports
+    /// The owner of the cNFT is verified against the merkle tree with the proof nodes passed as remaining accounts
+    /// If the data account is close locked, it must have been unlocked for at least the cool-down
+    /// If the data account received donations, the donations pda and its top donors precede the proof nodes
+    /// Requires data account to be bound to a cNFT
+    #[account(0, signer, name = "owner", desc = "Owner of the cNFT")]
+    #[account(1, name = "merkle_tree", desc = "Merkle tree of the cNFT")]
+    #[account(2, name = "compression_program", desc = "Account compression program")]
+    #[account(3, writable, name = "data", desc = "Data account pda")]
+    #[account(4, writable, name = "pda", desc = "Data account metadata pda")]
+    #[account(
+        5,
+        writable,
+        name = "rent_receiver",
+        desc = "Account refunded the lamports"
+    )]
+    CloseCnftDataAccount(CloseCnftDataAccountArgs),
+}
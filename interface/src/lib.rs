@@ -0,0 +1,21 @@
+//! Instructions, account layouts and errors of the Data Program, without the program itself
+//! Clients depend on this crate to encode instructions and decode accounts without pulling the program's
+//! on-chain dependencies. It follows semver: changing the encoding of an existing instruction, account or
+//! error (e.g., reordering variants or fields) is a breaking change, while appending instructions, errors,
+//! data types or trailing metadata fields is not
+//! It has no entrypoint, so off-chain services can decode accounts (e.g., with VersionedMetadata::unpack) and
+//! other programs can link it without the `no-entrypoint` feature the program crate requires
+
+pub mod checksum;
+pub mod cnft;
+pub mod compute;
+pub mod crdt;
+pub mod error;
+pub mod events;
+pub mod instruction;
+pub mod manifest;
+pub mod merkle;
+pub mod nft;
+pub mod sha256;
+pub mod state;
+pub mod validation;
@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+
+pub const MAX_PATH_LEN: usize = 256;
+
+/// Verifies that the data is a DIRECTORY manifest of the form
+/// `{"files": {"/path": {"account": "<pubkey>", "hash": "<sha256 hex>", "mime": "<type/subtype>"}}}`
+pub fn is_valid_manifest(data: &[u8]) -> bool {
+    let manifest: Value = match serde_json::from_slice(data) {
+        Ok(manifest) => manifest,
+        Err(_) => return false,
+    };
+    match manifest.get("files").and_then(Value::as_object) {
+        Some(files) => {
+            !files.is_empty()
+                && files
+                    .iter()
+                    .all(|(path, entry)| is_valid_entry(path, entry))
+        }
+        None => false,
+    }
+}
+
+/// Verifies that the path is absolute and the entry points to a child data account
+fn is_valid_entry(path: &str, entry: &Value) -> bool {
+    let account = entry.get("account").and_then(Value::as_str);
+    let hash = entry.get("hash").and_then(Value::as_str);
+    let mime = entry.get("mime").and_then(Value::as_str);
+    path.starts_with('/')
+        && path.len() <= MAX_PATH_LEN
+        && account.map_or(false, |account| Pubkey::from_str(account).is_ok())
+        && hash.map_or(false, |hash| {
+            hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+        && mime.map_or(false, |mime| {
+            mime.split_once('/').map_or(false, |(kind, subtype)| {
+                !kind.is_empty() && !subtype.is_empty()
+            })
+        })
+}
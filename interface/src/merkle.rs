@@ -0,0 +1,22 @@
+use solana_program::hash::hashv;
+
+pub const ROOT_SIZE: usize = 32;
+
+/// Hashes an allowlist entry into a leaf
+pub fn leaf_hash(entry: &[u8]) -> [u8; 32] {
+    hashv(&[&[0], entry]).to_bytes()
+}
+
+/// Hashes a pair of nodes in sorted order so proofs need no direction bits
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1], left, right]).to_bytes()
+}
+
+/// Verifies that the entry is included in the tree with the given root
+pub fn verify_proof(root: &[u8; 32], entry: &[u8], proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf_hash(entry), |node, sibling| node_hash(&node, sibling));
+    computed == *root
+}
@@ -0,0 +1,10 @@
+//! Addresses of data accounts bound to an NFT mint
+
+use solana_program::pubkey::Pubkey;
+
+pub const NFT_SEED: &[u8] = b"data_account_nft";
+
+/// Get the data account pda bound to the mint
+pub fn nft_data_address(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[NFT_SEED, mint.as_ref()], program_id).0
+}
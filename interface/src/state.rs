@@ -0,0 +1,1708 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
+use solana_program::{
+    clock::UnixTimestamp, entrypoint::MAX_PERMITTED_DATA_INCREASE, pubkey::Pubkey,
+};
+
+use crate::{
+    cnft::CnftLeaf,
+    crdt::{CrdtDelta, CrdtKindOption, CrdtState},
+    manifest::is_valid_manifest,
+    merkle::ROOT_SIZE,
+    validation::is_valid_json,
+};
+
+/// The data_version 1 layout is not frozen until it is first released: fields are still appended to it
+/// in place, after which any change to it must bump the DATA_VERSION and add a migration of V1 metadata
+pub const DATA_VERSION: u8 = 1;
+/// Offset of the data_version in every metadata layout
+pub const DATA_VERSION_OFFSET: usize = 1 + 1 + 32 + 1;
+pub const METADATA_V0_SIZE: usize = 1 + 1 + 32 + 1 + 1 + 1 + 1;
+pub const METADATA_SIZE: usize = 1
+    + 1
+    + 32
+    + 1
+    + 1
+    + 1
+    + 1
+    + (1 + 32)
+    + 1
+    + 1
+    + (1 + 32)
+    + 8
+    + (1 + 4)
+    + (1 + 2)
+    + (1 + ARCHIVE_INFO_MAX_SIZE)
+    + (1 + 32 + 32)
+    + (1 + 32 + 8 + 8)
+    + (1 + 32 + 8 + 8)
+    + (1 + 8 + 8 + 2 + 8)
+    + (1 + 8 + 1 + 32)
+    + (1 + 1 + 4 + LICENSE_MAX_LEN)
+    + (1 + 8 + 1 + 8)
+    + (1 + 32)
+    + 8
+    + (1 + 8 + 32 + 8)
+    + 1
+    + (4 + MAX_SEQUENCED_WRITERS * (32 + 8))
+    + (1 + 32)
+    + (4 + MAX_WRITERS * 32)
+    + (1 + 8 + 32)
+    + (1 + 32)
+    + (1 + 4)
+    + (1 + 32 + 8 + 8)
+    + 8
+    + 8
+    + (1 + 32)
+    + 1
+    + (1 + 32)
+    + (1 + 8 + 32)
+    + 8
+    + 8
+    + (1 + 32 + 8)
+    + 8;
+pub const PDA_SEED: &[u8] = b"data_account_metadata";
+pub const STAGING_SEED: &[u8] = b"data_account_staging";
+pub const STAGED_BUFFER_SEED: &[u8] = b"data_account_staged_buffer";
+pub const TAGS_SEED: &[u8] = b"data_account_tags";
+pub const DROP_BOX_SEED: &[u8] = b"data_account_drop_box";
+pub const DONATIONS_SEED: &[u8] = b"data_account_donations";
+pub const SESSION_SEED: &[u8] = b"data_account_session";
+pub const DATA_PDA_SEED: &[u8] = b"data_account_pda";
+pub const HOT_REGION_MAX_END: usize = 128;
+pub const TAG_LABEL_MAX_LEN: usize = 32;
+pub const MAX_TAGS: usize = 32;
+pub const LICENSE_MAX_LEN: usize = 64;
+pub const MAX_TOP_DONORS: usize = 10;
+pub const MAX_SESSION_RANGES: usize = 8;
+pub const MAX_SEQUENCED_WRITERS: usize = 8;
+pub const MAX_UPDATE_SEGMENTS: usize = 16;
+pub const MAX_WRITERS: usize = 8;
+pub const DONATIONS_SIZE: usize = 8 + 4 + MAX_TOP_DONORS * (32 + 8);
+pub const ARCHIVE_POINTER_MAX_LEN: usize = 96;
+pub const ARCHIVE_INFO_MAX_SIZE: usize = 32 + 8 + 8 + (1 + 4 + ARCHIVE_POINTER_MAX_LEN);
+/// Magic number that every zstd dictionary starts with
+pub const ZSTD_DICTIONARY_MAGIC: [u8; 4] = [0x37, 0xa4, 0x30, 0xec];
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum DataTypeOption {
+    CUSTOM = 0,
+    JSON = 1,
+    IMG = 2,
+    HTML = 3,
+    ALLOWLIST = 4,
+    DIRECTORY = 5,
+    CRDT = 6,
+    /// deflate-compressed content stored as is (UpdateDataAccountCompressed stores the expanded content instead)
+    COMPRESSED = 7,
+    /// zstd dictionary shared by the ZSTD compressed data accounts of an authority
+    DICTIONARY = 8,
+    PNG = 9,
+    SVG = 10,
+    /// borsh serialized struct whose schema is only known to its readers
+    BORSH = 11,
+    /// UTF-8 text
+    TEXT = 12,
+}
+
+/// Signature every PNG image starts with
+pub const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+
+impl DataTypeOption {
+    /// Content-Type of the content (e.g., for gateways serving data accounts over HTTP)
+    /// IMG does not record its format, so its readers should sniff it from the content instead
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            DataTypeOption::JSON | DataTypeOption::DIRECTORY => "application/json",
+            DataTypeOption::HTML => "text/html",
+            DataTypeOption::PNG => "image/png",
+            DataTypeOption::SVG => "image/svg+xml",
+            DataTypeOption::TEXT => "text/plain; charset=utf-8",
+            DataTypeOption::CUSTOM
+            | DataTypeOption::IMG
+            | DataTypeOption::ALLOWLIST
+            | DataTypeOption::CRDT
+            | DataTypeOption::COMPRESSED
+            | DataTypeOption::DICTIONARY
+            | DataTypeOption::BORSH => "application/octet-stream",
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum CompressionOption {
+    NONE,
+    DEFLATE,
+    /// zstd, with the compression_dictionary of the metadata if any
+    ZSTD,
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum GrowthPolicyOption {
+    /// Grow to exactly the required length
+    EXACT,
+    /// Grow in multiples of the given number of bytes
+    STEP(u32),
+    /// Grow by the given percentage of the current length (e.g., 150 for 1.5x)
+    FACTOR(u32),
+}
+
+impl GrowthPolicyOption {
+    /// Get the length a dynamic data account of old_len should grow to in order to fit required_len
+    /// The length is capped by the maximum increase permitted in a single instruction
+    pub fn grow(&self, old_len: usize, required_len: usize) -> usize {
+        if required_len <= old_len {
+            return old_len;
+        }
+        let policy_len = match self {
+            GrowthPolicyOption::EXACT => required_len,
+            GrowthPolicyOption::STEP(step) => {
+                let step = (*step as usize).max(1);
+                required_len.saturating_add(step - 1) / step * step
+            }
+            GrowthPolicyOption::FACTOR(percent) => old_len.saturating_mul(*percent as usize) / 100,
+        };
+        policy_len
+            .min(old_len.saturating_add(MAX_PERMITTED_DATA_INCREASE))
+            .max(required_len)
+    }
+}
+
+/// Verbosity of the program logs of an instruction
+/// QUIET and INFO are encoded like false and true so clients that send a bool debug flag keep working
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub enum LogLevel {
+    /// No logs (cheapest, meant for hot paths)
+    QUIET,
+    /// The instruction name and the outcome of its checks
+    INFO,
+    /// Additionally structured key=value lines of account keys, offsets, lengths, rent deltas and decisions
+    VERBOSE,
+}
+
+impl LogLevel {
+    /// Verifies whether info logs are enabled
+    pub fn info(&self) -> bool {
+        *self >= LogLevel::INFO
+    }
+
+    /// Verifies whether verbose logs are enabled
+    pub fn verbose(&self) -> bool {
+        *self >= LogLevel::VERBOSE
+    }
+}
+
+/// Region of the data account that consumers read frequently
+/// It always lies within the first HOT_REGION_MAX_END bytes so it can be fetched with a single small slice
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct HotRegion {
+    pub offset: u8,
+    pub len: u8,
+}
+
+impl HotRegion {
+    /// Get the end of the hot region
+    pub fn end(&self) -> usize {
+        self.offset as usize + self.len as usize
+    }
+}
+
+/// Stub kept in the metadata after the data account of an archived data account is released
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct ArchiveInfo {
+    /// sha256 hash of the content at the time of archival
+    pub hash: [u8; 32],
+    /// length of the content at the time of archival
+    pub size: u64,
+    /// slot at which the data account was archived
+    pub archived_at: u64,
+    /// optional off-chain pointer (e.g., Arweave or IPFS uri) to a backup of the content
+    pub pointer: Option<String>,
+}
+
+/// SPL Governance that controls the authority of a governance-gated data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct GovernanceConfig {
+    pub governance_program: Pubkey,
+    pub governance: Pubkey,
+}
+
+/// Automation (e.g., a Clockwork thread) allowed to write to the data account on a schedule
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct AutomationConfig {
+    /// delegate key owned by the automation that signs the scheduled writes
+    pub delegate: Pubkey,
+    /// minimum number of slots between two scheduled writes
+    pub min_interval_slots: u64,
+    /// slot of the last scheduled write
+    pub last_heartbeat_slot: u64,
+}
+
+impl AutomationConfig {
+    /// Verifies whether the automation missed more than max_missed scheduled writes
+    pub fn is_stale(&self, current_slot: u64, max_missed: u64) -> bool {
+        let max_gap = self
+            .min_interval_slots
+            .max(1)
+            .saturating_mul(max_missed.saturating_add(1));
+        current_slot.saturating_sub(self.last_heartbeat_slot) > max_gap
+    }
+}
+
+/// Periodic proof (by the authority or its automation delegate) that the content is still what was published
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct Attestation {
+    /// sha256 hash of the content at the last attestation
+    pub hash: [u8; 32],
+    /// slot of the last attestation
+    pub slot: u64,
+    /// maximum number of slots between two attestations before they lapse (0 never lapses)
+    pub interval_slots: u64,
+}
+
+impl Attestation {
+    /// Verifies whether the next attestation is overdue
+    pub fn is_lapsed(&self, current_slot: u64) -> bool {
+        self.interval_slots != 0 && current_slot.saturating_sub(self.slot) > self.interval_slots
+    }
+}
+
+/// Successor allowed to claim the authority after a window of authority inactivity
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct DeadManSwitch {
+    /// account that can claim the authority once the authority is dormant
+    pub successor: Pubkey,
+    /// number of slots without an authority-signed instruction after which the authority is dormant
+    pub inactivity_timeout_slots: u64,
+    /// slot of the last authority-signed instruction
+    pub last_active_slot: u64,
+}
+
+impl DeadManSwitch {
+    /// Verifies whether the authority has been inactive for longer than the timeout
+    pub fn is_dormant(&self, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.last_active_slot) > self.inactivity_timeout_slots
+    }
+}
+
+/// Time lock of sealed (e.g., encrypted) content that can only be swapped with the plaintext after a slot
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct TimeLock {
+    /// slot from which the plaintext can be revealed
+    pub reveal_after_slot: u64,
+    /// sha256 hash of the plaintext that lets anyone reveal it (only the authority can otherwise)
+    pub plaintext_hash: Option<[u8; 32]>,
+}
+
+/// Close protection requiring an UnlockClose instruction at least cooldown_slots before CloseDataAccount
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct CloseLock {
+    /// minimum number of slots between unlocking and closing
+    pub cooldown_slots: u64,
+    /// slot of the latest UnlockClose instruction
+    pub unlocked_at: Option<u64>,
+}
+
+impl CloseLock {
+    /// Verifies whether the data account has been unlocked for at least the cool-down
+    pub fn is_closable(&self, current_slot: u64) -> bool {
+        match self.unlocked_at {
+            Some(unlocked_at) => current_slot >= unlocked_at.saturating_add(self.cooldown_slots),
+            None => false,
+        }
+    }
+}
+
+/// Expiry after which anyone can close the data account, refunding the pre-configured rent_receiver
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct Expiry {
+    pub expires_at: UnixTimestamp,
+    pub rent_receiver: Pubkey,
+}
+
+/// Latest sequence number written by a writer (e.g., a program writing via CPI) of the data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct WriterSequence {
+    pub writer: Pubkey,
+    pub sequence: u64,
+}
+
+/// Progress of a chunked upload that replaces the content once committed
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct UploadProgress {
+    /// length of the uploaded content
+    pub total_len: u64,
+    /// sha256 hash of the uploaded content
+    pub checksum: [u8; 32],
+    /// number of bytes appended so far (i.e., the offset of the next chunk)
+    pub written: u64,
+}
+
+/// Progress of an incremental verification of the content against its content_hash (see VerifyDataStep)
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct VerificationCursor {
+    /// number of bytes hashed so far (a multiple of the sha256 block length)
+    pub offset: u64,
+    /// sha256 state after hashing the content up to offset
+    pub state: [u32; 8],
+}
+
+/// Account allowed to sign UpdateDataAccount in place of the authority until it expires (see DelegateWriteAuthority)
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct WriteDelegate {
+    pub delegate: Pubkey,
+    /// last slot at which the delegate can sign
+    pub expiry_slot: u64,
+}
+
+impl WriteDelegate {
+    /// Verifies whether the delegation has expired
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        current_slot > self.expiry_slot
+    }
+}
+
+/// Public drop box where anyone can append small messages after a given offset of the data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct DropBoxConfig {
+    /// offset of the data account from which the append-only region starts
+    pub start: u64,
+    /// lamports paid to the authority per byte of message
+    pub fee_per_byte: u64,
+    /// maximum length of a single message
+    pub max_message_len: u16,
+    /// minimum number of slots between two messages of the same sender
+    pub min_interval_slots: u64,
+}
+
+/// Reuse terms of the content of the data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum LicenseOption {
+    /// SPDX license identifier (e.g., CC-BY-4.0)
+    SPDX(String),
+    /// Pointer to custom terms (e.g., a URI)
+    POINTER(String),
+}
+
+impl LicenseOption {
+    /// Verifies that the SPDX id or pointer is non-empty and at most LICENSE_MAX_LEN bytes
+    pub fn is_valid(&self) -> bool {
+        let (LicenseOption::SPDX(value) | LicenseOption::POINTER(value)) = self;
+        !value.is_empty() && value.len() <= LICENSE_MAX_LEN
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum DataStatusOption {
+    UNINITIALIZED,
+    INITIALIZED,
+    UPDATED,
+    COMMITTED,
+    ARCHIVED,
+    FINALIZED,
+}
+
+impl DataStatusOption {
+    /// Every data_status a data account can be in
+    pub const ALL: [DataStatusOption; 6] = [
+        DataStatusOption::UNINITIALIZED,
+        DataStatusOption::INITIALIZED,
+        DataStatusOption::UPDATED,
+        DataStatusOption::COMMITTED,
+        DataStatusOption::ARCHIVED,
+        DataStatusOption::FINALIZED,
+    ];
+
+    /// Verifies whether the data_status can transition to the next one
+    /// A data account is initialized once, then updated or committed any number of times until it is either
+    /// finalized, which is terminal, or archived, in which case it can only be restored
+    pub fn can_transition_to(&self, next: &DataStatusOption) -> bool {
+        use DataStatusOption::*;
+        match self {
+            UNINITIALIZED => *next == INITIALIZED,
+            INITIALIZED | UPDATED | COMMITTED => {
+                matches!(next, UPDATED | COMMITTED | ARCHIVED | FINALIZED)
+            }
+            ARCHIVED => *next == UPDATED,
+            FINALIZED => false,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum SerializationStatusOption {
+    UNVERIFIED,
+    VERIFIED,
+    FAILED,
+}
+
+/// Verfies that the data conforms to the data_type
+pub fn verify(data: &[u8], data_type: DataTypeOption) -> SerializationStatusOption {
+    // compressed content can expand beyond what can be inflated on-chain so it is not verified
+    // and borsh content can't be without its schema
+    if data.is_empty()
+        || data_type == DataTypeOption::CUSTOM
+        || data_type == DataTypeOption::COMPRESSED
+        || data_type == DataTypeOption::BORSH
+    {
+        return SerializationStatusOption::UNVERIFIED;
+    }
+    match data_type {
+        DataTypeOption::JSON => {
+            if is_valid_json(data) {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::ALLOWLIST => {
+            if data.len() >= ROOT_SIZE {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::DIRECTORY => {
+            if is_valid_manifest(data) {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::CRDT => {
+            if CrdtState::try_from_slice(data).is_ok() {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::DICTIONARY => {
+            if data.starts_with(&ZSTD_DICTIONARY_MAGIC) {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::PNG => {
+            if data.starts_with(&PNG_SIGNATURE) {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        DataTypeOption::SVG => match std::str::from_utf8(data) {
+            Ok(text) if text.trim_start().starts_with('<') && text.contains("<svg") => {
+                SerializationStatusOption::VERIFIED
+            }
+            _ => SerializationStatusOption::FAILED,
+        },
+        DataTypeOption::TEXT => {
+            if std::str::from_utf8(data).is_ok() {
+                SerializationStatusOption::VERIFIED
+            } else {
+                SerializationStatusOption::FAILED
+            }
+        }
+        _ => SerializationStatusOption::FAILED,
+    }
+}
+
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
+pub struct DataAccountMetadata {
+    data_status: DataStatusOption,
+    serialization_status: SerializationStatusOption,
+    authority: Pubkey,
+    is_dynamic: bool,
+    data_version: u8,
+    data_type: DataTypeOption,
+    bump_seed: u8,
+    user: Option<Pubkey>,
+    require_user_consent: bool,
+    compression: CompressionOption,
+    content_hash: Option<[u8; 32]>,
+    content_len: u64,
+    growth_policy: GrowthPolicyOption,
+    hot_region: Option<HotRegion>,
+    archive: Option<ArchiveInfo>,
+    governance: Option<GovernanceConfig>,
+    automation: Option<AutomationConfig>,
+    dead_man_switch: Option<DeadManSwitch>,
+    drop_box: Option<DropBoxConfig>,
+    time_lock: Option<TimeLock>,
+    license: Option<LicenseOption>,
+    close_lock: Option<CloseLock>,
+    content_keccak: Option<[u8; 32]>,
+    record_count: u64,
+    upload: Option<UploadProgress>,
+    append_only: bool,
+    writer_sequences: Vec<WriterSequence>,
+    nft_mint: Option<Pubkey>,
+    writers: Vec<Pubkey>,
+    expiry: Option<Expiry>,
+    notification_hint: Option<[u8; 32]>,
+    checksum: Option<u32>,
+    attestation: Option<Attestation>,
+    realloc_count: u64,
+    rent_paid: u64,
+    freeze_authority: Option<Pubkey>,
+    frozen: bool,
+    compression_dictionary: Option<Pubkey>,
+    verification_cursor: Option<VerificationCursor>,
+    generation: u64,
+    revision: u64,
+    write_delegate: Option<WriteDelegate>,
+    donated_lamports: u64,
+}
+
+impl DataAccountMetadata {
+    /// Default constructor
+    pub fn new(
+        data_status: DataStatusOption,
+        serialization_status: SerializationStatusOption,
+        authority: Pubkey,
+        is_dynamic: bool,
+        data_version: u8,
+        data_type: DataTypeOption,
+        bump_seed: u8,
+    ) -> Self {
+        DataAccountMetadata {
+            data_status,
+            serialization_status,
+            authority,
+            is_dynamic,
+            data_version,
+            data_type,
+            bump_seed,
+            user: None,
+            require_user_consent: false,
+            compression: CompressionOption::NONE,
+            content_hash: None,
+            content_len: 0,
+            growth_policy: GrowthPolicyOption::EXACT,
+            hot_region: None,
+            archive: None,
+            governance: None,
+            automation: None,
+            dead_man_switch: None,
+            drop_box: None,
+            time_lock: None,
+            license: None,
+            close_lock: None,
+            content_keccak: None,
+            record_count: 0,
+            upload: None,
+            append_only: false,
+            writer_sequences: Vec::new(),
+            nft_mint: None,
+            writers: Vec::new(),
+            expiry: None,
+            notification_hint: None,
+            checksum: None,
+            attestation: None,
+            realloc_count: 0,
+            rent_paid: 0,
+            freeze_authority: None,
+            frozen: false,
+            compression_dictionary: None,
+            verification_cursor: None,
+            generation: 0,
+            revision: 0,
+            write_delegate: None,
+            donated_lamports: 0,
+        }
+    }
+    /// Get the data_status
+    pub fn data_status(&self) -> &DataStatusOption {
+        &self.data_status
+    }
+    /// Set the data_status
+    /// The program only changes it along DataStatusOption::can_transition_to
+    pub fn set_data_status(&mut self, data_status: DataStatusOption) {
+        self.data_status = data_status;
+    }
+    /// Get the serialization_status
+    pub fn serialization_status(&self) -> &SerializationStatusOption {
+        &self.serialization_status
+    }
+    /// Set the serialization_status
+    pub fn set_serialization_status(&mut self, serialization_status: SerializationStatusOption) {
+        self.serialization_status = serialization_status;
+    }
+    /// Get the authority
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+    /// Set the authority
+    pub fn set_authority(&mut self, authority: Pubkey) {
+        self.authority = authority;
+    }
+    /// Get the dynamic flag
+    pub fn dynamic(&self) -> bool {
+        self.is_dynamic
+    }
+    /// Get the current data version
+    pub fn version(&self) -> u8 {
+        self.data_version
+    }
+    /// Get the data_type
+    pub fn data_type(&self) -> &DataTypeOption {
+        &self.data_type
+    }
+    /// Set the data_type
+    pub fn set_data_type(&mut self, data_type: DataTypeOption) {
+        self.data_type = data_type;
+    }
+    /// Get the bump seed
+    pub fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+    /// Get the user the data account was derived for (if any)
+    pub fn user(&self) -> Option<&Pubkey> {
+        self.user.as_ref()
+    }
+    /// Set the user the data account was derived for
+    pub fn set_user(&mut self, user: Option<Pubkey>) {
+        self.user = user;
+    }
+    /// Get the user consent flag
+    pub fn require_user_consent(&self) -> bool {
+        self.require_user_consent
+    }
+    /// Set the user consent flag
+    pub fn set_require_user_consent(&mut self, require_user_consent: bool) {
+        self.require_user_consent = require_user_consent;
+    }
+    /// Get the compression of the stored data
+    pub fn compression(&self) -> &CompressionOption {
+        &self.compression
+    }
+    /// Set the compression of the stored data
+    pub fn set_compression(&mut self, compression: CompressionOption) {
+        self.compression = compression;
+    }
+    /// Get the sha256 hash of the plaintext (decompressed) content
+    pub fn content_hash(&self) -> Option<&[u8; 32]> {
+        self.content_hash.as_ref()
+    }
+    /// Set the sha256 hash of the plaintext (decompressed) content
+    /// Also clears the keccak256 hash, resets the serialization_status and drops any incremental verification
+    /// as they are only valid along with the content_hash they were computed or verified with
+    pub fn set_content_hash(&mut self, content_hash: Option<[u8; 32]>) {
+        self.content_hash = content_hash;
+        self.content_keccak = None;
+        self.checksum = None;
+        self.serialization_status = SerializationStatusOption::UNVERIFIED;
+        self.verification_cursor = None;
+    }
+    /// Get the keccak256 hash of the uncompressed content computed on-chain (e.g., for EVM-side verifiers)
+    pub fn content_keccak(&self) -> Option<&[u8; 32]> {
+        self.content_keccak.as_ref()
+    }
+    /// Set the keccak256 hash of the uncompressed content
+    pub fn set_content_keccak(&mut self, content_keccak: Option<[u8; 32]>) {
+        self.content_keccak = content_keccak;
+    }
+    /// Get the number of meaningful bytes in the data account
+    pub fn content_len(&self) -> u64 {
+        self.content_len
+    }
+    /// Set the number of meaningful bytes in the data account
+    pub fn set_content_len(&mut self, content_len: u64) {
+        self.content_len = content_len;
+    }
+    /// Get the growth_policy
+    pub fn growth_policy(&self) -> &GrowthPolicyOption {
+        &self.growth_policy
+    }
+    /// Set the growth_policy
+    pub fn set_growth_policy(&mut self, growth_policy: GrowthPolicyOption) {
+        self.growth_policy = growth_policy;
+    }
+    /// Get the hot_region
+    pub fn hot_region(&self) -> Option<&HotRegion> {
+        self.hot_region.as_ref()
+    }
+    /// Set the hot_region
+    pub fn set_hot_region(&mut self, hot_region: Option<HotRegion>) {
+        self.hot_region = hot_region;
+    }
+    /// Get the archive stub
+    pub fn archive(&self) -> Option<&ArchiveInfo> {
+        self.archive.as_ref()
+    }
+    /// Set the archive stub
+    pub fn set_archive(&mut self, archive: Option<ArchiveInfo>) {
+        self.archive = archive;
+    }
+    /// Get the governance config
+    pub fn governance(&self) -> Option<&GovernanceConfig> {
+        self.governance.as_ref()
+    }
+    /// Set the governance config
+    pub fn set_governance(&mut self, governance: Option<GovernanceConfig>) {
+        self.governance = governance;
+    }
+    /// Get the automation config
+    pub fn automation(&self) -> Option<&AutomationConfig> {
+        self.automation.as_ref()
+    }
+    /// Set the automation config
+    pub fn set_automation(&mut self, automation: Option<AutomationConfig>) {
+        self.automation = automation;
+    }
+    /// Get the dead man switch
+    pub fn dead_man_switch(&self) -> Option<&DeadManSwitch> {
+        self.dead_man_switch.as_ref()
+    }
+    /// Set the dead man switch
+    pub fn set_dead_man_switch(&mut self, dead_man_switch: Option<DeadManSwitch>) {
+        self.dead_man_switch = dead_man_switch;
+    }
+    /// Get the drop box config
+    pub fn drop_box(&self) -> Option<&DropBoxConfig> {
+        self.drop_box.as_ref()
+    }
+    /// Set the drop box config
+    pub fn set_drop_box(&mut self, drop_box: Option<DropBoxConfig>) {
+        self.drop_box = drop_box;
+    }
+    /// Get the time lock of the sealed content
+    pub fn time_lock(&self) -> Option<&TimeLock> {
+        self.time_lock.as_ref()
+    }
+    /// Set the time lock of the sealed content
+    pub fn set_time_lock(&mut self, time_lock: Option<TimeLock>) {
+        self.time_lock = time_lock;
+    }
+    /// Get the license
+    pub fn license(&self) -> Option<&LicenseOption> {
+        self.license.as_ref()
+    }
+    /// Set the license
+    pub fn set_license(&mut self, license: Option<LicenseOption>) {
+        self.license = license;
+    }
+    /// Get the close lock
+    pub fn close_lock(&self) -> Option<&CloseLock> {
+        self.close_lock.as_ref()
+    }
+    /// Set the close lock
+    pub fn set_close_lock(&mut self, close_lock: Option<CloseLock>) {
+        self.close_lock = close_lock;
+    }
+    /// Get the number of framed records appended to the content
+    pub fn record_count(&self) -> u64 {
+        self.record_count
+    }
+    /// Set the number of framed records appended to the content
+    pub fn set_record_count(&mut self, record_count: u64) {
+        self.record_count = record_count;
+    }
+    /// Get the progress of the chunked upload in progress
+    pub fn upload(&self) -> Option<&UploadProgress> {
+        self.upload.as_ref()
+    }
+    /// Set the progress of the chunked upload in progress
+    pub fn set_upload(&mut self, upload: Option<UploadProgress>) {
+        self.upload = upload;
+    }
+    /// Get whether the content can only be extended, never overwritten
+    pub fn append_only(&self) -> bool {
+        self.append_only
+    }
+    /// Set whether the content can only be extended, never overwritten
+    pub fn set_append_only(&mut self, append_only: bool) {
+        self.append_only = append_only;
+    }
+    /// Get the latest sequence number of every sequenced writer
+    pub fn writer_sequences(&self) -> &[WriterSequence] {
+        &self.writer_sequences
+    }
+    /// Set the latest sequence number of every sequenced writer
+    pub fn set_writer_sequences(&mut self, writer_sequences: Vec<WriterSequence>) {
+        self.writer_sequences = writer_sequences;
+    }
+    /// Get the NFT mint (or the asset id of the compressed NFT) the data account is bound to
+    pub fn nft_mint(&self) -> Option<&Pubkey> {
+        self.nft_mint.as_ref()
+    }
+    /// Set the NFT mint (or the asset id of the compressed NFT) the data account is bound to
+    pub fn set_nft_mint(&mut self, nft_mint: Option<Pubkey>) {
+        self.nft_mint = nft_mint;
+    }
+    /// Get the additional accounts that can write to the data account along with the authority
+    pub fn writers(&self) -> &[Pubkey] {
+        &self.writers
+    }
+    /// Set the additional accounts that can write to the data account along with the authority
+    pub fn set_writers(&mut self, writers: Vec<Pubkey>) {
+        self.writers = writers;
+    }
+    /// Get the expiry
+    pub fn expiry(&self) -> Option<&Expiry> {
+        self.expiry.as_ref()
+    }
+    /// Set the expiry
+    pub fn set_expiry(&mut self, expiry: Option<Expiry>) {
+        self.expiry = expiry;
+    }
+    /// Get the notification hint (e.g., a URL hash or topic id) off-chain infrastructure routes change notifications by
+    pub fn notification_hint(&self) -> Option<&[u8; 32]> {
+        self.notification_hint.as_ref()
+    }
+    /// Set the notification hint
+    pub fn set_notification_hint(&mut self, notification_hint: Option<[u8; 32]>) {
+        self.notification_hint = notification_hint;
+    }
+    /// Get the crc32 checksum of the content as of the last UpdateDataAccount, regardless of its data_type
+    /// None if the content has since been written by any other instruction
+    pub fn checksum(&self) -> Option<u32> {
+        self.checksum
+    }
+    /// Set the crc32 checksum of the content
+    pub fn set_checksum(&mut self, checksum: Option<u32>) {
+        self.checksum = checksum;
+    }
+    /// Get the last attestation of the content (if any)
+    pub fn attestation(&self) -> Option<&Attestation> {
+        self.attestation.as_ref()
+    }
+    /// Set the last attestation of the content
+    pub fn set_attestation(&mut self, attestation: Option<Attestation>) {
+        self.attestation = attestation;
+    }
+    /// Get the number of times the data_account was realloc-ed
+    pub fn realloc_count(&self) -> u64 {
+        self.realloc_count
+    }
+    /// Get the total lamports of rent paid into the data_account by its reallocs
+    pub fn rent_paid(&self) -> u64 {
+        self.rent_paid
+    }
+    /// Record a realloc of the data_account along with the lamports of rent it paid (0 when shrinking)
+    pub fn record_realloc(&mut self, rent_paid: u64) {
+        self.realloc_count = self.realloc_count.saturating_add(1);
+        self.rent_paid = self.rent_paid.saturating_add(rent_paid);
+    }
+    /// Get the freeze_authority that can pause writes to the data_account (if any)
+    pub fn freeze_authority(&self) -> Option<&Pubkey> {
+        self.freeze_authority.as_ref()
+    }
+    /// Set the freeze_authority
+    pub fn set_freeze_authority(&mut self, freeze_authority: Option<Pubkey>) {
+        self.freeze_authority = freeze_authority;
+    }
+    /// Get whether the data_account is frozen by its freeze_authority
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+    /// Set whether the data_account is frozen
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+    /// Get the data account holding the zstd dictionary that ZSTD compressed content is compressed with (if any)
+    pub fn compression_dictionary(&self) -> Option<&Pubkey> {
+        self.compression_dictionary.as_ref()
+    }
+    /// Set the data account holding the zstd dictionary
+    pub fn set_compression_dictionary(&mut self, compression_dictionary: Option<Pubkey>) {
+        self.compression_dictionary = compression_dictionary;
+    }
+    /// Get the progress of an incremental verification (if one is in progress)
+    pub fn verification_cursor(&self) -> Option<&VerificationCursor> {
+        self.verification_cursor.as_ref()
+    }
+    /// Set the progress of an incremental verification
+    pub fn set_verification_cursor(&mut self, verification_cursor: Option<VerificationCursor>) {
+        self.verification_cursor = verification_cursor;
+    }
+    /// Get the number of staged buffers committed (see CommitStaged)
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Count a staged buffer swapped in as the content
+    pub fn bump_generation(&mut self) {
+        self.generation = self.generation.saturating_add(1);
+    }
+    /// Get the number of successful updates of the content, e.g., to invalidate cached copies
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+    /// Count a successful update of the content
+    pub fn bump_revision(&mut self) {
+        self.revision = self.revision.saturating_add(1);
+    }
+    /// Get the account temporarily allowed to sign UpdateDataAccount (if any)
+    pub fn write_delegate(&self) -> Option<&WriteDelegate> {
+        self.write_delegate.as_ref()
+    }
+    /// Set the account temporarily allowed to sign UpdateDataAccount
+    pub fn set_write_delegate(&mut self, write_delegate: Option<WriteDelegate>) {
+        self.write_delegate = write_delegate;
+    }
+    /// Get the donated lamports held by the data_account that have not been spent on its growth
+    pub fn donated_lamports(&self) -> u64 {
+        self.donated_lamports
+    }
+    /// Set the donated lamports held by the data_account
+    pub fn set_donated_lamports(&mut self, donated_lamports: u64) {
+        self.donated_lamports = donated_lamports;
+    }
+    /// Record an authority-signed instruction to keep the dead man switch from triggering
+    pub fn record_activity(&mut self, slot: u64) {
+        if let Some(dead_man_switch) = self.dead_man_switch.as_mut() {
+            dead_man_switch.last_active_slot = slot;
+        }
+    }
+}
+
+/// Metadata layout of data_version 0, holding only the fields of the first release
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct DataAccountMetadataV0 {
+    pub data_status: DataStatusOption,
+    pub serialization_status: SerializationStatusOption,
+    pub authority: Pubkey,
+    pub is_dynamic: bool,
+    pub data_version: u8,
+    pub data_type: DataTypeOption,
+    pub bump_seed: u8,
+}
+
+/// Every metadata layout stored by a release of the data program, keyed by its data_version
+#[derive(Clone, Debug)]
+pub enum VersionedMetadata {
+    V0(DataAccountMetadataV0),
+    V1(DataAccountMetadata),
+}
+
+impl VersionedMetadata {
+    /// Reads the data_version of the stored metadata without deserializing it
+    pub fn version_of(data: &[u8]) -> Option<u8> {
+        data.get(DATA_VERSION_OFFSET).copied()
+    }
+    /// Deserializes the stored metadata with the layout of its data_version
+    /// Returns None for an unknown data_version or a malformed layout
+    pub fn unpack(data: &[u8]) -> Option<Self> {
+        let version = Self::version_of(data)?;
+        let data = &mut &data[..];
+        match version {
+            0 => DataAccountMetadataV0::deserialize(data).ok().map(Self::V0),
+            1 => DataAccountMetadata::deserialize(data).ok().map(Self::V1),
+            _ => None,
+        }
+    }
+    /// Get the data_version of the layout
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(_) => 1,
+        }
+    }
+    /// Get the authority
+    pub fn authority(&self) -> &Pubkey {
+        match self {
+            Self::V0(metadata) => &metadata.authority,
+            Self::V1(metadata) => metadata.authority(),
+        }
+    }
+    /// Get the bump_seed
+    pub fn bump_seed(&self) -> u8 {
+        match self {
+            Self::V0(metadata) => metadata.bump_seed,
+            Self::V1(metadata) => metadata.bump_seed(),
+        }
+    }
+    /// Rewrites the metadata into the current layout, defaulting every field added since its data_version
+    /// Layouts without a content_len treat the entire data account (of data_len bytes) as the content
+    pub fn into_current(self, data_len: u64) -> DataAccountMetadata {
+        match self {
+            Self::V0(metadata) => {
+                let mut account_metadata = DataAccountMetadata::new(
+                    metadata.data_status,
+                    metadata.serialization_status,
+                    metadata.authority,
+                    metadata.is_dynamic,
+                    DATA_VERSION,
+                    metadata.data_type,
+                    metadata.bump_seed,
+                );
+                account_metadata.set_content_len(data_len);
+                account_metadata
+            }
+            Self::V1(account_metadata) => account_metadata,
+        }
+    }
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeDataAccountArgs {
+    pub authority: Pubkey,
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub is_created: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub append_only: bool,
+    pub idempotent: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeUserDataAccountArgs {
+    pub user: Pubkey,
+    pub name: String,
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub require_user_consent: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateDataAccountArgs {
+    pub data_type: DataTypeOption,
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub realloc_down: bool,
+    pub truncate: bool,
+    pub commit_flag: bool,
+    pub verify_flag: bool,
+    pub compression: CompressionOption,
+    pub content_hash: Option<[u8; 32]>,
+    pub sequence: Option<u64>,
+    pub segments: Vec<UpdateSegment>,
+    pub expected_revision: Option<u64>,
+    pub debug: LogLevel,
+}
+
+/// Write of an UpdateDataAccount applied atomically along with its data, e.g. to patch scattered fields
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct UpdateSegment {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
+impl UpdateSegment {
+    /// Get every (offset, data) write of an update, i.e., the data at offset followed by the segments
+    /// None unless there are at most MAX_UPDATE_SEGMENTS non-empty segments that overlap neither each other
+    /// nor the data and, along with the data, write at most MAX_PERMITTED_DATA_INCREASE bytes in total
+    pub fn writes<'a>(
+        offset: u64,
+        data: &'a [u8],
+        segments: &'a [UpdateSegment],
+    ) -> Option<Vec<(usize, &'a [u8])>> {
+        if segments.len() > MAX_UPDATE_SEGMENTS
+            || segments.iter().any(|segment| segment.data.is_empty())
+        {
+            return None;
+        }
+
+        let writes: Vec<(usize, &[u8])> = std::iter::once((offset as usize, data))
+            .chain(
+                segments
+                    .iter()
+                    .map(|segment| (segment.offset as usize, segment.data.as_slice())),
+            )
+            .collect();
+
+        let mut ranges = Vec::with_capacity(writes.len());
+        let mut total_len: usize = 0;
+        for (offset, data) in writes.iter() {
+            ranges.push((*offset, offset.checked_add(data.len())?));
+            total_len = total_len.checked_add(data.len())?;
+        }
+        if !segments.is_empty() && total_len > MAX_PERMITTED_DATA_INCREASE {
+            return None;
+        }
+
+        // a write only overlaps another one if it starts before the end of the previous one
+        ranges.retain(|(start, end)| start < end);
+        ranges.sort_unstable();
+        if ranges.windows(2).any(|pair| pair[1].0 < pair[0].1) {
+            return None;
+        }
+
+        Some(writes)
+    }
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateDataAccountAuthorityArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CloseDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetAllowlistRootArgs {
+    pub root: [u8; 32],
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct VerifyAllowlistInclusionArgs {
+    pub entry: Vec<u8>,
+    pub proof: Vec<[u8; 32]>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotPriceFeedArgs {
+    pub feed_offset: u64,
+    pub feed_len: u64,
+    pub offset: u64,
+    pub debug: LogLevel,
+}
+
+/// Cumulative donation of a single donor
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct Donation {
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+/// Donations toward the rent of the data account along with its top donors (sorted by amount)
+#[derive(PartialEq, Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+pub struct DonationsTable {
+    pub total: u64,
+    pub top: Vec<Donation>,
+}
+
+impl DonationsTable {
+    /// Credits the donation and keeps only the top donors
+    pub fn credit(&mut self, donor: Pubkey, amount: u64) -> Option<()> {
+        self.total = self.total.checked_add(amount)?;
+        match self.top.iter_mut().find(|donation| donation.donor == donor) {
+            Some(donation) => donation.amount = donation.amount.checked_add(amount)?,
+            None => self.top.push(Donation { donor, amount }),
+        }
+        self.top
+            .sort_by_key(|donation| std::cmp::Reverse(donation.amount));
+        self.top.truncate(MAX_TOP_DONORS);
+        Some(())
+    }
+}
+
+/// A message appended to the drop box region of the data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct DropBoxMessage {
+    pub sender: Pubkey,
+    pub slot: u64,
+    pub message: Vec<u8>,
+}
+
+/// Instructions that a session key can sign on behalf of the authority
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum SessionInstructionOption {
+    /// UpdateDataAccount
+    UPDATE,
+    /// StageUpdate
+    STAGE,
+    /// ApplyDelta
+    DELTA,
+}
+
+/// Byte range of the data account that a session key can write to
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct SessionRange {
+    pub offset: u64,
+    pub len: u64,
+}
+
+/// Instructions and byte ranges a session key is limited to
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct SessionScope {
+    pub ranges: Vec<SessionRange>,
+    pub instructions: Vec<SessionInstructionOption>,
+}
+
+impl SessionScope {
+    /// Verifies that the scope has between 1 and MAX_SESSION_RANGES non-empty ranges and at least one instruction
+    pub fn is_valid(&self) -> bool {
+        !self.ranges.is_empty()
+            && self.ranges.len() <= MAX_SESSION_RANGES
+            && self
+                .ranges
+                .iter()
+                .all(|range| range.len > 0 && range.offset.checked_add(range.len).is_some())
+            && !self.instructions.is_empty()
+    }
+
+    /// Verifies that the instruction is in scope and the write of [start, end) lies within a single range
+    pub fn allows(&self, instruction: &SessionInstructionOption, start: u64, end: u64) -> bool {
+        self.instructions.contains(instruction)
+            && self
+                .ranges
+                .iter()
+                .any(|range| range.offset <= start && end <= range.offset + range.len)
+    }
+}
+
+/// Ephemeral key (e.g., of a game client) allowed to write to the data account until it expires
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct Session {
+    pub data_account: Pubkey,
+    pub session_key: Pubkey,
+    /// last slot at which the session key can sign
+    pub expiry_slot: u64,
+    pub scope: SessionScope,
+}
+
+impl Session {
+    /// Verifies whether the session has expired
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        current_slot > self.expiry_slot
+    }
+}
+
+/// A write accumulated in the staging pda until it is flushed to the data account
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct StagedWrite {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct StageUpdateArgs {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FlushStagingArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetHotRegionArgs {
+    pub hot_region: Option<HotRegion>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ArchiveDataAccountArgs {
+    pub pointer: Option<String>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RestoreFromArchiveArgs {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub finalize: bool,
+    /// Seeds (without the bump seed) of a data account that is a pda, to recreate it by signing for it
+    pub seeds: Option<Vec<Vec<u8>>>,
+    pub debug: LogLevel,
+}
+
+/// Human-readable label of the content of a data account at a given slot
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct RevisionTag {
+    pub label: String,
+    pub slot: u64,
+    pub content_len: u64,
+    pub hash: [u8; 32],
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct TagRevisionArgs {
+    pub label: String,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetGovernanceArgs {
+    pub governance: Option<GovernanceConfig>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetAutomationArgs {
+    pub delegate: Option<Pubkey>,
+    pub min_interval_slots: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AutomatedUpdateArgs {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ValidateDataAccountArgs {
+    pub authority: Option<Pubkey>,
+    pub data_version: u8,
+    pub require_committed: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetDeadManSwitchArgs {
+    pub successor: Option<Pubkey>,
+    pub inactivity_timeout_slots: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ClaimDormantAuthorityArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetDropBoxArgs {
+    pub drop_box: Option<DropBoxConfig>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct PostToDropBoxArgs {
+    pub message: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct DonateArgs {
+    pub amount: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SealContentArgs {
+    pub reveal_after_slot: u64,
+    pub plaintext_hash: Option<[u8; 32]>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevealContentArgs {
+    pub plaintext: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CommitValueArgs {
+    pub hash: [u8; 32],
+    pub reveal_deadline_slot: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevealValueArgs {
+    pub value: Vec<u8>,
+    pub salt: [u8; 32],
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ExpireCommitmentArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetLicenseArgs {
+    pub license: Option<LicenseOption>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeMetadataTreeArgs {
+    pub max_depth: u8,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeCompressedDataAccountArgs {
+    pub authority: Pubkey,
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateCompressedDataAccountArgs {
+    pub metadata: DataAccountMetadata,
+    pub leaf_index: u64,
+    pub proof: Vec<[u8; 32]>,
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CloseCompressedDataAccountArgs {
+    pub metadata: DataAccountMetadata,
+    pub leaf_index: u64,
+    pub proof: Vec<[u8; 32]>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CreateSessionArgs {
+    pub session_key: Pubkey,
+    pub expiry_slot: u64,
+    pub scope: SessionScope,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevokeSessionArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeCrdtArgs {
+    pub kind: CrdtKindOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ApplyDeltaArgs {
+    pub delta: CrdtDelta,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct LockCloseArgs {
+    pub cooldown_slots: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UnlockCloseArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FinalizeDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AppendFramedRecordArgs {
+    pub record: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct BeginUploadArgs {
+    pub total_len: u64,
+    pub checksum: [u8; 32],
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AppendChunkArgs {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CommitUploadArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct VerifyDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeNftDataAccountArgs {
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateNftDataAccountArgs {
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub truncate: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AddWriterArgs {
+    pub writer: Pubkey,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RemoveWriterArgs {
+    pub writer: Pubkey,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeCnftDataAccountArgs {
+    pub leaf: CnftLeaf,
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateCnftDataAccountArgs {
+    pub leaf: CnftLeaf,
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub truncate: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetExpiryArgs {
+    pub expiry: Option<Expiry>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ExpireDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetNotificationHintArgs {
+    pub notification_hint: Option<[u8; 32]>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ResizeDataAccountArgs {
+    pub space: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateDataAccountCompressedArgs {
+    pub data_type: DataTypeOption,
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub max_output_len: u32,
+    pub truncate: bool,
+    pub sequence: Option<u64>,
+    pub expected_revision: Option<u64>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeDataAccountPdaArgs {
+    pub seed: String,
+    pub space: u64,
+    pub is_dynamic: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub append_only: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub enum ScrubFillOption {
+    ZEROS,
+    RANDOM,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ScrubRangeArgs {
+    pub offset: u64,
+    pub len: u64,
+    pub fill: ScrubFillOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct MigrateMetadataArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AttestArgs {
+    pub hash: [u8; 32],
+    pub interval_slots: Option<u64>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetFreezeAuthorityArgs {
+    pub freeze_authority: Option<Pubkey>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FreezeDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ThawDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CloneDataAccountArgs {
+    pub authority: Pubkey,
+    pub is_dynamic: bool,
+    pub growth_policy: GrowthPolicyOption,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetCompressionDictionaryArgs {
+    pub compression_dictionary: Option<Pubkey>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct VerifyDataStepArgs {
+    pub window: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct TopUpRentArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct WriteStagedArgs {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    pub reset: bool,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CommitStagedArgs {
+    pub expected_revision: Option<u64>,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct DelegateWriteAuthorityArgs {
+    pub delegate: Pubkey,
+    pub expiry_slot: u64,
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevokeDelegateArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CloseNftDataAccountArgs {
+    pub debug: LogLevel,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CloseCnftDataAccountArgs {
+    pub leaf: CnftLeaf,
+    pub debug: LogLevel,
+}
@@ -0,0 +1,48 @@
+//! Typed validation of the final content written by UpdateDataAccount with its verify_flag set
+//! Parsing JSON costs compute proportional to the content, so JSON content is only validated on-chain
+//! when the program is built with the `json-validation` feature and is left UNVERIFIED otherwise
+
+use serde::de::IgnoredAny;
+
+use crate::{
+    compute::copy_cost,
+    state::{verify, DataTypeOption, SerializationStatusOption},
+};
+
+/// Base cost of parsing JSON content
+const JSON_BASE_COST: u64 = 100;
+/// Estimated compute units per byte of parsed JSON content
+const JSON_UNITS_PER_BYTE: u64 = 4;
+/// Estimated compute units per byte of content checked to be UTF-8 (TEXT and SVG)
+const UTF8_UNITS_PER_BYTE: u64 = 2;
+
+/// Whether JSON content is validated on-chain
+pub const JSON_VALIDATION: bool = cfg!(feature = "json-validation");
+
+/// Whether the data parses as JSON
+/// Values are skipped instead of built so the heap only holds the parser state
+pub fn is_valid_json(data: &[u8]) -> bool {
+    serde_json::from_slice::<IgnoredAny>(data).is_ok()
+}
+
+/// Estimated compute units to validate len bytes of content of the data_type
+pub fn validation_cost(data_type: &DataTypeOption, len: usize) -> u64 {
+    match data_type {
+        DataTypeOption::JSON if JSON_VALIDATION => {
+            JSON_BASE_COST.saturating_add((len as u64).saturating_mul(JSON_UNITS_PER_BYTE))
+        }
+        DataTypeOption::JSON => 0,
+        DataTypeOption::TEXT | DataTypeOption::SVG => {
+            (len as u64).saturating_mul(UTF8_UNITS_PER_BYTE)
+        }
+        _ => copy_cost(len),
+    }
+}
+
+/// Validates that the final content conforms to its data_type
+pub fn validate_content(data: &[u8], data_type: DataTypeOption) -> SerializationStatusOption {
+    if data_type == DataTypeOption::JSON && !JSON_VALIDATION {
+        return SerializationStatusOption::UNVERIFIED;
+    }
+    verify(data, data_type)
+}
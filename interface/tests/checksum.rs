@@ -0,0 +1,20 @@
+use solana_data_account_interface::checksum::{crc32, crc32_update};
+
+#[test]
+fn test_crc32_known_values() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    assert_eq!(
+        crc32(b"The quick brown fox jumps over the lazy dog"),
+        0x414f_a339
+    );
+}
+
+#[test]
+fn test_crc32_rolls_over_appends() {
+    let content = br#"{"name":"data","tags":[1,2,3]}"#;
+    for split in 0..=content.len() {
+        let (head, tail) = content.split_at(split);
+        assert_eq!(crc32_update(crc32(head), tail), crc32(content));
+    }
+}
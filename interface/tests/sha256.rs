@@ -0,0 +1,26 @@
+use solana_data_account_interface::sha256::{
+    sha256, sha256_finalize, sha256_update, BLOCK_LEN, INITIAL_STATE,
+};
+use solana_program::hash::hash;
+
+#[test]
+fn test_sha256_matches_the_syscall() {
+    for len in [0, 1, 55, 56, 63, 64, 65, 119, 120, 128, 1_000] {
+        let content: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        assert_eq!(sha256(&content), hash(&content).to_bytes());
+    }
+}
+
+#[test]
+fn test_sha256_resumes_from_a_stored_state() {
+    let content: Vec<u8> = (0..1_000).map(|i| (i * 13) as u8).collect();
+    for blocks in 0..=content.len() / BLOCK_LEN {
+        let (head, tail) = content.split_at(blocks * BLOCK_LEN);
+        let mut state = INITIAL_STATE;
+        sha256_update(&mut state, head);
+        assert_eq!(
+            sha256_finalize(state, tail, content.len() as u64),
+            sha256(&content)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use solana_data_account_interface::{
+    state::{DataTypeOption, SerializationStatusOption},
+    validation::{is_valid_json, validate_content, validation_cost, JSON_VALIDATION},
+};
+
+#[test]
+fn test_is_valid_json() {
+    assert!(is_valid_json(
+        br#"{"name":"data","tags":[1,2,{"nested":null}]}"#
+    ));
+    assert!(is_valid_json(b" [] "));
+    assert!(!is_valid_json(br#"{"name":"data""#));
+    assert!(!is_valid_json(br#"{"name":"data"} trailing"#));
+    assert!(!is_valid_json(b""));
+}
+
+#[test]
+fn test_validate_json_content_is_feature_gated() {
+    let expected = |status| {
+        if JSON_VALIDATION {
+            status
+        } else {
+            SerializationStatusOption::UNVERIFIED
+        }
+    };
+    assert_eq!(
+        validate_content(br#"{"a":1}"#, DataTypeOption::JSON),
+        expected(SerializationStatusOption::VERIFIED)
+    );
+    assert_eq!(
+        validate_content(br#"{"a":"#, DataTypeOption::JSON),
+        expected(SerializationStatusOption::FAILED)
+    );
+    assert_eq!(
+        validation_cost(&DataTypeOption::JSON, 1024) > 0,
+        JSON_VALIDATION
+    );
+}
+
+#[test]
+fn test_validate_other_content() {
+    assert_eq!(
+        validate_content(&[0; 32], DataTypeOption::ALLOWLIST),
+        SerializationStatusOption::VERIFIED
+    );
+    assert_eq!(
+        validate_content(&[0; 31], DataTypeOption::ALLOWLIST),
+        SerializationStatusOption::FAILED
+    );
+    assert_eq!(
+        validate_content(b"anything", DataTypeOption::CUSTOM),
+        SerializationStatusOption::UNVERIFIED
+    );
+}
+
+#[test]
+fn test_validate_dictionary_content() {
+    assert_eq!(
+        validate_content(
+            &[0x37, 0xa4, 0x30, 0xec, 1, 0, 0, 0],
+            DataTypeOption::DICTIONARY
+        ),
+        SerializationStatusOption::VERIFIED
+    );
+    assert_eq!(
+        validate_content(b"not a dictionary", DataTypeOption::DICTIONARY),
+        SerializationStatusOption::FAILED
+    );
+}
+
+#[test]
+fn test_validate_media_content() {
+    let png = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 13];
+    assert_eq!(
+        validate_content(&png, DataTypeOption::PNG),
+        SerializationStatusOption::VERIFIED
+    );
+    assert_eq!(
+        validate_content(b"GIF89a", DataTypeOption::PNG),
+        SerializationStatusOption::FAILED
+    );
+    assert_eq!(
+        validate_content(
+            br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"/>"#,
+            DataTypeOption::SVG
+        ),
+        SerializationStatusOption::VERIFIED
+    );
+    assert_eq!(
+        validate_content(b"plain text", DataTypeOption::SVG),
+        SerializationStatusOption::FAILED
+    );
+    assert_eq!(
+        validate_content("héllo".as_bytes(), DataTypeOption::TEXT),
+        SerializationStatusOption::VERIFIED
+    );
+    assert_eq!(
+        validate_content(&[0xff, 0xfe], DataTypeOption::TEXT),
+        SerializationStatusOption::FAILED
+    );
+    // borsh content has no schema on-chain
+    assert_eq!(
+        validate_content(&[1, 0, 0, 0], DataTypeOption::BORSH),
+        SerializationStatusOption::UNVERIFIED
+    );
+}
+
+#[test]
+fn test_data_type_mime_type() {
+    assert_eq!(DataTypeOption::JSON.mime_type(), "application/json");
+    assert_eq!(DataTypeOption::HTML.mime_type(), "text/html");
+    assert_eq!(DataTypeOption::PNG.mime_type(), "image/png");
+    assert_eq!(DataTypeOption::SVG.mime_type(), "image/svg+xml");
+    assert_eq!(
+        DataTypeOption::TEXT.mime_type(),
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        DataTypeOption::BORSH.mime_type(),
+        "application/octet-stream"
+    );
+}
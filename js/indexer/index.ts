@@ -14,8 +14,8 @@ const programId = new PublicKey(process.env.PROGRAM_ID as string);
 const connection = new Connection("http://localhost:8899");
 
 const SELECTQUERY = "SELECT * FROM DataAccountIndexer WHERE data_account = $1";
-const UPDATEQUERY = "UPDATE DataAccountIndexer SET data_type = $1, data = $2, tx_id = $3, serialization_status= $4 WHERE data_account = $5 AND tx_id <> $3";
-const INSERTQUERY = "INSERT INTO DataAccountIndexer(data_account, authority, data_type, data, tx_id, serialization_status) VALUES ($1, $2, $3, $4, $5, $6)";
+const UPDATEQUERY = "UPDATE DataAccountIndexer SET data_type = $1, data = $2, tx_id = $3, serialization_status= $4, realloc_count = $5, rent_paid = $6 WHERE data_account = $7 AND tx_id <> $3";
+const INSERTQUERY = "INSERT INTO DataAccountIndexer(data_account, authority, data_type, data, tx_id, serialization_status, realloc_count, rent_paid) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)";
 
 
 const connectDb = async () => {
@@ -72,7 +72,7 @@ const populateDB = async () => {
                     parseData(connection, dataKey)
                     .then((account_state) => {
                         if (Object.keys(account_state).length === 0) return;
-                        const { authority, serialization_status } = account_state;
+                        const { authority, serialization_status, realloc_count, rent_paid } = account_state;
                         connectDb().then((client) => {
                             // check if row already present
                             client?.query(SELECTQUERY, [dataPubKey])
@@ -81,7 +81,7 @@ const populateDB = async () => {
                                     if (res.rowCount === 1) {
                                         client.query(
                                             UPDATEQUERY, 
-                                            [data_type, dataJSON, transaction.signature, serialization_status, dataPubKey]
+                                            [data_type, dataJSON, transaction.signature, serialization_status, realloc_count, rent_paid, dataPubKey]
                                         )
                                         .then((res) => {
                                             if (res.rowCount === 1) {
@@ -96,7 +96,7 @@ const populateDB = async () => {
                                     else {
                                         client?.query(
                                             INSERTQUERY,
-                                            [dataPubKey, authority, data_type, dataJSON, transaction.signature, serialization_status]
+                                            [dataPubKey, authority, data_type, dataJSON, transaction.signature, serialization_status, realloc_count, rent_paid]
                                         )
                                         .then((res) => {
                                             if (res.rowCount === 1) {
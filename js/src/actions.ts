@@ -0,0 +1,184 @@
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey, Transaction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+import { donateInstruction } from "./donations";
+import { postToDropBoxInstruction } from "./dropBox";
+import { parseMetadata } from "./parseData";
+import { initializeInstruction } from "./site";
+
+// data account interactions a wallet can execute from a link: initialize a new data account,
+// append a message to the drop box of a data account and donate toward the rent of a data account
+export type DataAccountAction = "initialize" | "append" | "donate";
+
+export interface IActionParameter {
+  name: string;
+  label: string;
+  required?: boolean;
+}
+
+export interface IActionLink {
+  label: string;
+  href: string;
+  parameters?: IActionParameter[];
+}
+
+// response to the GET request of a Solana Action (rendered by wallets and blink clients)
+export interface IActionGetResponse {
+  icon: string;
+  title: string;
+  description: string;
+  label: string;
+  links: { actions: IActionLink[] };
+}
+
+// response to the POST request of a Solana Pay transaction request or Solana Action
+export interface IActionPostResponse {
+  // base64 transaction for the wallet to sign and send
+  transaction: string;
+  message?: string;
+}
+
+export interface IActionParams {
+  // data account to append to or donate to (unused by initialize)
+  data?: string;
+  // message to append
+  message?: string;
+  // SOL to donate
+  amount?: string;
+}
+
+// url of the endpoint serving the action, e.g., https://example.com/api/data/donate?data=<key>
+// parameters left as {name} are filled in by the wallet from the action parameters
+export const actionUrl = (baseUrl: string, action: DataAccountAction, params: IActionParams = {}): string => {
+  const query = (Object.keys(params) as (keyof IActionParams)[])
+    .filter((key) => params[key] !== undefined)
+    .map((key) => {
+      const value = params[key] as string;
+      return `${key}=${/^\{\w+\}$/.test(value) ? value : encodeURIComponent(value)}`;
+    })
+    .join("&");
+  return `${baseUrl.replace(/\/+$/, "")}/${action}${query ? `?${query}` : ""}`;
+};
+
+// Solana Pay transaction request link, e.g., for a QR code
+export const transactionRequestLink = (url: string): string => {
+  return `solana:${encodeURIComponent(url)}`;
+};
+
+// Solana Action link and the blink that unfurls it on a blink client (e.g., dial.to)
+export const actionLink = (url: string): string => {
+  return `solana-action:${encodeURIComponent(url)}`;
+};
+
+export const blinkLink = (url: string, blinkClient = "https://dial.to"): string => {
+  return `${blinkClient.replace(/\/+$/, "")}/?action=${encodeURIComponent(`solana-action:${url}`)}`;
+};
+
+// metadata returned by the GET request of an action
+export const actionMetadata = (
+  baseUrl: string,
+  action: DataAccountAction,
+  icon: string,
+  dataKey?: PublicKey
+): IActionGetResponse => {
+  const data = dataKey?.toBase58();
+  switch (action) {
+    case "initialize":
+      return {
+        icon,
+        title: "Create a data account",
+        description: "Initialize an empty dynamic data account owned by your wallet",
+        label: "Create",
+        links: { actions: [{ label: "Create", href: actionUrl(baseUrl, action) }] },
+      };
+    case "append":
+      return {
+        icon,
+        title: "Leave a message",
+        description: `Append a message to the drop box of ${data}`,
+        label: "Send",
+        links: {
+          actions: [
+            {
+              label: "Send",
+              href: actionUrl(baseUrl, action, { data, message: "{message}" }),
+              parameters: [{ name: "message", label: "Your message", required: true }],
+            },
+          ],
+        },
+      };
+    case "donate":
+      return {
+        icon,
+        title: "Donate toward rent",
+        description: `Help keep ${data} alive by donating toward its rent`,
+        label: "Donate",
+        links: {
+          actions: ["0.01", "0.1"]
+            .map((amount): IActionLink => ({ label: `${amount} SOL`, href: actionUrl(baseUrl, action, { data, amount }) }))
+            .concat({
+              label: "Donate",
+              href: actionUrl(baseUrl, action, { data, amount: "{amount}" }),
+              parameters: [{ name: "amount", label: "SOL to donate", required: true }],
+            }),
+        },
+      };
+  }
+};
+
+// builds the transaction returned by the POST request of an action for the wallet account
+// initialize generates the new data account keypair and partially signs with it
+export const actionTransaction = async (
+  connection: Connection,
+  programId: PublicKey,
+  account: PublicKey,
+  action: DataAccountAction,
+  params: IActionParams
+): Promise<IActionPostResponse> => {
+  const transaction = new Transaction();
+  const signers: Keypair[] = [];
+  let message: string;
+  if (action === "initialize") {
+    const dataAccount = Keypair.generate();
+    transaction.add(initializeInstruction(programId, account, dataAccount.publicKey));
+    signers.push(dataAccount);
+    message = `Created data account ${dataAccount.publicKey.toBase58()}`;
+  } else {
+    if (!params.data) {
+      throw new Error("missing data account");
+    }
+    const dataKey = new PublicKey(params.data);
+    if (action === "append") {
+      if (!params.message) {
+        throw new Error("missing message");
+      }
+      const meta_account = await connection.getAccountInfo(getMetadataKey(programId, dataKey), "confirmed");
+      if (!meta_account) {
+        throw new Error(`Data account ${params.data} not found`);
+      }
+      const meta = parseMetadata(meta_account.data);
+      if (!meta.drop_box) {
+        throw new Error(`Data account ${params.data} has no drop box`);
+      }
+      transaction.add(
+        postToDropBoxInstruction(programId, account, dataKey, new PublicKey(meta.authority), Buffer.from(params.message, "utf8"))
+      );
+      message = `Appended a message to ${params.data}`;
+    } else {
+      const lamports = Math.round(Number(params.amount) * LAMPORTS_PER_SOL);
+      if (!(lamports > 0)) {
+        throw new Error(`invalid amount ${params.amount}`);
+      }
+      transaction.add(donateInstruction(programId, account, dataKey, lamports));
+      message = `Donated ${params.amount} SOL to ${params.data}`;
+    }
+  }
+  transaction.feePayer = account;
+  transaction.recentBlockhash = (await connection.getLatestBlockhash("confirmed")).blockhash;
+  if (signers.length) {
+    transaction.partialSign(...signers);
+  }
+  return {
+    transaction: transaction.serialize({ requireAllSignatures: false }).toString("base64"),
+    message,
+  };
+};
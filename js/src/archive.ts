@@ -0,0 +1,220 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { parseData } from "./parseData";
+
+const ARCHIVE_DATA_ACCOUNT_IX = 11;
+const RESTORE_FROM_ARCHIVE_IX = 12;
+const RESTORE_CHUNK_SIZE = 900;
+
+// off-chain store that keeps a backup of the content of an archived data account
+export interface IBackupStore {
+  // uploads the content and returns the pointer recorded in the archival stub
+  upload(content: Buffer): Promise<string>;
+  fetch(pointer: string): Promise<Buffer>;
+}
+
+// backup store backed by an IPFS node's HTTP API and a gateway for reads
+export class IpfsBackupStore implements IBackupStore {
+  constructor(
+    private readonly apiUrl = "http://127.0.0.1:5001",
+    private readonly gatewayUrl = "https://ipfs.io"
+  ) {}
+
+  async upload(content: Buffer): Promise<string> {
+    const form = new FormData();
+    form.append("file", new Blob([content]));
+    const res = await fetch(`${this.apiUrl}/api/v0/add?pin=true&cid-version=1`, {
+      method: "POST",
+      body: form,
+    });
+    if (!res.ok) {
+      throw new Error(`IPFS upload failed: ${res.status}`);
+    }
+    const { Hash } = await res.json();
+    return `ipfs://${Hash}`;
+  }
+
+  async fetch(pointer: string): Promise<Buffer> {
+    return fetchFromGateway(pointer.replace("ipfs://", `${this.gatewayUrl}/ipfs/`));
+  }
+}
+
+// backup store for Arweave; uploads need a signed bundler upload supplied by the caller
+export class ArweaveBackupStore implements IBackupStore {
+  constructor(
+    private readonly uploader: (content: Buffer) => Promise<string>,
+    private readonly gatewayUrl = "https://arweave.net"
+  ) {}
+
+  async upload(content: Buffer): Promise<string> {
+    const txid = await this.uploader(content);
+    return `ar://${txid}`;
+  }
+
+  async fetch(pointer: string): Promise<Buffer> {
+    return fetchFromGateway(pointer.replace("ar://", `${this.gatewayUrl}/`));
+  }
+}
+
+const fetchFromGateway = async (url: string): Promise<Buffer> => {
+  const res = await fetch(url);
+  if (!res.ok) {
+    throw new Error(`Backup fetch failed: ${res.status}`);
+  }
+  return Buffer.from(await res.arrayBuffer());
+};
+
+const encodeOptionString = (value?: string): Buffer => {
+  if (value === undefined) {
+    return Buffer.from([0]);
+  }
+  const bytes = Buffer.from(value, "utf8");
+  return Buffer.concat([Buffer.from([1]), Buffer.from(new BN(bytes.length).toArray("le", 4)), bytes]);
+};
+
+const encodeOptionSeeds = (seeds?: Buffer[]): Buffer => {
+  if (seeds === undefined) {
+    return Buffer.from([0]);
+  }
+  return Buffer.concat([
+    Buffer.from([1]),
+    Buffer.from(new BN(seeds.length).toArray("le", 4)),
+    ...seeds.map((seed) => Buffer.concat([Buffer.from(new BN(seed.length).toArray("le", 4)), seed])),
+  ]);
+};
+
+export const archiveDataAccountInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  pointer?: string,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([ARCHIVE_DATA_ACCOUNT_IX]),
+      encodeOptionString(pointer),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const restoreFromArchiveInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  offset: number,
+  data: Buffer,
+  finalize: boolean,
+  isDataSigner: boolean,
+  debug?: boolean,
+  seeds?: Buffer[]
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: isDataSigner, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([RESTORE_FROM_ARCHIVE_IX]),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from([finalize ? 1 : 0]),
+      encodeOptionSeeds(seeds),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// backs up the content to the store, verifies the backup and archives the data account pointing to it
+export const archiveWithBackup = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataKey: PublicKey,
+  store: IBackupStore,
+  debug?: boolean
+): Promise<string> => {
+  const { data } = await parseData(connection, dataKey, getMetadataKey(programId, dataKey));
+  const content = data ?? Buffer.alloc(0);
+  const pointer = await store.upload(content);
+
+  // ensure the mirror serves exactly the content before releasing the data account
+  const mirrored = await store.fetch(pointer);
+  if (!plaintextHash(mirrored).equals(plaintextHash(content))) {
+    throw new Error(`Backup at ${pointer} does not match the data account content`);
+  }
+  if (debug) {
+    console.log(`content backed up to ${pointer}`);
+  }
+
+  const ix = archiveDataAccountInstruction(programId, authority.publicKey, dataKey, pointer, debug);
+  await sendAndConfirmTransaction(connection, new Transaction().add(ix), [authority], {
+    commitment: "confirmed",
+  });
+  return pointer;
+};
+
+// fetches the backup from the pointer in the archival stub, verifies it and restores the data account
+export const restoreFromBackup = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataAccount: Keypair,
+  store: IBackupStore,
+  debug?: boolean
+) => {
+  const { meta } = await parseData(connection, dataAccount.publicKey, getMetadataKey(programId, dataAccount.publicKey));
+  if (!meta.archive?.pointer) {
+    throw new Error("Data account has no archived backup pointer");
+  }
+  const { hash, size, pointer } = meta.archive;
+
+  // ensure the backup matches the archived hash before spending any lamports
+  const content = await store.fetch(pointer);
+  if (content.length !== size || !plaintextHash(content).equals(hash)) {
+    throw new Error(`Backup at ${pointer} does not match the archived hash`);
+  }
+
+  for (let offset = 0; offset < size || offset === 0; offset += RESTORE_CHUNK_SIZE) {
+    const chunk = content.subarray(offset, offset + RESTORE_CHUNK_SIZE);
+    const isFirst = offset === 0;
+    const isLast = offset + RESTORE_CHUNK_SIZE >= size;
+    const ix = restoreFromArchiveInstruction(
+      programId,
+      authority.publicKey,
+      dataAccount.publicKey,
+      offset,
+      chunk,
+      isLast,
+      isFirst,
+      debug
+    );
+    const signers = isFirst ? [authority, dataAccount] : [authority];
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), signers, {
+      commitment: "confirmed",
+    });
+    if (debug) {
+      console.log(`restored ${offset + chunk.length}/${size} bytes`);
+    }
+  }
+};
@@ -0,0 +1,123 @@
+import { Connection, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash } from "crypto";
+import { IAttestation } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { parseData } from "./parseData";
+
+const ATTEST_IX = 59;
+
+// re-asserts the sha256 hash of the content, signed by the authority or its automation delegate
+// only the authority can pass intervalSlots (0 never lapses), otherwise the current interval is kept
+export const attestInstruction = (
+  programId: PublicKey,
+  attester: PublicKey,
+  dataKey: PublicKey,
+  hash: Buffer,
+  intervalSlots?: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: attester, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([ATTEST_IX]),
+      hash,
+      intervalSlots === undefined
+        ? Buffer.from([0])
+        : Buffer.concat([Buffer.from([1]), Buffer.from(new BN(intervalSlots).toArray("le", 8))]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// hashes the current content so a watcher can attest it without tracking what was published
+export const attestCurrentContentInstruction = async (
+  connection: Connection,
+  programId: PublicKey,
+  attester: PublicKey,
+  dataKey: PublicKey,
+  intervalSlots?: number,
+  debug?: boolean
+): Promise<TransactionInstruction> => {
+  const { data } = await parseData(connection, dataKey, getMetadataKey(programId, dataKey));
+  const hash = createHash("sha256").update(data ?? Buffer.alloc(0)).digest();
+  return attestInstruction(programId, attester, dataKey, hash, intervalSlots, debug);
+};
+
+// mirrors Attestation::is_lapsed in the program
+export const isAttestationLapsed = (attestation: IAttestation, currentSlot: number): boolean => {
+  return attestation.interval_slots !== 0 && currentSlot - attestation.slot > attestation.interval_slots;
+};
+
+export type AttestationAlertKind = "lapsed" | "changed" | "unattested";
+
+export interface IAttestationAlert {
+  kind: AttestationAlertKind;
+  dataKey: PublicKey;
+  slot: number;
+  attestation?: IAttestation;
+}
+
+export interface IAttestationMonitorOptions {
+  onAlert: (alert: IAttestationAlert) => void;
+  // also alerts when the content no longer hashes to the last attested hash
+  checkContent?: boolean;
+  pollIntervalMs?: number;
+}
+
+// polls the attestations of data accounts and alerts when they lapse, e.g. to enforce an SLA on published data
+export class AttestationMonitor {
+  private timer?: NodeJS.Timeout;
+
+  constructor(
+    private readonly connection: Connection,
+    private readonly programId: PublicKey,
+    private readonly dataKeys: PublicKey[],
+    private readonly options: IAttestationMonitorOptions
+  ) {}
+
+  start() {
+    const poll = async () => {
+      try {
+        await this.scan();
+      } catch (e) {
+        console.error(e);
+      }
+      this.timer = setTimeout(poll, this.options.pollIntervalMs ?? 60_000);
+    };
+    poll();
+  }
+
+  stop() {
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+  }
+
+  async scan() {
+    const slot = await this.connection.getSlot("confirmed");
+    for (let i = 0; i < this.dataKeys.length; i++) {
+      const dataKey = this.dataKeys[i];
+      const { meta, data } = await parseData(this.connection, dataKey, getMetadataKey(this.programId, dataKey));
+      const attestation = meta.attestation;
+      if (!attestation) {
+        this.options.onAlert({ kind: "unattested", dataKey, slot });
+        continue;
+      }
+      if (isAttestationLapsed(attestation, slot)) {
+        this.options.onAlert({ kind: "lapsed", dataKey, slot, attestation });
+      }
+      if (this.options.checkContent) {
+        const hash = createHash("sha256").update(data ?? Buffer.alloc(0)).digest();
+        if (!hash.equals(attestation.hash)) {
+          this.options.onAlert({ kind: "changed", dataKey, slot, attestation });
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,61 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { plaintextHash } from "./common/utils";
+
+// a write of the content (by UpdateDataAccount or any other instruction writing it) as emitted by its audit event
+export interface IAuditEvent {
+  signature: string;
+  slot: number;
+  offset: number;
+  // number of bytes of the written range that existed before the write (the rest grew the data account)
+  overwrittenLen: number;
+  // sha256 of the overwritten bytes
+  beforeHash: Buffer;
+  // sha256 of the written bytes
+  afterHash: Buffer;
+}
+
+// extracts the audit events from the log messages of a transaction
+export const parseAuditEvents = (
+  signature: string,
+  slot: number,
+  logs: string[]
+): IAuditEvent[] => {
+  return logs
+    .filter((log) => log.startsWith("Program data: "))
+    .map((log) => log.slice("Program data: ".length).split(" ").map((field) => Buffer.from(field, "base64")))
+    .filter(([kind]) => kind.toString() === "audit")
+    .map(([, offset, overwrittenLen, beforeHash, afterHash]) => ({
+      signature,
+      slot,
+      offset: new BN(offset, "le").toNumber(),
+      overwrittenLen: new BN(overwrittenLen, "le").toNumber(),
+      beforeHash,
+      afterHash,
+    }));
+};
+
+// replays the successful transactions of the data account, oldest first, into its audit trail
+export const fetchAuditTrail = async (connection: Connection, dataKey: PublicKey): Promise<IAuditEvent[]> => {
+  const signatures: string[] = [];
+  let before: string | undefined;
+  for (;;) {
+    const page = await connection.getSignaturesForAddress(dataKey, { before }, "confirmed");
+    if (page.length === 0) break;
+    page.filter((sig) => !sig.err).forEach((sig) => signatures.push(sig.signature));
+    before = page[page.length - 1].signature;
+  }
+  const events: IAuditEvent[] = [];
+  for (const signature of signatures.reverse()) {
+    const tx = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+    if (tx) {
+      events.push(...parseAuditEvents(signature, tx.slot, tx.meta?.logMessages ?? []));
+    }
+  }
+  return events;
+};
+
+// checks that the event recorded a write of data
+export const matchesWrite = (event: IAuditEvent, data: Buffer): boolean => {
+  return event.afterHash.equals(plaintextHash(data));
+};
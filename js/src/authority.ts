@@ -0,0 +1,30 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const UPDATE_DATA_ACCOUNT_AUTHORITY_IX = 2;
+
+// both the current and the new authority (e.g., a DAO multisig) must sign so a transfer cannot go to an address that didn't consent
+// the user must also co-sign if the data account requires their consent
+export const updateAuthorityInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  newAuthority: PublicKey,
+  user?: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  const keys = [
+    { pubkey: authority, isSigner: true, isWritable: false },
+    { pubkey: dataKey, isSigner: false, isWritable: false },
+    { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    { pubkey: newAuthority, isSigner: true, isWritable: false },
+  ];
+  if (user) {
+    keys.push({ pubkey: user, isSigner: true, isWritable: false });
+  }
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: Buffer.from([UPDATE_DATA_ACCOUNT_AUTHORITY_IX, debug ? 1 : 0]),
+  });
+};
@@ -0,0 +1,189 @@
+import { Connection, Keypair, PublicKey, sendAndConfirmTransaction, Transaction } from "@solana/web3.js";
+import { existsSync, readFileSync, renameSync, writeFileSync } from "fs";
+import { updateAuthorityInstruction } from "./authority";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+// proposed: planned, accepted: transfer confirmed, verified: new authority read back, failed: see error
+export type MigrationStatus = "proposed" | "accepted" | "verified" | "failed";
+
+export interface IMigrationEntry {
+  dataKey: string;
+  from: string;
+  to: string;
+  status: MigrationStatus;
+  signature?: string;
+  error?: string;
+  updatedAt: string;
+}
+
+export interface IMigrationJournal {
+  entries: IMigrationEntry[];
+}
+
+export const loadJournal = (path: string): IMigrationJournal => {
+  return existsSync(path) ? JSON.parse(readFileSync(path, "utf8")) : { entries: [] };
+};
+
+// writes to a temporary file first so a crash never leaves a truncated journal behind
+export const saveJournal = (path: string, journal: IMigrationJournal) => {
+  writeFileSync(`${path}.tmp`, JSON.stringify(journal, null, 2));
+  renameSync(`${path}.tmp`, path);
+};
+
+const fetchAuthorities = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[]
+): Promise<(string | undefined)[]> => {
+  const authorities: (string | undefined)[] = [];
+  for (let i = 0; i < dataKeys.length; i += 100) {
+    const batch = dataKeys.slice(i, i + 100);
+    const infos = await connection.getMultipleAccountsInfo(
+      batch.map((dataKey) => getMetadataKey(programId, dataKey)),
+      "confirmed"
+    );
+    infos.forEach((info) =>
+      authorities.push(info && info.data.length > 0 ? parseMetadata(info.data).authority : undefined)
+    );
+  }
+  return authorities;
+};
+
+// step 1: records the data accounts to move from `from` to `to` in the journal
+// accounts already in the journal are left untouched so proposing again is idempotent
+export const proposeMigration = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[],
+  from: PublicKey,
+  to: PublicKey,
+  journalPath: string
+): Promise<IMigrationJournal> => {
+  const journal = loadJournal(journalPath);
+  const known = new Set(journal.entries.map((entry) => entry.dataKey));
+  const fresh = dataKeys.filter((dataKey) => !known.has(dataKey.toBase58()));
+  const authorities = await fetchAuthorities(connection, programId, fresh);
+  fresh.forEach((dataKey, idx) => {
+    const authority = authorities[idx];
+    const entry: IMigrationEntry = {
+      dataKey: dataKey.toBase58(),
+      from: from.toBase58(),
+      to: to.toBase58(),
+      status: "proposed",
+      updatedAt: new Date().toISOString(),
+    };
+    if (authority === undefined) {
+      entry.status = "failed";
+      entry.error = "metadata account not found";
+    } else if (authority !== entry.from && authority !== entry.to) {
+      entry.status = "failed";
+      entry.error = `unexpected authority ${authority}`;
+    }
+    journal.entries.push(entry);
+  });
+  saveJournal(journalPath, journal);
+  return journal;
+};
+
+// step 2: transfers every proposed account signed by both authorities, saving the journal after each one
+// an account that already has the new authority (e.g., a transfer confirmed before a crash) is only marked accepted
+export const acceptMigration = async (
+  connection: Connection,
+  programId: PublicKey,
+  from: Keypair,
+  to: Keypair,
+  journalPath: string,
+  debug?: boolean
+): Promise<IMigrationJournal> => {
+  const journal = loadJournal(journalPath);
+  const pending = journal.entries.filter((entry) => entry.status === "proposed");
+  const authorities = await fetchAuthorities(
+    connection,
+    programId,
+    pending.map((entry) => new PublicKey(entry.dataKey))
+  );
+  for (let idx = 0; idx < pending.length; ++idx) {
+    const entry = pending[idx];
+    if (authorities[idx] !== entry.to) {
+      try {
+        entry.signature = await sendAndConfirmTransaction(
+          connection,
+          new Transaction().add(
+            updateAuthorityInstruction(programId, from.publicKey, new PublicKey(entry.dataKey), to.publicKey)
+          ),
+          [from, to],
+          { commitment: "confirmed" }
+        );
+      } catch (err) {
+        entry.status = "failed";
+        entry.error = String(err);
+        entry.updatedAt = new Date().toISOString();
+        saveJournal(journalPath, journal);
+        continue;
+      }
+    }
+    entry.status = "accepted";
+    entry.updatedAt = new Date().toISOString();
+    saveJournal(journalPath, journal);
+    if (debug) {
+      console.log(`${entry.dataKey} transferred to ${entry.to}`);
+    }
+  }
+  return journal;
+};
+
+// step 3: reads back the authority of every accepted account
+export const verifyMigration = async (
+  connection: Connection,
+  programId: PublicKey,
+  journalPath: string
+): Promise<IMigrationJournal> => {
+  const journal = loadJournal(journalPath);
+  const accepted = journal.entries.filter((entry) => entry.status === "accepted");
+  const authorities = await fetchAuthorities(
+    connection,
+    programId,
+    accepted.map((entry) => new PublicKey(entry.dataKey))
+  );
+  accepted.forEach((entry, idx) => {
+    if (authorities[idx] === entry.to) {
+      entry.status = "verified";
+    } else {
+      entry.status = "failed";
+      entry.error = `authority is ${authorities[idx]} after the transfer`;
+    }
+    entry.updatedAt = new Date().toISOString();
+  });
+  saveJournal(journalPath, journal);
+  return journal;
+};
+
+// step 4: summarizes the journal, listing the failed accounts (retry them by setting their status back to proposed)
+export const reportMigration = (journal: IMigrationJournal): string[] => {
+  const counts = new Map<MigrationStatus, number>();
+  journal.entries.forEach((entry) => counts.set(entry.status, (counts.get(entry.status) ?? 0) + 1));
+  return [
+    (["proposed", "accepted", "verified", "failed"] as MigrationStatus[])
+      .map((status) => `${status}: ${counts.get(status) ?? 0}`)
+      .join(", "),
+    ...journal.entries
+      .filter((entry) => entry.status === "failed")
+      .map((entry) => `${entry.dataKey}: ${entry.error}`),
+  ];
+};
+
+// runs every step; rerunning after an interruption resumes from the journal
+export const migrateAuthority = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[],
+  from: Keypair,
+  to: Keypair,
+  journalPath: string,
+  debug?: boolean
+): Promise<string[]> => {
+  await proposeMigration(connection, programId, dataKeys, from.publicKey, to.publicKey, journalPath);
+  await acceptMigration(connection, programId, from, to, journalPath, debug);
+  return reportMigration(await verifyMigration(connection, programId, journalPath));
+};
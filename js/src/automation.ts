@@ -0,0 +1,90 @@
+import { Connection, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IAutomationConfig } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { parseData } from "./parseData";
+
+const SET_AUTOMATION_IX = 15;
+const AUTOMATED_UPDATE_IX = 16;
+
+// Clockwork thread program (v2); a thread pda signs the instructions it executes
+export const CLOCKWORK_THREAD_PROGRAM_ID = new PublicKey("CLoCKyJ6DXBJqqu2VWx9RLbgnwwR6BMHHuyasVmfMzBh");
+
+// derives the thread pda that becomes the automation delegate of the data account
+export const getClockworkThreadKey = (threadAuthority: PublicKey, threadId: string): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("thread", "ascii"), threadAuthority.toBuffer(), Buffer.from(threadId, "utf8")],
+    CLOCKWORK_THREAD_PROGRAM_ID
+  )[0];
+};
+
+export const setAutomationInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  delegate: PublicKey | null,
+  minIntervalSlots: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_AUTOMATION_IX]),
+      delegate ? Buffer.concat([Buffer.from([1]), delegate.toBuffer()]) : Buffer.from([0]),
+      Buffer.from(new BN(minIntervalSlots).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// scheduled write signed by the delegate; it must fit within the current length of the data account
+export const automatedUpdateInstruction = (
+  programId: PublicKey,
+  delegate: PublicKey,
+  dataKey: PublicKey,
+  offset: number,
+  data: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: delegate, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([AUTOMATED_UPDATE_IX]),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// mirrors AutomationConfig::is_stale in the program
+export const isAutomationStale = (automation: IAutomationConfig, currentSlot: number, maxMissed = 2): boolean => {
+  const maxGap = Math.max(automation.min_interval_slots, 1) * (maxMissed + 1);
+  return currentSlot - automation.last_heartbeat_slot > maxGap;
+};
+
+// checks whether the automation of the data account missed more than maxMissed scheduled writes
+export const checkAutomationHealth = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKey: PublicKey,
+  maxMissed = 2
+): Promise<{ automation?: IAutomationConfig; stale: boolean }> => {
+  const { meta } = await parseData(connection, dataKey, getMetadataKey(programId, dataKey));
+  if (!meta.automation) {
+    return { stale: false };
+  }
+  const slot = await connection.getSlot("confirmed");
+  return { automation: meta.automation, stale: isAutomationStale(meta.automation, slot, maxMissed) };
+};
@@ -0,0 +1,51 @@
+import { Connection, Keypair, PublicKey, sendAndConfirmTransaction, SystemProgram, Transaction, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const CLONE_DATA_ACCOUNT_IX = 63;
+
+// creates dataKey holding a copy of the content, data_type and compression of sourceKey, funded by the feePayer
+// the content of sourceKey must not be sealed
+export const cloneDataAccountInstruction = (
+  programId: PublicKey,
+  feePayer: PublicKey,
+  sourceKey: PublicKey,
+  dataKey: PublicKey,
+  authority: PublicKey = feePayer,
+  isDynamic = true,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: feePayer, isSigner: true, isWritable: true },
+      { pubkey: sourceKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, sourceKey), isSigner: false, isWritable: false },
+      { pubkey: dataKey, isSigner: true, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([CLONE_DATA_ACCOUNT_IX]),
+      authority.toBuffer(),
+      // is_dynamic, EXACT growth policy, debug
+      Buffer.from([isDynamic ? 1 : 0, 0, debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// forks a template (e.g., prefilled HTML or a config blob) into a new data account owned by the payer
+export const forkTemplate = async (
+  connection: Connection,
+  programId: PublicKey,
+  payer: Keypair,
+  templateKey: PublicKey
+): Promise<PublicKey> => {
+  const dataAccount = Keypair.generate();
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(cloneDataAccountInstruction(programId, payer.publicKey, templateKey, dataAccount.publicKey)),
+    [payer, dataAccount],
+    { commitment: "confirmed" }
+  );
+  return dataAccount.publicKey;
+};
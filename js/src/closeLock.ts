@@ -0,0 +1,54 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+const LOCK_CLOSE_IX = 37;
+const UNLOCK_CLOSE_IX = 38;
+
+export const lockCloseInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  cooldownSlots: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([LOCK_CLOSE_IX]),
+      Buffer.from(new BN(cooldownSlots).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// signer can be the authority or the user of a per-user data account
+export const unlockCloseInstruction = (
+  programId: PublicKey,
+  signer: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: signer, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([UNLOCK_CLOSE_IX, debug ? 1 : 0]),
+  });
+};
+
+// first slot at which the data account can be closed, undefined if it is close locked and not unlocked yet
+export const closableFromSlot = (meta: IDataAccountMeta): number | undefined => {
+  if (!meta.close_lock) return 0;
+  const { cooldown_slots, unlocked_at } = meta.close_lock;
+  return unlocked_at === undefined ? undefined : unlocked_at + cooldown_slots;
+};
@@ -0,0 +1,191 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import * as bs58 from "bs58";
+import { GrowthPolicyOption, IGrowthPolicy } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { donationRefundKeys, IDonationsTable } from "./donations";
+
+export const CNFT_SEED = "data_account_cnft";
+export const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+export const ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+const INITIALIZE_CNFT_DATA_ACCOUNT_IX = 49;
+const UPDATE_CNFT_DATA_ACCOUNT_IX = 50;
+const CLOSE_CNFT_DATA_ACCOUNT_IX = 72;
+
+// mirrors CnftLeaf in program/src/cnft.rs
+export interface ICnftLeaf {
+  root: Buffer;
+  dataHash: Buffer;
+  creatorHash: Buffer;
+  nonce: number;
+  index: number;
+  delegate: PublicKey;
+}
+
+// everything needed to prove the current ownership of a cNFT on-chain
+export interface ICnftProof {
+  assetId: PublicKey;
+  owner: PublicKey;
+  merkleTree: PublicKey;
+  leaf: ICnftLeaf;
+  proof: PublicKey[];
+}
+
+export const getCnftAssetId = (merkleTree: PublicKey, nonce: number): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("asset", "ascii"), merkleTree.toBuffer(), Buffer.from(new BN(nonce).toArray("le", 8))],
+    BUBBLEGUM_PROGRAM_ID
+  )[0];
+};
+
+// the data account bound to a cNFT is the pda derived from its asset id
+export const getCnftDataKey = (programId: PublicKey, assetId: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync([Buffer.from(CNFT_SEED, "ascii"), assetId.toBuffer()], programId)[0];
+};
+
+const dasRequest = async (rpcUrl: string, method: string, assetId: PublicKey): Promise<any> => {
+  const res = await fetch(rpcUrl, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ jsonrpc: "2.0", id: method, method, params: { id: assetId.toBase58() } }),
+  });
+  if (!res.ok) {
+    throw new Error(`DAS ${method} failed: ${res.status}`);
+  }
+  const { result, error } = await res.json();
+  if (error) {
+    throw new Error(`DAS ${method} failed: ${error.message}`);
+  }
+  return result;
+};
+
+// fetches the leaf and the proof of the cNFT from an RPC supporting the DAS API (getAsset and getAssetProof)
+// canopyDepth proof nodes closest to the root are cached in the merkle tree account and are not passed
+export const fetchCnftProof = async (rpcUrl: string, assetId: PublicKey, canopyDepth = 0): Promise<ICnftProof> => {
+  const [asset, assetProof] = await Promise.all([
+    dasRequest(rpcUrl, "getAsset", assetId),
+    dasRequest(rpcUrl, "getAssetProof", assetId),
+  ]);
+  if (!asset.compression?.compressed) {
+    throw new Error(`${assetId.toBase58()} is not a compressed NFT`);
+  }
+
+  const owner = new PublicKey(asset.ownership.owner);
+  const proof: string[] = assetProof.proof;
+  return {
+    assetId,
+    owner,
+    merkleTree: new PublicKey(assetProof.tree_id),
+    leaf: {
+      root: Buffer.from(bs58.decode(assetProof.root)),
+      dataHash: Buffer.from(bs58.decode(asset.compression.data_hash)),
+      creatorHash: Buffer.from(bs58.decode(asset.compression.creator_hash)),
+      nonce: asset.compression.leaf_id,
+      index: asset.compression.leaf_id,
+      delegate: asset.ownership.delegate ? new PublicKey(asset.ownership.delegate) : owner,
+    },
+    proof: proof.slice(0, proof.length - canopyDepth).map((node) => new PublicKey(node)),
+  };
+};
+
+const encodeLeaf = (leaf: ICnftLeaf): Buffer => {
+  return Buffer.concat([
+    leaf.root,
+    leaf.dataHash,
+    leaf.creatorHash,
+    Buffer.from(new BN(leaf.nonce).toArray("le", 8)),
+    Buffer.from(new BN(leaf.index).toArray("le", 4)),
+    leaf.delegate.toBuffer(),
+  ]);
+};
+
+const cnftKeys = (programId: PublicKey, proof: ICnftProof) => {
+  const dataKey = getCnftDataKey(programId, proof.assetId);
+  return [
+    { pubkey: proof.owner, isSigner: true, isWritable: true },
+    { pubkey: proof.merkleTree, isSigner: false, isWritable: false },
+    { pubkey: ACCOUNT_COMPRESSION_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: dataKey, isSigner: false, isWritable: true },
+    { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ...proof.proof.map((node) => ({ pubkey: node, isSigner: false, isWritable: false })),
+  ];
+};
+
+// the proof is fetched via fetchCnftProof and its owner signs as the current owner of the cNFT
+export const initializeCnftInstruction = (
+  programId: PublicKey,
+  proof: ICnftProof,
+  space: number,
+  isDynamic: boolean,
+  growthPolicy: IGrowthPolicy = { kind: GrowthPolicyOption.EXACT },
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: cnftKeys(programId, proof),
+    programId,
+    data: Buffer.concat([
+      Buffer.from([INITIALIZE_CNFT_DATA_ACCOUNT_IX]),
+      encodeLeaf(proof.leaf),
+      Buffer.from(new BN(space).toArray("le", 8)),
+      Buffer.from([isDynamic ? 1 : 0, growthPolicy.kind]),
+      growthPolicy.kind === GrowthPolicyOption.EXACT
+        ? Buffer.alloc(0)
+        : Buffer.from(new BN(growthPolicy.value ?? 0).toArray("le", 4)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const updateCnftInstruction = (
+  programId: PublicKey,
+  proof: ICnftProof,
+  offset: number,
+  data: Buffer,
+  truncate?: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: cnftKeys(programId, proof),
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_CNFT_DATA_ACCOUNT_IX]),
+      encodeLeaf(proof.leaf),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from([truncate ? 1 : 0, debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// the data account is its own authority, so the owner of the cNFT closes it in place of CloseDataAccount
+export const closeCnftInstruction = (
+  programId: PublicKey,
+  proof: ICnftProof,
+  rentReceiver: PublicKey,
+  debug?: boolean,
+  donations: IDonationsTable = { total: 0, top: [] }
+): TransactionInstruction => {
+  const dataKey = getCnftDataKey(programId, proof.assetId);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: proof.owner, isSigner: true, isWritable: false },
+      { pubkey: proof.merkleTree, isSigner: false, isWritable: false },
+      { pubkey: ACCOUNT_COMPRESSION_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: rentReceiver, isSigner: false, isWritable: true },
+      // the donations pda and the donors precede the proof nodes
+      ...donationRefundKeys(programId, dataKey, donations),
+      ...proof.proof.map((node) => ({ pubkey: node, isSigner: false, isWritable: false })),
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([CLOSE_CNFT_DATA_ACCOUNT_IX]),
+      encodeLeaf(proof.leaf),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
@@ -0,0 +1,122 @@
+import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash, randomBytes } from "crypto";
+import { getMetadataKey } from "./common/utils";
+import { MetadataReader } from "./parseData";
+
+export const COMMITMENT_SEED = "data_account_commitment";
+
+const COMMIT_VALUE_IX = 25;
+const REVEAL_VALUE_IX = 26;
+const EXPIRE_COMMITMENT_IX = 27;
+
+export interface ICommitment {
+  committer: string;
+  hash: Buffer;
+  reveal_deadline_slot: number;
+  revealed?: Buffer;
+}
+
+// mirrors commitment_hash in the program i.e., sha256(salt || value)
+export const commitmentHash = (value: Buffer, salt: Buffer): Buffer => {
+  return createHash("sha256").update(salt).update(value).digest();
+};
+
+export const generateSalt = (): Buffer => randomBytes(32);
+
+export const getCommitmentKey = (programId: PublicKey, dataKey: PublicKey, committer: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(COMMITMENT_SEED, "ascii"), dataKey.toBuffer(), committer.toBuffer()],
+    programId
+  )[0];
+};
+
+export const commitValueInstruction = (
+  programId: PublicKey,
+  committer: PublicKey,
+  dataKey: PublicKey,
+  hash: Buffer,
+  revealDeadlineSlot: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: committer, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: getCommitmentKey(programId, dataKey, committer), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([COMMIT_VALUE_IX]),
+      hash,
+      Buffer.from(new BN(revealDeadlineSlot).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const revealValueInstruction = (
+  programId: PublicKey,
+  committer: PublicKey,
+  dataKey: PublicKey,
+  value: Buffer,
+  salt: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: committer, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getCommitmentKey(programId, dataKey, committer), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([REVEAL_VALUE_IX]),
+      Buffer.from(new BN(value.length).toArray("le", 4)),
+      value,
+      salt,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const expireCommitmentInstruction = (
+  programId: PublicKey,
+  dataKey: PublicKey,
+  committer: PublicKey,
+  authority: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: getCommitmentKey(programId, dataKey, committer), isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([Buffer.from([EXPIRE_COMMITMENT_IX]), Buffer.from([debug ? 1 : 0])]),
+  });
+};
+
+export const parseCommitment = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKey: PublicKey,
+  committer: PublicKey
+): Promise<ICommitment | undefined> => {
+  const commitment_account = await connection.getAccountInfo(getCommitmentKey(programId, dataKey, committer), "confirmed");
+  if (!commitment_account) {
+    return undefined;
+  }
+  const reader = new MetadataReader(commitment_account.data);
+  return {
+    committer: reader.pubkey(),
+    hash: reader.bytes(32),
+    reveal_deadline_slot: reader.u64(),
+    revealed: reader.option(() => reader.bytes(reader.u32())),
+  };
+};
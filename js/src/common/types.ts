@@ -1,7 +1,9 @@
-enum DataStatusOption {
+export enum DataStatusOption {
     UNINITIALIZED,
     INITIALIZED,
     UPDATED,
+    COMMITTED,
+    ARCHIVED,
     FINALIZED,
 }
 
@@ -14,7 +16,53 @@ enum SerializationStatusOption {
 export enum DataTypeOption {
     CUSTOM = 0,
     JSON = 1,
-    IMG = 2
+    IMG = 2,
+    HTML = 3,
+    ALLOWLIST = 4,
+    DIRECTORY = 5,
+    CRDT = 6,
+    COMPRESSED = 7,
+    DICTIONARY = 8,
+    PNG = 9,
+    SVG = 10,
+    BORSH = 11,
+    TEXT = 12,
+}
+
+export enum CompressionOption {
+    NONE,
+    DEFLATE,
+    ZSTD,
+}
+
+export enum GrowthPolicyOption {
+    EXACT,
+    STEP,
+    FACTOR,
+}
+
+export interface IGrowthPolicy {
+    kind: GrowthPolicyOption;
+    // step in bytes for STEP or percentage for FACTOR
+    value?: number;
+}
+
+export enum LicenseOption {
+    SPDX,
+    POINTER,
+}
+
+export interface ILicense {
+    kind: LicenseOption;
+    // SPDX id (e.g., CC-BY-4.0) or pointer to the terms
+    value: string;
+}
+
+export interface IArchiveInfo {
+    hash: Buffer;
+    size: number;
+    archived_at: number;
+    pointer?: string;
 }
 
 export interface IDataAccountDataLegacy {
@@ -34,6 +82,31 @@ export interface IDataAccountStateLegacy {
     account_data: IDataAccountDataLegacy;
 };
 
+export interface IAutomationConfig {
+    delegate: string;
+    min_interval_slots: number;
+    last_heartbeat_slot: number;
+}
+
+export interface IAttestation {
+    hash: Buffer;
+    slot: number;
+    interval_slots: number;
+}
+
+export interface IDeadManSwitch {
+    successor: string;
+    inactivity_timeout_slots: number;
+    last_active_slot: number;
+}
+
+export interface IDropBoxConfig {
+    start: number;
+    fee_per_byte: number;
+    max_message_len: number;
+    min_interval_slots: number;
+}
+
 export interface IDataAccountMeta {
     data_status: DataStatusOption;
     serialization_status: SerializationStatusOption;
@@ -42,6 +115,70 @@ export interface IDataAccountMeta {
     data_version: number;
     data_type: number;
     bump_seed: number;
+    user?: string;
+    require_user_consent: boolean;
+    compression: CompressionOption;
+    content_hash?: Buffer;
+    content_len: number;
+    growth_policy: IGrowthPolicy;
+    hot_region?: {
+        offset: number;
+        len: number;
+    };
+    archive?: IArchiveInfo;
+    governance?: {
+        governance_program: string;
+        governance: string;
+    };
+    automation?: IAutomationConfig;
+    dead_man_switch?: IDeadManSwitch;
+    drop_box?: IDropBoxConfig;
+    time_lock?: {
+        reveal_after_slot: number;
+        plaintext_hash?: Buffer;
+    };
+    license?: ILicense;
+    close_lock?: {
+        cooldown_slots: number;
+        unlocked_at?: number;
+    };
+    content_keccak?: Buffer;
+    record_count: number;
+    upload?: {
+        total_len: number;
+        checksum: Buffer;
+        written: number;
+    };
+    append_only: boolean;
+    writer_sequences: {
+        writer: string;
+        sequence: number;
+    }[];
+    nft_mint?: string;
+    writers: string[];
+    expiry?: {
+        expires_at: number;
+        rent_receiver: string;
+    };
+    notification_hint?: Buffer;
+    checksum?: number;
+    attestation?: IAttestation;
+    realloc_count: number;
+    rent_paid: number;
+    freeze_authority?: string;
+    frozen: boolean;
+    compression_dictionary?: string;
+    verification_cursor?: {
+        offset: number;
+        state: number[];
+    };
+    generation: number;
+    revision: number;
+    write_delegate?: {
+        delegate: string;
+        expiry_slot: number;
+    };
+    donated_lamports: number;
 }
 
 export interface IDataAccount {
@@ -1,4 +1,5 @@
 import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
 import { readFileSync } from "fs";
 
 export const loadKeypairFromFile = (filename: string): Keypair => {
@@ -21,4 +22,35 @@ export const copyAccountData = async (connection: Connection, dataKey: PublicKey
   proc.stdin.write(data); proc.stdin.end();
 }
 
+// hash of the plaintext content, computed before any compression is applied
+export const plaintextHash = (content: Buffer): Buffer => {
+  return createHash("sha256").update(content).digest();
+}
+
+// mirrors crc32 in interface/src/checksum.rs (IEEE, as stored in the checksum of the metadata)
+const CRC32_TABLE = Array.from({ length: 256 }, (_, i) => {
+  let crc = i;
+  for (let bit = 0; bit < 8; bit++) {
+    crc = crc & 1 ? (crc >>> 1) ^ 0xedb88320 : crc >>> 1;
+  }
+  return crc >>> 0;
+});
+
+export const crc32 = (content: Buffer): number => {
+  let crc = 0xffffffff;
+  for (let i = 0; i < content.length; i++) {
+    crc = CRC32_TABLE[(crc ^ content[i]) & 0xff] ^ (crc >>> 8);
+  }
+  return (crc ^ 0xffffffff) >>> 0;
+}
+
 export const PDA_SEED = "data_account_metadata";
+// mirrors METADATA_SIZE in interface/src/state.rs
+export const METADATA_SIZE = 1609;
+
+export const getMetadataKey = (programId: PublicKey, dataKey: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEED, "ascii"), dataKey.toBuffer()],
+    programId
+  )[0];
+}
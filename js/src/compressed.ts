@@ -0,0 +1,224 @@
+import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash } from "crypto";
+import { IDataAccountMeta } from "./common/types";
+import { parseMetadata } from "./parseData";
+
+export const TREE_SEED = "data_account_tree";
+
+const INITIALIZE_METADATA_TREE_IX = 29;
+const INITIALIZE_COMPRESSED_DATA_ACCOUNT_IX = 30;
+const UPDATE_COMPRESSED_DATA_ACCOUNT_IX = 31;
+const CLOSE_COMPRESSED_DATA_ACCOUNT_IX = 32;
+
+const EMPTY_LEAF = Buffer.alloc(32);
+
+// latest metadata of a compressed data account as logged by the program
+export interface ICompressedLeaf {
+  dataKey: PublicKey;
+  leafIndex: number;
+  // borsh encoded metadata that has to be passed back to update or close
+  metadata: Buffer;
+  meta: IDataAccountMeta;
+}
+
+export const getTreeKey = (programId: PublicKey, operator: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync([Buffer.from(TREE_SEED, "ascii"), operator.toBuffer()], programId)[0];
+};
+
+const sha256 = (...buffers: Buffer[]): Buffer => {
+  const hasher = createHash("sha256");
+  buffers.forEach((buffer) => hasher.update(buffer));
+  return hasher.digest();
+};
+
+// mirrors metadata_leaf and node_hash in the program
+export const metadataLeaf = (dataKey: PublicKey, metadata: Buffer): Buffer => sha256(dataKey.toBuffer(), metadata);
+const nodeHash = (left: Buffer, right: Buffer): Buffer => sha256(Buffer.from([1]), left, right);
+
+export const initializeMetadataTreeInstruction = (
+  programId: PublicKey,
+  operator: PublicKey,
+  maxDepth: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: operator, isSigner: true, isWritable: true },
+      { pubkey: getTreeKey(programId, operator), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.from([INITIALIZE_METADATA_TREE_IX, maxDepth, debug ? 1 : 0]),
+  });
+};
+
+export const initializeCompressedDataAccountInstruction = (
+  programId: PublicKey,
+  operator: PublicKey,
+  dataKey: PublicKey,
+  authority: PublicKey,
+  space: number,
+  isDynamic: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: operator, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: true, isWritable: true },
+      { pubkey: getTreeKey(programId, operator), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([INITIALIZE_COMPRESSED_DATA_ACCOUNT_IX]),
+      authority.toBuffer(),
+      Buffer.from(new BN(space).toArray("le", 8)),
+      Buffer.from([isDynamic ? 1 : 0, debug ? 1 : 0]),
+    ]),
+  });
+};
+
+const encodeProof = (proof: Buffer[]): Buffer => {
+  return Buffer.concat([Buffer.from(new BN(proof.length).toArray("le", 4)), ...proof]);
+};
+
+export const updateCompressedDataAccountInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  treeKey: PublicKey,
+  leaf: ICompressedLeaf,
+  proof: Buffer[],
+  offset: number,
+  data: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: leaf.dataKey, isSigner: false, isWritable: true },
+      { pubkey: treeKey, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_COMPRESSED_DATA_ACCOUNT_IX]),
+      leaf.metadata,
+      Buffer.from(new BN(leaf.leafIndex).toArray("le", 8)),
+      encodeProof(proof),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const closeCompressedDataAccountInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  treeKey: PublicKey,
+  leaf: ICompressedLeaf,
+  proof: Buffer[],
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: leaf.dataKey, isSigner: false, isWritable: true },
+      { pubkey: treeKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([CLOSE_COMPRESSED_DATA_ACCOUNT_IX]),
+      leaf.metadata,
+      Buffer.from(new BN(leaf.leafIndex).toArray("le", 8)),
+      encodeProof(proof),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// rebuilds the leaves of the metadata tree from the program data logged by its transactions
+export class MetadataTreeIndex {
+  private leaves: Buffer[] = [];
+  private latest = new Map<string, ICompressedLeaf>();
+
+  constructor(readonly treeKey: PublicKey, readonly maxDepth: number) {}
+
+  static async load(connection: Connection, programId: PublicKey, operator: PublicKey): Promise<MetadataTreeIndex> {
+    const treeKey = getTreeKey(programId, operator);
+    const tree_account = await connection.getAccountInfo(treeKey, "confirmed");
+    if (!tree_account) {
+      throw new Error(`Metadata tree of ${operator.toBase58()} not found`);
+    }
+    const index = new MetadataTreeIndex(treeKey, tree_account.data.readUInt8(32));
+
+    // replay all successful transactions of the tree, oldest first
+    const signatures: string[] = [];
+    let before: string | undefined;
+    for (;;) {
+      const page = await connection.getSignaturesForAddress(treeKey, { before }, "confirmed");
+      if (page.length === 0) break;
+      page.filter((sig) => !sig.err).forEach((sig) => signatures.push(sig.signature));
+      before = page[page.length - 1].signature;
+    }
+    for (const signature of signatures.reverse()) {
+      const tx = await connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      (tx?.meta?.logMessages ?? [])
+        .filter((log) => log.startsWith("Program data: "))
+        .forEach((log) => index.apply(log.slice("Program data: ".length).split(" ").map((field) => Buffer.from(field, "base64"))));
+    }
+    return index;
+  }
+
+  // applies a compressed_metadata or compressed_close event
+  apply(fields: Buffer[]) {
+    const [kind, dataKey, leafIndex, metadata] = fields;
+    const key = new PublicKey(dataKey);
+    const index = new BN(leafIndex, "le").toNumber();
+    switch (kind.toString()) {
+      case "compressed_metadata":
+        this.leaves[index] = metadataLeaf(key, metadata);
+        this.latest.set(key.toBase58(), { dataKey: key, leafIndex: index, metadata, meta: parseMetadata(metadata) });
+        break;
+      case "compressed_close":
+        this.leaves[index] = EMPTY_LEAF;
+        this.latest.delete(key.toBase58());
+        break;
+    }
+  }
+
+  getLeaf(dataKey: PublicKey): ICompressedLeaf | undefined {
+    return this.latest.get(dataKey.toBase58());
+  }
+
+  getRoot(): Buffer {
+    return this.layers()[this.maxDepth][0];
+  }
+
+  getProof(leafIndex: number): Buffer[] {
+    return this.layers()
+      .slice(0, this.maxDepth)
+      .map((layer, level) => layer[(leafIndex >> level) ^ 1] ?? this.zero(level));
+  }
+
+  private zero(level: number): Buffer {
+    let zero = EMPTY_LEAF;
+    for (let i = 0; i < level; i++) zero = nodeHash(zero, zero);
+    return zero;
+  }
+
+  private layers(): Buffer[][] {
+    const layers = [Array.from(this.leaves, (leaf) => leaf ?? EMPTY_LEAF)];
+    for (let level = 0; level < this.maxDepth; level++) {
+      const layer = layers[level];
+      const next: Buffer[] = [];
+      for (let i = 0; i < Math.max(layer.length, 1); i += 2) {
+        next.push(nodeHash(layer[i] ?? this.zero(level), layer[i + 1] ?? this.zero(level)));
+      }
+      layers.push(next);
+    }
+    return layers;
+  }
+}
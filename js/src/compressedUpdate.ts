@@ -0,0 +1,77 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { deflateRawSync } from "zlib";
+import { DataTypeOption } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+const UPDATE_DATA_ACCOUNT_COMPRESSED_IX = 55;
+// mirrors MAX_DECOMPRESSED_LEN in program/src/deflate.rs
+export const MAX_DECOMPRESSED_LEN = 10 * 1024;
+// room left for the payload in a transaction after the signatures, accounts and other instruction fields
+const MAX_PAYLOAD_LEN = 881;
+
+// compresses the content and lets the program expand it on-chain, e.g. for JSON or SVG
+export const updateCompressedInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  dataType: DataTypeOption,
+  content: Buffer,
+  offset: number,
+  truncate?: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  if (content.length > MAX_DECOMPRESSED_LEN) {
+    throw new Error(`content of ${content.length} bytes expands beyond ${MAX_DECOMPRESSED_LEN} bytes`);
+  }
+  const data = deflateRawSync(content, { level: 9 });
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_DATA_ACCOUNT_COMPRESSED_IX, dataType]),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(content.length).toArray("le", 4)),
+      Buffer.from([truncate ? 1 : 0]),
+      // no sequence
+      Buffer.from([0]),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// splits the content into the fewest parts whose compressed payload fits in a transaction,
+// each expanding to at most MAX_DECOMPRESSED_LEN bytes, and returns one instruction per part
+export const updateCompressedInstructions = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  dataType: DataTypeOption,
+  content: Buffer,
+  debug?: boolean
+): TransactionInstruction[] => {
+  const ixs: TransactionInstruction[] = [];
+  let offset = 0;
+  while (offset < content.length) {
+    let len = Math.min(MAX_DECOMPRESSED_LEN, content.length - offset);
+    // halve the part until it compresses small enough
+    while (len > 1 && deflateRawSync(content.subarray(offset, offset + len), { level: 9 }).length > MAX_PAYLOAD_LEN) {
+      len = Math.floor(len / 2);
+    }
+    const part = content.subarray(offset, offset + len);
+    ixs.push(
+      updateCompressedInstruction(programId, authority, dataKey, dataType, part, offset, offset + len === content.length, debug)
+    );
+    offset += len;
+  }
+  return ixs;
+};
@@ -0,0 +1,95 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+import { MetadataReader } from "./parseData";
+import { getSessionKey } from "./session";
+
+const INITIALIZE_CRDT_IX = 35;
+const APPLY_DELTA_IX = 36;
+
+export enum CrdtKindOption {
+  GSET,
+  PNCOUNTER,
+}
+
+export type CrdtDelta = { add: Buffer[] } | { increment: number } | { decrement: number };
+
+export type CrdtState =
+  | { kind: CrdtKindOption.GSET; elements: Buffer[] }
+  | { kind: CrdtKindOption.PNCOUNTER; entries: { writer: string; increments: number; decrements: number }[]; value: number };
+
+// decodes the payload (the first content_len bytes) of a CRDT data account
+export const parseCrdt = (payload: Buffer): CrdtState => {
+  const reader = new MetadataReader(payload);
+  if (reader.u8() === CrdtKindOption.GSET) {
+    const elements: Buffer[] = [];
+    for (let len = reader.u32(); len > 0; --len) {
+      elements.push(reader.bytes(reader.u32()));
+    }
+    return { kind: CrdtKindOption.GSET, elements };
+  }
+  const entries: { writer: string; increments: number; decrements: number }[] = [];
+  for (let len = reader.u32(); len > 0; --len) {
+    entries.push({ writer: reader.pubkey(), increments: reader.u64(), decrements: reader.u64() });
+  }
+  const value = entries.reduce((sum, { increments, decrements }) => sum + increments - decrements, 0);
+  return { kind: CrdtKindOption.PNCOUNTER, entries, value };
+};
+
+export const initializeCrdtInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  kind: CrdtKindOption,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.from([INITIALIZE_CRDT_IX, kind, debug ? 1 : 0]),
+  });
+};
+
+const encodeDelta = (delta: CrdtDelta): Buffer => {
+  if ("add" in delta) {
+    return Buffer.concat([
+      Buffer.from([0]),
+      Buffer.from(new BN(delta.add.length).toArray("le", 4)),
+      ...delta.add.map((element) => Buffer.concat([Buffer.from(new BN(element.length).toArray("le", 4)), element])),
+    ]);
+  }
+  if ("increment" in delta) {
+    return Buffer.concat([Buffer.from([1]), Buffer.from(new BN(delta.increment).toArray("le", 8))]);
+  }
+  return Buffer.concat([Buffer.from([2]), Buffer.from(new BN(delta.decrement).toArray("le", 8))]);
+};
+
+// writer is the authority or a session key whose scope includes DELTA and covers the whole payload
+export const applyDeltaInstruction = (
+  programId: PublicKey,
+  writer: PublicKey,
+  dataKey: PublicKey,
+  delta: CrdtDelta,
+  isSessionKey?: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  const keys = [
+    { pubkey: writer, isSigner: true, isWritable: true },
+    { pubkey: dataKey, isSigner: false, isWritable: true },
+    { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+  ];
+  if (isSessionKey) {
+    keys.push({ pubkey: getSessionKey(programId, dataKey, writer), isSigner: false, isWritable: false });
+  }
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: Buffer.concat([Buffer.from([APPLY_DELTA_IX]), encodeDelta(delta), Buffer.from([debug ? 1 : 0])]),
+  });
+};
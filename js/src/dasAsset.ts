@@ -0,0 +1,137 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import * as bs58 from "bs58";
+import { DataStatusOption, DataTypeOption } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { IExportRow, snapshotDataAccounts } from "./exportSqlite";
+import { dataTypeMime } from "./mime";
+
+// subset of the asset shape returned by the DAS getAsset API that asset tooling displays,
+// plus a data_account section with the fields that have no DAS equivalent
+export interface IDasAsset {
+  interface: "Custom";
+  id: string;
+  content: {
+    $schema: string;
+    json_uri: string;
+    files: { uri: string; mime: string }[];
+    metadata: { name: string; symbol: string };
+    links: { external_url: string };
+  };
+  authorities: { address: string; scopes: string[] }[];
+  compression: {
+    eligible: boolean;
+    compressed: boolean;
+    data_hash: string;
+    creator_hash: string;
+    asset_hash: string;
+    tree: string;
+    seq: number;
+    leaf_id: number;
+  };
+  grouping: { group_key: string; group_value: string }[];
+  creators: { address: string; share: number; verified: boolean }[];
+  ownership: {
+    frozen: boolean;
+    delegated: boolean;
+    delegate: string | null;
+    ownership_model: "single";
+    owner: string;
+  };
+  supply: null;
+  mutable: boolean;
+  burnt: boolean;
+  data_account: {
+    metadata: string;
+    data_type: DataTypeOption;
+    data_status: DataStatusOption;
+    content_len: number;
+    content_hash: string | null;
+    content_keccak: string | null;
+    snapshot_slot: number;
+  };
+}
+
+const DATA_TYPE_NAMES: Record<number, string> = {
+  [DataTypeOption.CUSTOM]: "CUSTOM",
+  [DataTypeOption.JSON]: "JSON",
+  [DataTypeOption.IMG]: "IMG",
+  [DataTypeOption.HTML]: "HTML",
+  [DataTypeOption.ALLOWLIST]: "ALLOWLIST",
+  [DataTypeOption.DIRECTORY]: "DIRECTORY",
+  [DataTypeOption.CRDT]: "CRDT",
+  [DataTypeOption.COMPRESSED]: "COMPRESSED",
+  [DataTypeOption.DICTIONARY]: "DICTIONARY",
+  [DataTypeOption.PNG]: "PNG",
+  [DataTypeOption.SVG]: "SVG",
+  [DataTypeOption.BORSH]: "BORSH",
+  [DataTypeOption.TEXT]: "TEXT",
+};
+
+// renders a snapshotted data account as a DAS asset whose content links point to the data account
+// through the gateway at gatewayUrl (served as `${gatewayUrl}/${data account}`)
+export const toDasAsset = (programId: PublicKey, row: IExportRow, gatewayUrl: string, slot: number): IDasAsset => {
+  const { dataKey, meta, content } = row;
+  const id = dataKey.toBase58();
+  const uri = `${gatewayUrl.replace(/\/$/, "")}/${id}`;
+  const isJson = meta.data_type === DataTypeOption.JSON;
+  return {
+    interface: "Custom",
+    id,
+    content: {
+      $schema: "https://schema.metaplex.com/nft1.0.json",
+      // JSON content is the off-chain metadata itself, anything else is the single file of the asset
+      json_uri: isJson ? uri : "",
+      files: [{ uri, mime: dataTypeMime(meta.data_type, content) }],
+      metadata: { name: id, symbol: DATA_TYPE_NAMES[meta.data_type] ?? "CUSTOM" },
+      links: { external_url: uri },
+    },
+    authorities: [
+      { address: meta.authority, scopes: ["full"] },
+      ...meta.writers.map((writer) => ({ address: writer, scopes: ["metadata"] })),
+    ],
+    compression: {
+      eligible: false,
+      compressed: false,
+      data_hash: meta.content_hash ? bs58.encode(meta.content_hash) : "",
+      creator_hash: "",
+      asset_hash: "",
+      tree: "",
+      seq: 0,
+      leaf_id: 0,
+    },
+    grouping: meta.nft_mint ? [{ group_key: "nft", group_value: meta.nft_mint }] : [],
+    creators: [],
+    ownership: {
+      frozen: meta.data_status === DataStatusOption.FINALIZED,
+      delegated: false,
+      delegate: null,
+      ownership_model: "single",
+      // a per-user data account belongs to its user even though the app authority writes it
+      owner: meta.user ?? meta.authority,
+    },
+    supply: null,
+    mutable: meta.data_status !== DataStatusOption.FINALIZED,
+    burnt: false,
+    data_account: {
+      metadata: getMetadataKey(programId, dataKey).toBase58(),
+      data_type: meta.data_type,
+      data_status: meta.data_status,
+      content_len: meta.content_len,
+      content_hash: meta.content_hash?.toString("hex") ?? null,
+      content_keccak: meta.content_keccak?.toString("hex") ?? null,
+      snapshot_slot: slot,
+    },
+  };
+};
+
+// snapshots the data accounts (see snapshotDataAccounts) and renders them as DAS assets,
+// e.g. to serve them from a getAsset/getAssetBatch compatible endpoint
+export const getDasAssets = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[],
+  gatewayUrl: string
+): Promise<IDasAsset[]> => {
+  const { slot, rows } = await snapshotDataAccounts(connection, programId, dataKeys);
+  return rows.map((row) => toDasAsset(programId, row, gatewayUrl, slot));
+};
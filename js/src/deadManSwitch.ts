@@ -0,0 +1,53 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDeadManSwitch } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+const SET_DEAD_MAN_SWITCH_IX = 18;
+const CLAIM_DORMANT_AUTHORITY_IX = 19;
+
+export const setDeadManSwitchInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  successor: PublicKey | null,
+  inactivityTimeoutSlots: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_DEAD_MAN_SWITCH_IX]),
+      successor ? Buffer.concat([Buffer.from([1]), successor.toBuffer()]) : Buffer.from([0]),
+      Buffer.from(new BN(inactivityTimeoutSlots).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const claimDormantAuthorityInstruction = (
+  programId: PublicKey,
+  successor: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: successor, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([Buffer.from([CLAIM_DORMANT_AUTHORITY_IX]), Buffer.from([debug ? 1 : 0])]),
+  });
+};
+
+// mirrors DeadManSwitch::is_dormant in the program
+export const isAuthorityDormant = (deadManSwitch: IDeadManSwitch, currentSlot: number): boolean => {
+  return currentSlot - deadManSwitch.last_active_slot > deadManSwitch.inactivity_timeout_slots;
+};
@@ -0,0 +1,49 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const DELEGATE_WRITE_AUTHORITY_IX = 69;
+const REVOKE_DELEGATE_IX = 70;
+
+// lets the delegate (e.g., a bot maintaining a data feed) sign UpdateDataAccount until expirySlot (inclusive)
+// replaces any previous delegate, so calling it again extends the delegation
+export const delegateWriteAuthorityInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  delegate: PublicKey,
+  expirySlot: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([DELEGATE_WRITE_AUTHORITY_IX]),
+      delegate.toBuffer(),
+      Buffer.from(new BN(expirySlot).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const revokeDelegateInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([REVOKE_DELEGATE_IX, debug ? 1 : 0]),
+  });
+};
@@ -0,0 +1,107 @@
+import { Connection, Keypair, PublicKey, sendAndConfirmTransaction, Transaction, TransactionInstruction } from "@solana/web3.js";
+import { execFileSync } from "child_process";
+import { mkdtempSync, readFileSync, rmSync, writeFileSync } from "fs";
+import { tmpdir } from "os";
+import { join } from "path";
+import { DataTypeOption } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { finalizeInstruction } from "./finalize";
+import { DataAccountSink } from "./worker";
+
+const SET_COMPRESSION_DICTIONARY_IX = 64;
+
+// mirrors ZSTD_DICTIONARY_MAGIC in interface/src/state.rs
+export const ZSTD_DICTIONARY_MAGIC = Buffer.from([0x37, 0xa4, 0x30, 0xec]);
+
+// sets (or clears, if dictionaryKey is undefined) the finalized DICTIONARY data account
+// the ZSTD content of the data account is compressed with
+export const setCompressionDictionaryInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  dictionaryKey?: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  const keys = [
+    { pubkey: authority, isSigner: true, isWritable: false },
+    { pubkey: dataKey, isSigner: false, isWritable: false },
+    { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+  ];
+  if (dictionaryKey) {
+    keys.push(
+      { pubkey: dictionaryKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dictionaryKey), isSigner: false, isWritable: false }
+    );
+  }
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_COMPRESSION_DICTIONARY_IX]),
+      dictionaryKey ? Buffer.concat([Buffer.from([1]), dictionaryKey.toBuffer()]) : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// runs the zstd CLI, which has to be on the PATH, in a scratch directory
+const zstd = <T>(run: (dir: string, exec: (args: string[], input?: Buffer) => Buffer) => T): T => {
+  const dir = mkdtempSync(join(tmpdir(), "zstd-"));
+  try {
+    return run(dir, (args, input) => execFileSync("zstd", ["-q", ...args], { input, maxBuffer: 64 * 1024 * 1024 }));
+  } finally {
+    rmSync(dir, { recursive: true, force: true });
+  }
+};
+
+// trains a dictionary on samples of similar content (e.g., JSON documents of the same schema)
+// the training is deterministic, so the same samples always give the same dictionary
+export const trainDictionary = (samples: Buffer[], maxSize = 16 * 1024): Buffer =>
+  zstd((dir, exec) => {
+    const files = samples.map((sample, i) => {
+      const file = join(dir, `sample-${i}`);
+      writeFileSync(file, sample);
+      return file;
+    });
+    const output = join(dir, "dictionary");
+    exec(["--train", ...files, `--maxdict=${maxSize}`, "-o", output]);
+    return readFileSync(output);
+  });
+
+export const compressWithDictionary = (content: Buffer, dictionary: Buffer, level = 19): Buffer =>
+  zstd((dir, exec) => {
+    const file = join(dir, "dictionary");
+    writeFileSync(file, dictionary);
+    return exec([`-${level}`, "-c", "-D", file], content);
+  });
+
+export const decompressWithDictionary = (content: Buffer, dictionary: Buffer): Buffer =>
+  zstd((dir, exec) => {
+    const file = join(dir, "dictionary");
+    writeFileSync(file, dictionary);
+    return exec(["-d", "-c", "-D", file], content);
+  });
+
+// writes the dictionary to an initialized data account of the authority and finalizes it
+// so it can be referenced by setCompressionDictionaryInstruction
+export const publishDictionary = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataKey: PublicKey,
+  dictionary: Buffer,
+  debug?: boolean
+): Promise<void> => {
+  if (!dictionary.subarray(0, 4).equals(ZSTD_DICTIONARY_MAGIC)) {
+    throw new Error("not a zstd dictionary");
+  }
+  await new DataAccountSink(connection, programId, authority, dataKey, DataTypeOption.DICTIONARY, debug).write(
+    dictionary
+  );
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(finalizeInstruction(programId, authority.publicKey, dataKey, debug)),
+    [authority],
+    { commitment: "confirmed" }
+  );
+};
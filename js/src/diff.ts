@@ -0,0 +1,135 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+// largest data of a single UpdateDataAccount that fits in a transaction
+const PART_SIZE = 881;
+// changed ranges closer than this are sent as one write since each write costs more than the bytes in between
+const MERGE_GAP = 32;
+const LAMPORTS_PER_SIGNATURE = 5000;
+// bytes of each side of a changed range shown in the byte diff
+const PREVIEW_LEN = 32;
+
+export interface IPatch {
+  offset: number;
+  data: Buffer;
+  // the last write truncates the content when the new content is shorter
+  truncate: boolean;
+}
+
+export interface IDiffReport {
+  patches: IPatch[];
+  transactions: number;
+  fees: number;
+  // rent-exempt minimum of the new length minus the current one (negative once released by realloc_down)
+  rentDelta: number;
+  lines: string[];
+}
+
+// computes the minimal UpdateDataAccount writes that turn current into next
+export const computePatches = (current: Buffer, next: Buffer): IPatch[] => {
+  const ranges: [number, number][] = [];
+  for (let i = 0; i < next.length; ++i) {
+    if (i < current.length && current[i] === next[i]) continue;
+    const last = ranges[ranges.length - 1];
+    if (last && i - last[1] <= MERGE_GAP) {
+      last[1] = i + 1;
+    } else {
+      ranges.push([i, i + 1]);
+    }
+  }
+  const patches: IPatch[] = ranges.flatMap(([start, end]) => {
+    const parts: IPatch[] = [];
+    for (let offset = start; offset < end; offset += PART_SIZE) {
+      parts.push({ offset, data: next.subarray(offset, Math.min(offset + PART_SIZE, end)), truncate: false });
+    }
+    return parts;
+  });
+  if (next.length < current.length) {
+    const last = patches[patches.length - 1];
+    if (last && last.offset + last.data.length === next.length) {
+      last.truncate = true;
+    } else {
+      patches.push({ offset: next.length, data: Buffer.alloc(0), truncate: true });
+    }
+  }
+  return patches;
+};
+
+const preview = (bytes: Buffer): string =>
+  bytes.length > PREVIEW_LEN ? `${bytes.subarray(0, PREVIEW_LEN).toString("hex")}...` : bytes.toString("hex");
+
+// describes each write as the bytes it replaces and the bytes it writes
+export const byteDiff = (current: Buffer, patches: IPatch[]): string[] => {
+  return patches.map(({ offset, data, truncate }) => {
+    const end = truncate ? current.length : offset + data.length;
+    const replaced = current.subarray(Math.min(offset, current.length), Math.min(end, current.length));
+    return `@${offset} -${replaced.length} +${data.length}${truncate ? " (truncate)" : ""}: -${preview(
+      replaced
+    )} +${preview(data)}`;
+  });
+};
+
+// describes the added (+), removed (-) and changed (~) values between two JSON values by path
+export const jsonDiff = (current: unknown, next: unknown, path = "$"): string[] => {
+  const isObject = (v: unknown): v is Record<string, unknown> => !!v && typeof v === "object";
+  if (isObject(current) && isObject(next) && Array.isArray(current) === Array.isArray(next)) {
+    const keys = Object.keys(current).concat(Object.keys(next).filter((key) => !(key in current)));
+    return keys.flatMap((key) => {
+      const child = Array.isArray(next) ? `${path}[${key}]` : `${path}.${key}`;
+      if (!(key in next)) return [`- ${child}`];
+      if (!(key in current)) return [`+ ${child}: ${JSON.stringify(next[key])}`];
+      return jsonDiff(current[key], next[key], child);
+    });
+  }
+  if (JSON.stringify(current) === JSON.stringify(next)) return [];
+  return [`~ ${path}: ${JSON.stringify(current)} -> ${JSON.stringify(next)}`];
+};
+
+const parseJSON = (content: Buffer): { value?: unknown } => {
+  try {
+    return { value: JSON.parse(content.toString("utf8")) };
+  } catch {
+    return {};
+  }
+};
+
+// downloads the content of the data account and reports what uploading next would send and cost, without sending anything
+// a JSON diff is shown when both contents are JSON, a byte diff otherwise
+export const dryRunDiff = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKey: PublicKey,
+  next: Buffer
+): Promise<IDiffReport> => {
+  const [data_account, meta_account] = await connection.getMultipleAccountsInfo(
+    [dataKey, getMetadataKey(programId, dataKey)],
+    "confirmed"
+  );
+  if (!data_account || !meta_account) {
+    throw new Error(`Data account ${dataKey.toBase58()} not found`);
+  }
+  const meta = parseMetadata(meta_account.data);
+  const current = data_account.data.subarray(0, Math.min(meta.content_len, data_account.data.length));
+
+  const patches = computePatches(current, next);
+  const newLen = meta.is_dynamic ? next.length : data_account.data.length;
+  const rentDelta =
+    (await connection.getMinimumBalanceForRentExemption(newLen, "confirmed")) -
+    (await connection.getMinimumBalanceForRentExemption(data_account.data.length, "confirmed"));
+
+  const currentJSON = parseJSON(current);
+  const nextJSON = parseJSON(next);
+  const diff =
+    currentJSON.value !== undefined && nextJSON.value !== undefined
+      ? jsonDiff(currentJSON.value, nextJSON.value)
+      : byteDiff(current, patches);
+
+  const fees = patches.length * LAMPORTS_PER_SIGNATURE;
+  const lines = [
+    `${dataKey.toBase58()}: ${current.length} -> ${next.length} bytes`,
+    ...diff,
+    `${patches.length} transactions, ${fees} lamports in fees, ${rentDelta} lamports of rent`,
+  ];
+  return { patches, transactions: patches.length, fees, rentDelta, lines };
+};
@@ -0,0 +1,73 @@
+import { AccountMeta, Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+import { MetadataReader } from "./parseData";
+
+export const DONATIONS_SEED = "data_account_donations";
+
+const DONATE_IX = 22;
+
+export interface IDonation {
+  donor: string;
+  amount: number;
+}
+
+export interface IDonationsTable {
+  total: number;
+  top: IDonation[];
+}
+
+export const getDonationsKey = (programId: PublicKey, dataKey: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync([Buffer.from(DONATIONS_SEED, "ascii"), dataKey.toBuffer()], programId)[0];
+};
+
+// remaining accounts of the instructions closing a data account (e.g., CloseDataAccount) that refund
+// its unspent donations to the top donors and close its donations pda (none if it never received a donation)
+export const donationRefundKeys = (
+  programId: PublicKey,
+  dataKey: PublicKey,
+  donations: IDonationsTable
+): AccountMeta[] => {
+  if (donations.top.length === 0) {
+    return [];
+  }
+  return [
+    { pubkey: getDonationsKey(programId, dataKey), isSigner: false, isWritable: true },
+    ...donations.top.map((donation) => ({ pubkey: new PublicKey(donation.donor), isSigner: false, isWritable: true })),
+  ];
+};
+
+export const donateInstruction = (
+  programId: PublicKey,
+  donor: PublicKey,
+  dataKey: PublicKey,
+  lamports: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: donor, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: getDonationsKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([DONATE_IX]),
+      Buffer.from(new BN(lamports).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const parseDonations = async (connection: Connection, programId: PublicKey, dataKey: PublicKey): Promise<IDonationsTable> => {
+  const donations_account = await connection.getAccountInfo(getDonationsKey(programId, dataKey), "confirmed");
+  if (!donations_account) {
+    return { total: 0, top: [] };
+  }
+  const reader = new MetadataReader(donations_account.data);
+  const total = reader.u64();
+  const top = Array.from({ length: reader.u32() }, () => ({ donor: reader.pubkey(), amount: reader.u64() }));
+  return { total, top };
+};
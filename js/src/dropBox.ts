@@ -0,0 +1,95 @@
+import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDropBoxConfig } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { MetadataReader, parseData } from "./parseData";
+
+export const DROP_BOX_SEED = "data_account_drop_box";
+
+const SET_DROP_BOX_IX = 20;
+const POST_TO_DROP_BOX_IX = 21;
+
+export interface IDropBoxMessage {
+  sender: string;
+  slot: number;
+  message: Buffer;
+}
+
+export const getDropBoxSenderKey = (programId: PublicKey, dataKey: PublicKey, sender: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DROP_BOX_SEED, "ascii"), dataKey.toBuffer(), sender.toBuffer()],
+    programId
+  )[0];
+};
+
+export const setDropBoxInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  dropBox: IDropBoxConfig | null,
+  debug?: boolean
+): TransactionInstruction => {
+  const config = dropBox
+    ? Buffer.concat([
+        Buffer.from([1]),
+        Buffer.from(new BN(dropBox.start).toArray("le", 8)),
+        Buffer.from(new BN(dropBox.fee_per_byte).toArray("le", 8)),
+        Buffer.from(new BN(dropBox.max_message_len).toArray("le", 2)),
+        Buffer.from(new BN(dropBox.min_interval_slots).toArray("le", 8)),
+      ])
+    : Buffer.from([0]);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([Buffer.from([SET_DROP_BOX_IX]), config, Buffer.from([debug ? 1 : 0])]),
+  });
+};
+
+export const postToDropBoxInstruction = (
+  programId: PublicKey,
+  sender: PublicKey,
+  dataKey: PublicKey,
+  authority: PublicKey,
+  message: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: sender, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: getDropBoxSenderKey(programId, dataKey, sender), isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([POST_TO_DROP_BOX_IX]),
+      Buffer.from(new BN(message.length).toArray("le", 4)),
+      message,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// reads all the messages appended to the drop box region of the data account
+export const parseDropBox = async (connection: Connection, programId: PublicKey, dataKey: PublicKey): Promise<IDropBoxMessage[]> => {
+  const { meta, data } = await parseData(connection, dataKey, getMetadataKey(programId, dataKey));
+  if (!meta.drop_box || !data) {
+    return [];
+  }
+  const region = data.subarray(meta.drop_box.start, meta.content_len);
+  const reader = new MetadataReader(region);
+  const messages: IDropBoxMessage[] = [];
+  let read = 0;
+  while (read < region.length) {
+    const message = { sender: reader.pubkey(), slot: reader.u64(), message: reader.bytes(reader.u32()) };
+    read += 32 + 8 + 4 + message.message.length;
+    messages.push(message);
+  }
+  return messages;
+};
@@ -0,0 +1,69 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { donationRefundKeys, IDonationsTable } from "./donations";
+
+const SET_EXPIRY_IX = 51;
+const EXPIRE_DATA_ACCOUNT_IX = 52;
+
+// expiresAt is a unix timestamp; passing no expiry clears it
+export const setExpiryInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  expiry?: { expiresAt: number; rentReceiver: PublicKey },
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_EXPIRY_IX]),
+      expiry
+        ? Buffer.concat([
+            Buffer.from([1]),
+            Buffer.from(new BN(expiry.expiresAt).toArray("le", 8)),
+            expiry.rentReceiver.toBuffer(),
+          ])
+        : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// permissionless, e.g. sent by a crank once isExpired holds
+// the user must co-sign if the data account requires their consent
+// the donations (see parseDonations) are refunded to the top donors
+export const expireInstruction = (
+  programId: PublicKey,
+  dataKey: PublicKey,
+  rentReceiver: PublicKey,
+  user?: PublicKey,
+  debug?: boolean,
+  donations: IDonationsTable = { total: 0, top: [] }
+): TransactionInstruction => {
+  const keys = [
+    { pubkey: dataKey, isSigner: false, isWritable: true },
+    { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    { pubkey: rentReceiver, isSigner: false, isWritable: true },
+  ];
+  if (user) {
+    keys.push({ pubkey: user, isSigner: true, isWritable: false });
+  }
+  keys.push(...donationRefundKeys(programId, dataKey, donations));
+  return new TransactionInstruction({
+    keys,
+    programId,
+    data: Buffer.from([EXPIRE_DATA_ACCOUNT_IX, debug ? 1 : 0]),
+  });
+};
+
+// whether the data account can be expired at the unix timestamp (ignoring any close lock)
+export const isExpired = (meta: IDataAccountMeta, unixTimestamp: number): boolean => {
+  return meta.expiry !== undefined && unixTimestamp >= meta.expiry.expires_at;
+};
@@ -0,0 +1,118 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import { spawn } from "child_process";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+export interface IExportRow {
+  dataKey: PublicKey;
+  meta: IDataAccountMeta;
+  accountLen: number;
+  lamports: number;
+  // the first content_len bytes of the data account (empty once archived)
+  content: Buffer;
+}
+
+const SCHEMA = `CREATE TABLE IF NOT EXISTS data_accounts (
+  data_key TEXT PRIMARY KEY,
+  meta_key TEXT NOT NULL,
+  authority TEXT NOT NULL,
+  data_status INTEGER NOT NULL,
+  serialization_status INTEGER NOT NULL,
+  is_dynamic INTEGER NOT NULL,
+  data_version INTEGER NOT NULL,
+  data_type INTEGER NOT NULL,
+  compression INTEGER NOT NULL,
+  content_hash TEXT,
+  content_len INTEGER NOT NULL,
+  account_len INTEGER NOT NULL,
+  lamports INTEGER NOT NULL,
+  archived_pointer TEXT,
+  license TEXT,
+  snapshot_slot INTEGER NOT NULL,
+  content BLOB NOT NULL
+);`;
+
+const text = (value?: string): string => (value === undefined ? "NULL" : `'${value.replace(/'/g, "''")}'`);
+
+// fetches the data accounts along with their metadata in batches of getMultipleAccountsInfo
+export const snapshotDataAccounts = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[]
+): Promise<{ slot: number; rows: IExportRow[] }> => {
+  const slot = await connection.getSlot("confirmed");
+  const rows: IExportRow[] = [];
+  // 50 data accounts and their 50 metadata accounts per request
+  for (let i = 0; i < dataKeys.length; i += 50) {
+    const batch = dataKeys.slice(i, i + 50);
+    const keys = batch.flatMap((dataKey) => [dataKey, getMetadataKey(programId, dataKey)]);
+    const infos = await connection.getMultipleAccountsInfo(keys, "confirmed");
+    batch.forEach((dataKey, idx) => {
+      const data_account = infos[2 * idx];
+      const meta_account = infos[2 * idx + 1];
+      if (!meta_account || meta_account.data.length === 0) return;
+      const meta = parseMetadata(meta_account.data);
+      const data = data_account?.data ?? Buffer.alloc(0);
+      rows.push({
+        dataKey,
+        meta,
+        accountLen: data.length,
+        lamports: data_account?.lamports ?? 0,
+        content: data.subarray(0, Math.min(meta.content_len, data.length)),
+      });
+    });
+  }
+  return { slot, rows };
+};
+
+// renders the rows as a SQL script that creates (or upserts into) the data_accounts table
+export const toSql = (programId: PublicKey, slot: number, rows: IExportRow[]): string => {
+  const statements = rows.map(
+    ({ dataKey, meta, accountLen, lamports, content }) =>
+      `INSERT OR REPLACE INTO data_accounts VALUES (${[
+        text(dataKey.toBase58()),
+        text(getMetadataKey(programId, dataKey).toBase58()),
+        text(meta.authority),
+        meta.data_status,
+        meta.serialization_status,
+        meta.is_dynamic ? 1 : 0,
+        meta.data_version,
+        meta.data_type,
+        meta.compression,
+        text(meta.content_hash?.toString("hex")),
+        meta.content_len,
+        accountLen,
+        lamports,
+        text(meta.archive?.pointer),
+        text(meta.license?.value),
+        slot,
+        `X'${content.toString("hex")}'`,
+      ].join(", ")});`
+  );
+  return [SCHEMA, "BEGIN;", ...statements, "COMMIT;", ""].join("\n");
+};
+
+// snapshots the data accounts into a local SQLite file using the sqlite3 command line shell
+// e.g. exportToSqlite(connection, programId, dataKeys, "snapshot.db") then `sqlite3 snapshot.db "SELECT ..."`
+export const exportToSqlite = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[],
+  filename: string,
+  debug?: boolean
+): Promise<number> => {
+  const { slot, rows } = await snapshotDataAccounts(connection, programId, dataKeys);
+  const sql = toSql(programId, slot, rows);
+  await new Promise<void>((resolve, reject) => {
+    const proc = spawn("sqlite3", [filename]);
+    proc.on("error", reject);
+    proc.on("close", (code) => (code === 0 ? resolve() : reject(new Error(`sqlite3 exited with ${code}`))));
+    proc.stdin.write(sql);
+    proc.stdin.end();
+  });
+  if (debug) {
+    console.log(`${rows.length} data accounts exported to ${filename} at slot ${slot}`);
+  }
+  return rows.length;
+};
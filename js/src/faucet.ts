@@ -0,0 +1,75 @@
+import { Connection, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { METADATA_SIZE } from "./common/utils";
+
+const MAINNET_BETA_GENESIS_HASH = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+// largest airdrop the devnet faucet grants per request
+const MAX_AIRDROP_LAMPORTS = 2 * LAMPORTS_PER_SOL;
+const LAMPORTS_PER_SIGNATURE = 5000;
+
+export interface IFaucetOptions {
+  // cap on the total lamports airdropped for a single upload
+  maxLamports?: number;
+  maxAttempts?: number;
+  // delay before the first retry, doubled after every failed airdrop
+  backoffMs?: number;
+  debug?: boolean;
+}
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+// rent of the data account and its metadata pda along with the fees of the upload transactions
+export const estimateUploadCost = async (connection: Connection, space: number, numTransactions: number): Promise<number> => {
+  const dataRent = await connection.getMinimumBalanceForRentExemption(space, "confirmed");
+  const metadataRent = await connection.getMinimumBalanceForRentExemption(METADATA_SIZE, "confirmed");
+  // the initialize transaction is also signed by the data account
+  const fees = (numTransactions + 1) * LAMPORTS_PER_SIGNATURE;
+  return dataRent + metadataRent + fees;
+};
+
+// airdrops the missing lamports to the payer before an upload (only on clusters with a faucet)
+// returns the lamports airdropped
+export const ensureFaucetFunds = async (
+  connection: Connection,
+  payer: PublicKey,
+  required: number,
+  options: IFaucetOptions = {}
+): Promise<number> => {
+  const { maxLamports = 5 * LAMPORTS_PER_SOL, maxAttempts = 5, backoffMs = 1000, debug } = options;
+  if ((await connection.getGenesisHash()) === MAINNET_BETA_GENESIS_HASH) {
+    throw new Error("Airdrops are not available on mainnet-beta");
+  }
+
+  let airdropped = 0;
+  let failures = 0;
+  for (;;) {
+    const balance = await connection.getBalance(payer, "confirmed");
+    if (balance >= required) {
+      return airdropped;
+    }
+    const deficit = required - balance;
+    if (airdropped + deficit > maxLamports) {
+      throw new Error(`Upload needs ${deficit} more lamports which exceeds the airdrop cap of ${maxLamports}`);
+    }
+
+    const lamports = Math.min(deficit, MAX_AIRDROP_LAMPORTS);
+    try {
+      if (debug) {
+        console.log(`Requesting airdrop of ${lamports / LAMPORTS_PER_SOL} SOL...`);
+      }
+      const signature = await connection.requestAirdrop(payer, lamports);
+      const latestBlockhash = await connection.getLatestBlockhash("confirmed");
+      await connection.confirmTransaction({ signature, ...latestBlockhash }, "confirmed");
+      airdropped += lamports;
+    } catch (e) {
+      // the faucet is rate limited, so back off before retrying
+      failures += 1;
+      if (failures >= maxAttempts) {
+        throw new Error(`Airdrop failed after ${failures} attempts: ${e}`);
+      }
+      if (debug) {
+        console.log(`Airdrop failed, retrying in ${backoffMs * 2 ** (failures - 1)}ms`);
+      }
+      await sleep(backoffMs * 2 ** (failures - 1));
+    }
+  }
+};
@@ -0,0 +1,21 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const FINALIZE_DATA_ACCOUNT_IX = 39;
+
+export const finalizeInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([FINALIZE_DATA_ACCOUNT_IX, debug ? 1 : 0]),
+  });
+};
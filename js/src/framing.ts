@@ -0,0 +1,52 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const APPEND_FRAMED_RECORD_IX = 40;
+// mirrors RECORD_PREFIX_SIZE in program/src/framing.rs
+export const RECORD_PREFIX_SIZE = 4;
+
+// frames each record as its length (u32 little-endian) followed by its bytes
+export const encodeRecords = (records: Buffer[]): Buffer => {
+  return Buffer.concat(
+    records.flatMap((record) => [Buffer.from(new BN(record.length).toArray("le", RECORD_PREFIX_SIZE)), record])
+  );
+};
+
+// splits the content (the first content_len bytes) of a data account into its framed records
+// the remainder is non-empty only if the last record is incomplete
+export const parseRecords = (content: Buffer): { records: Buffer[]; remainder: Buffer } => {
+  const records: Buffer[] = [];
+  let offset = 0;
+  while (offset + RECORD_PREFIX_SIZE <= content.length) {
+    const len = content.readUInt32LE(offset);
+    if (offset + RECORD_PREFIX_SIZE + len > content.length) break;
+    records.push(content.subarray(offset + RECORD_PREFIX_SIZE, offset + RECORD_PREFIX_SIZE + len));
+    offset += RECORD_PREFIX_SIZE + len;
+  }
+  return { records, remainder: content.subarray(offset) };
+};
+
+export const appendFramedRecordInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  record: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([APPEND_FRAMED_RECORD_IX]),
+      Buffer.from(new BN(record.length).toArray("le", 4)),
+      record,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
@@ -0,0 +1,67 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const SET_FREEZE_AUTHORITY_IX = 60;
+const FREEZE_DATA_ACCOUNT_IX = 61;
+const THAW_DATA_ACCOUNT_IX = 62;
+
+// sets (or clears, if freezeAuthority is undefined) the key that can pause writes to the data account
+// fails while the data account is frozen
+export const setFreezeAuthorityInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  freezeAuthority?: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_FREEZE_AUTHORITY_IX]),
+      freezeAuthority ? Buffer.concat([Buffer.from([1]), freezeAuthority.toBuffer()]) : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+const freezeAuthorityInstruction = (
+  ix: number,
+  programId: PublicKey,
+  freezeAuthority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: freezeAuthority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([ix, debug ? 1 : 0]),
+  });
+};
+
+// pauses every write to the content and closing the data account until it is thawed
+export const freezeDataAccountInstruction = (
+  programId: PublicKey,
+  freezeAuthority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return freezeAuthorityInstruction(FREEZE_DATA_ACCOUNT_IX, programId, freezeAuthority, dataKey, debug);
+};
+
+export const thawDataAccountInstruction = (
+  programId: PublicKey,
+  freezeAuthority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return freezeAuthorityInstruction(THAW_DATA_ACCOUNT_IX, programId, freezeAuthority, dataKey, debug);
+};
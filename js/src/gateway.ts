@@ -0,0 +1,153 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { createServer, IncomingMessage, Server, ServerResponse } from "http";
+import { DataTypeOption } from "./common/types";
+import { DataAccountSink } from "./worker";
+
+// what an authenticated frontend user may save through the gateway
+export interface IGatewayPolicy {
+  // data accounts the user may write to
+  dataKeys: string[];
+  // maximum size of a saved content in bytes
+  maxBytes: number;
+  dataTypes: DataTypeOption[];
+  // at most maxRequests saves every windowMs
+  maxRequests: number;
+  windowMs: number;
+}
+
+// body of a save request, the content replaces the entire content of the data account
+export interface IGatewayRequest {
+  dataKey: string;
+  dataType: DataTypeOption;
+  // base64 encoded content
+  content: string;
+}
+
+export interface IGatewayConfig {
+  connection: Connection;
+  programId: PublicKey;
+  // signs and pays for the updates, it must be a listed writer of every data account (see addWriterInstruction)
+  delegate: Keypair;
+  // resolves the Authorization header of a request to a user id, or undefined if it is not authenticated
+  authenticate: (authorization: string | undefined) => Promise<string | undefined>;
+  // policy of the user, or undefined if the user may not save anything
+  policyFor: (user: string) => IGatewayPolicy | undefined;
+  // Access-Control-Allow-Origin of the responses so browsers can call the gateway
+  allowOrigin?: string;
+  debug?: boolean;
+}
+
+export class GatewayError extends Error {
+  constructor(readonly status: number, message: string) {
+    super(message);
+    // keeps instanceof working when compiled to es5
+    Object.setPrototypeOf(this, GatewayError.prototype);
+  }
+}
+
+// accepts content from authenticated frontend users, checks it against their policy and saves it to chain
+// with the delegate key, so web apps can offer "save to chain" without exposing keys in the browser
+// e.g. new Gateway({ connection, programId, delegate, authenticate, policyFor }).listen(8080)
+export class Gateway {
+  // recent save timestamps of every user for rate limiting
+  private readonly saves = new Map<string, number[]>();
+
+  constructor(private readonly config: IGatewayConfig) {}
+
+  // ensures the request is allowed by the policy of the user and returns the decoded content
+  validate(user: string, request: IGatewayRequest, now = Date.now()): Buffer {
+    const policy = this.config.policyFor(user);
+    if (!policy) {
+      throw new GatewayError(403, `${user} may not save to chain`);
+    }
+    if (policy.dataKeys.indexOf(request.dataKey) === -1) {
+      throw new GatewayError(403, `${user} may not write to ${request.dataKey}`);
+    }
+    if (policy.dataTypes.indexOf(request.dataType) === -1) {
+      throw new GatewayError(403, `${user} may not save data_type ${request.dataType}`);
+    }
+    if (typeof request.content !== "string") {
+      throw new GatewayError(400, "missing content");
+    }
+    const content = Buffer.from(request.content, "base64");
+    if (content.length > policy.maxBytes) {
+      throw new GatewayError(413, `content of ${content.length} bytes exceeds ${policy.maxBytes} bytes`);
+    }
+    const recent = (this.saves.get(user) ?? []).filter((time) => now - time < policy.windowMs);
+    if (recent.length >= policy.maxRequests) {
+      this.saves.set(user, recent);
+      throw new GatewayError(429, `${user} exceeded ${policy.maxRequests} saves per ${policy.windowMs}ms`);
+    }
+    recent.push(now);
+    this.saves.set(user, recent);
+    return content;
+  }
+
+  // authenticates, validates and saves the content of a request
+  async save(authorization: string | undefined, request: IGatewayRequest): Promise<void> {
+    const user = await this.config.authenticate(authorization);
+    if (!user) {
+      throw new GatewayError(401, "not authenticated");
+    }
+    const content = this.validate(user, request);
+    const sink = new DataAccountSink(
+      this.config.connection,
+      this.config.programId,
+      this.config.delegate,
+      new PublicKey(request.dataKey),
+      request.dataType,
+      this.config.debug
+    );
+    await sink.write(content);
+    if (this.config.debug) {
+      console.log(`saved ${content.length} bytes to ${request.dataKey} for ${user}`);
+    }
+  }
+
+  // serves POST requests with a JSON IGatewayRequest body
+  listen(port: number): Server {
+    const server = createServer((req, res) => {
+      this.handle(req, res).catch((err) => {
+        const status = err instanceof GatewayError ? err.status : 500;
+        this.respond(res, status, { error: err instanceof GatewayError ? err.message : "internal error" });
+        if (status === 500) {
+          console.error(err);
+        }
+      });
+    });
+    return server.listen(port);
+  }
+
+  private async handle(req: IncomingMessage, res: ServerResponse): Promise<void> {
+    if (req.method === "OPTIONS") {
+      this.respond(res, 204);
+      return;
+    }
+    if (req.method !== "POST") {
+      throw new GatewayError(405, "expected a POST request");
+    }
+    const body = await new Promise<Buffer>((resolve, reject) => {
+      const chunks: Buffer[] = [];
+      req.on("data", (chunk: Buffer) => chunks.push(chunk));
+      req.on("end", () => resolve(Buffer.concat(chunks)));
+      req.on("error", reject);
+    });
+    let request: IGatewayRequest;
+    try {
+      request = JSON.parse(body.toString("utf8"));
+    } catch (err) {
+      throw new GatewayError(400, "body should be JSON");
+    }
+    await this.save(req.headers.authorization, request);
+    this.respond(res, 200, { saved: request.dataKey });
+  }
+
+  private respond(res: ServerResponse, status: number, body?: unknown): void {
+    res.writeHead(status, {
+      "Content-Type": "application/json",
+      "Access-Control-Allow-Origin": this.config.allowOrigin ?? "*",
+      "Access-Control-Allow-Headers": "Authorization, Content-Type",
+    });
+    res.end(body === undefined ? undefined : JSON.stringify(body));
+  }
+}
@@ -0,0 +1,96 @@
+import { Commitment, Connection, PublicKey } from "@solana/web3.js";
+import { inflateRawSync } from "zlib";
+import { CompressionOption, IDataAccount } from "./common/types";
+import { crc32, getMetadataKey, plaintextHash } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+// getMultipleAccounts accepts at most 100 accounts, i.e., 50 data accounts along with their metadata accounts
+const MAX_DATA_ACCOUNTS_PER_REQUEST = 50;
+
+export interface IVerifiedDataAccount extends IDataAccount {
+  // undefined if the metadata holds no content_hash (or checksum) to verify against,
+  // or the content is zstd compressed and can't be decompressed without its dictionary
+  contentHashValid?: boolean;
+  checksumValid?: boolean;
+}
+
+// either the decoded account or why it could not be read or verified (a failed verification keeps the account)
+export interface IGetManyResult {
+  account?: IVerifiedDataAccount;
+  error?: string;
+}
+
+// verifies the content against the content_hash of its plaintext and the checksum of the stored bytes
+const verify = (account: IDataAccount): IGetManyResult => {
+  const { meta, data } = account;
+  const content = data ?? Buffer.alloc(0);
+  const verified: IVerifiedDataAccount = { ...account };
+  const errors: string[] = [];
+
+  if (meta.checksum !== undefined) {
+    verified.checksumValid = crc32(content) === meta.checksum;
+    if (!verified.checksumValid) {
+      errors.push("checksum mismatch");
+    }
+  }
+  if (meta.content_hash && meta.compression !== CompressionOption.ZSTD) {
+    try {
+      const plaintext = meta.compression === CompressionOption.DEFLATE ? inflateRawSync(content) : content;
+      verified.contentHashValid = plaintextHash(plaintext).equals(meta.content_hash);
+      if (!verified.contentHashValid) {
+        errors.push("content_hash mismatch");
+      }
+    } catch (e) {
+      errors.push(`content could not be inflated: ${e}`);
+    }
+  }
+
+  return errors.length > 0 ? { account: verified, error: errors.join(", ") } : { account: verified };
+};
+
+// reads and verifies many data accounts with as few requests as possible, e.g. for dashboards listing hundreds of them
+// a missing, undecodable or tampered account (or a failed request) only fails its own entries, keyed by the base58 data key
+export const getMany = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKeys: PublicKey[],
+  commitment: Commitment = "confirmed"
+): Promise<Map<string, IGetManyResult>> => {
+  const results = new Map<string, IGetManyResult>();
+
+  for (let start = 0; start < dataKeys.length; start += MAX_DATA_ACCOUNTS_PER_REQUEST) {
+    const chunk = dataKeys.slice(start, start + MAX_DATA_ACCOUNTS_PER_REQUEST);
+    const keys = chunk.reduce<PublicKey[]>(
+      (keys, dataKey) => keys.concat([dataKey, getMetadataKey(programId, dataKey)]),
+      []
+    );
+
+    let accounts;
+    try {
+      accounts = await connection.getMultipleAccountsInfo(keys, commitment);
+    } catch (e) {
+      chunk.forEach((dataKey) => results.set(dataKey.toBase58(), { error: `request failed: ${e}` }));
+      continue;
+    }
+
+    chunk.forEach((dataKey, i) => {
+      const dataAccount = accounts[2 * i];
+      const metaAccount = accounts[2 * i + 1];
+      if (!dataAccount || !metaAccount || !dataAccount.owner.equals(programId) || !metaAccount.owner.equals(programId)) {
+        results.set(dataKey.toBase58(), { error: "not a data account" });
+        return;
+      }
+
+      try {
+        const meta = parseMetadata(metaAccount.data);
+        // only the first content_len bytes of the data account are meaningful
+        const data = dataAccount.data.subarray(0, meta.content_len);
+        results.set(dataKey.toBase58(), verify({ meta, data }));
+      } catch (e) {
+        results.set(dataKey.toBase58(), { error: `metadata could not be decoded: ${e}` });
+      }
+    });
+  }
+
+  return results;
+};
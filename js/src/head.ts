@@ -0,0 +1,70 @@
+import { Commitment, Connection, PublicKey } from "@solana/web3.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+export interface IDataAccountHead {
+  meta: IDataAccountMeta;
+  // at most the first n bytes of the content
+  head: Buffer;
+  // whether the content continues beyond the head
+  truncated: boolean;
+  slot: number;
+}
+
+interface IRpcAccountInfo {
+  context: { slot: number };
+  value: { data: [string, "base64"] } | null;
+}
+
+// fetches the metadata and only the first n bytes of the data account in a single batched RPC request,
+// e.g. to render previews or lists without downloading every content in full
+// getMultipleAccountsInfo can't be used since its dataSlice would apply to the metadata account as well
+export const getHead = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKey: PublicKey,
+  n: number,
+  commitment: Commitment = "confirmed"
+): Promise<IDataAccountHead | undefined> => {
+  const request = (id: string, key: PublicKey, dataSlice?: { offset: number; length: number }) => ({
+    jsonrpc: "2.0",
+    id,
+    method: "getAccountInfo",
+    params: [key.toBase58(), { encoding: "base64", commitment, dataSlice }],
+  });
+  const res = await fetch(connection.rpcEndpoint, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify([
+      request("meta", getMetadataKey(programId, dataKey)),
+      request("head", dataKey, { offset: 0, length: n }),
+    ]),
+  });
+  if (!res.ok) {
+    throw new Error(`getHead failed: ${res.status}`);
+  }
+
+  // batched responses may come back in any order
+  const responses: { id: string; result?: IRpcAccountInfo; error?: { message: string } }[] = await res.json();
+  const result = (id: string): IRpcAccountInfo => {
+    const response = responses.find((r) => r.id === id);
+    if (!response || response.error || !response.result) {
+      throw new Error(`getHead failed: ${response?.error?.message ?? `no ${id} response`}`);
+    }
+    return response.result;
+  };
+  const meta_account = result("meta");
+  const data_account = result("head");
+  if (!meta_account.value || !data_account.value) return undefined;
+
+  const meta = parseMetadata(Buffer.from(meta_account.value.data[0], "base64"));
+  // only the first content_len bytes of the data account are meaningful
+  const head = Buffer.from(data_account.value.data[0], "base64").subarray(0, Math.min(n, meta.content_len));
+  return {
+    meta,
+    head,
+    truncated: meta.content_len > head.length,
+    slot: Math.min(meta_account.context.slot, data_account.context.slot),
+  };
+};
@@ -0,0 +1,55 @@
+import { Connection, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDataAccountMeta, ILicense } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+const SET_LICENSE_IX = 28;
+
+export const setLicenseInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  license: ILicense | null,
+  debug?: boolean
+): TransactionInstruction => {
+  const value = license ? Buffer.from(license.value, "utf8") : Buffer.alloc(0);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_LICENSE_IX]),
+      license
+        ? Buffer.concat([Buffer.from([1, license.kind]), Buffer.from(new BN(value.length).toArray("le", 4)), value])
+        : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// scans the metadata accounts of the program and returns the ones whose license passes the filter
+export const getMetadataByLicense = async (
+  connection: Connection,
+  programId: PublicKey,
+  filter: (license: ILicense) => boolean
+): Promise<{ metaKey: PublicKey; meta: IDataAccountMeta }[]> => {
+  const accounts = await connection.getProgramAccounts(programId, { commitment: "confirmed" });
+  const matches: { metaKey: PublicKey; meta: IDataAccountMeta }[] = [];
+  for (const { pubkey, account } of accounts) {
+    let meta: IDataAccountMeta;
+    try {
+      meta = parseMetadata(account.data);
+    } catch {
+      // not a metadata account
+      continue;
+    }
+    if (meta.license && filter(meta.license)) {
+      matches.push({ metaKey: pubkey, meta });
+    }
+  }
+  return matches;
+};
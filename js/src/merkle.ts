@@ -0,0 +1,61 @@
+import { createHash } from "crypto";
+
+const hash = (...parts: Buffer[]): Buffer => {
+  const hasher = createHash("sha256");
+  parts.forEach((part) => hasher.update(part));
+  return hasher.digest();
+};
+
+export const leafHash = (entry: Buffer): Buffer => {
+  return hash(Buffer.from([0]), entry);
+};
+
+export const nodeHash = (a: Buffer, b: Buffer): Buffer => {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return hash(Buffer.from([1]), left, right);
+};
+
+export interface IAllowlistTree {
+  root: Buffer;
+  layers: Buffer[][];
+}
+
+// builds the allowlist merkle tree matching the on-chain sorted pair hashing
+export const buildAllowlistTree = (entries: Buffer[]): IAllowlistTree => {
+  if (entries.length === 0) {
+    throw new Error("Allowlist should not be empty");
+  }
+  const layers: Buffer[][] = [entries.map(leafHash)];
+  while (layers[layers.length - 1].length > 1) {
+    const prev = layers[layers.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < prev.length; i += 2) {
+      // odd nodes are promoted to the next layer as is
+      next.push(i + 1 < prev.length ? nodeHash(prev[i], prev[i + 1]) : prev[i]);
+    }
+    layers.push(next);
+  }
+  return {
+    root: layers[layers.length - 1][0],
+    layers,
+  };
+};
+
+// returns the sibling hashes needed to verify the entry at index against the root
+export const getAllowlistProof = (tree: IAllowlistTree, index: number): Buffer[] => {
+  const proof: Buffer[] = [];
+  for (let layer = 0; layer < tree.layers.length - 1; ++layer) {
+    const nodes = tree.layers[layer];
+    const sibling = index % 2 === 0 ? index + 1 : index - 1;
+    if (sibling < nodes.length) {
+      proof.push(nodes[sibling]);
+    }
+    index = Math.floor(index / 2);
+  }
+  return proof;
+};
+
+export const verifyAllowlistProof = (root: Buffer, entry: Buffer, proof: Buffer[]): boolean => {
+  const computed = proof.reduce((node, sibling) => nodeHash(node, sibling), leafHash(entry));
+  return computed.equals(root);
+};
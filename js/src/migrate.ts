@@ -0,0 +1,35 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const MIGRATE_METADATA_IX = 58;
+
+// mirrors DATA_VERSION and DATA_VERSION_OFFSET in interface/src/state.rs
+export const DATA_VERSION = 1;
+const DATA_VERSION_OFFSET = 35;
+
+// whether the metadata account was written by an older release and has to be migrated before it is used
+export const needsMigration = (metadataAccountData: Buffer): boolean => {
+  return (
+    metadataAccountData.length > DATA_VERSION_OFFSET &&
+    metadataAccountData[DATA_VERSION_OFFSET] < DATA_VERSION
+  );
+};
+
+// rewrites the metadata into the current layout, the authority funds the growth of the metadata account
+export const migrateMetadataInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.from([MIGRATE_METADATA_IX, debug ? 1 : 0]),
+  });
+};
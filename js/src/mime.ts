@@ -0,0 +1,49 @@
+import { DataTypeOption } from "./common/types";
+
+// mirrors DataTypeOption::mime_type in interface/src/state.rs
+const MIME_TYPES: Record<number, string> = {
+  [DataTypeOption.JSON]: "application/json",
+  [DataTypeOption.DIRECTORY]: "application/json",
+  [DataTypeOption.HTML]: "text/html",
+  [DataTypeOption.PNG]: "image/png",
+  [DataTypeOption.SVG]: "image/svg+xml",
+  [DataTypeOption.TEXT]: "text/plain; charset=utf-8",
+};
+
+// IMG content doesn't record its format so it is sniffed from the magic bytes
+export const imageMime = (content: Buffer): string => {
+  if (content.subarray(0, 8).equals(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]))) return "image/png";
+  if (content.subarray(0, 3).equals(Buffer.from([0xff, 0xd8, 0xff]))) return "image/jpeg";
+  if (content.subarray(0, 4).toString("ascii") === "GIF8") return "image/gif";
+  if (content.subarray(8, 12).toString("ascii") === "WEBP") return "image/webp";
+  if (/^\s*(<\?xml|<svg)/.test(content.subarray(0, 256).toString("utf8"))) return "image/svg+xml";
+  return "application/octet-stream";
+};
+
+// Content-Type to serve the content of a data account with, e.g. from an HTTP gateway
+// pass the content to sniff the format of IMG content
+export const dataTypeMime = (dataType: DataTypeOption, content?: Buffer): string => {
+  if (dataType === DataTypeOption.IMG && content) {
+    return imageMime(content);
+  }
+  return MIME_TYPES[dataType] ?? "application/octet-stream";
+};
+
+// data_type to store content of the given Content-Type with
+export const dataTypeForMime = (mime: string): DataTypeOption => {
+  const essence = mime.split(";")[0].trim().toLowerCase();
+  switch (essence) {
+    case "application/json":
+      return DataTypeOption.JSON;
+    case "text/html":
+      return DataTypeOption.HTML;
+    case "image/png":
+      return DataTypeOption.PNG;
+    case "image/svg+xml":
+      return DataTypeOption.SVG;
+    case "text/plain":
+      return DataTypeOption.TEXT;
+    default:
+      return essence.startsWith("image/") ? DataTypeOption.IMG : DataTypeOption.CUSTOM;
+  }
+};
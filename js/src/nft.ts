@@ -0,0 +1,105 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { GrowthPolicyOption, IGrowthPolicy } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+import { donationRefundKeys, IDonationsTable } from "./donations";
+
+export const NFT_SEED = "data_account_nft";
+
+const INITIALIZE_NFT_DATA_ACCOUNT_IX = 45;
+const UPDATE_NFT_DATA_ACCOUNT_IX = 46;
+const CLOSE_NFT_DATA_ACCOUNT_IX = 71;
+
+// the data account bound to an NFT is the pda derived from its mint
+export const getNftDataKey = (programId: PublicKey, mint: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync([Buffer.from(NFT_SEED, "ascii"), mint.toBuffer()], programId)[0];
+};
+
+// tokenAccount is the token account of the holder holding the NFT (e.g., its associated token account)
+export const initializeNftInstruction = (
+  programId: PublicKey,
+  holder: PublicKey,
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  space: number,
+  isDynamic: boolean,
+  growthPolicy: IGrowthPolicy = { kind: GrowthPolicyOption.EXACT },
+  debug?: boolean
+): TransactionInstruction => {
+  const dataKey = getNftDataKey(programId, mint);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: holder, isSigner: true, isWritable: true },
+      { pubkey: tokenAccount, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([INITIALIZE_NFT_DATA_ACCOUNT_IX]),
+      Buffer.from(new BN(space).toArray("le", 8)),
+      Buffer.from([isDynamic ? 1 : 0, growthPolicy.kind]),
+      growthPolicy.kind === GrowthPolicyOption.EXACT
+        ? Buffer.alloc(0)
+        : Buffer.from(new BN(growthPolicy.value ?? 0).toArray("le", 4)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const updateNftInstruction = (
+  programId: PublicKey,
+  holder: PublicKey,
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  offset: number,
+  data: Buffer,
+  truncate?: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  const dataKey = getNftDataKey(programId, mint);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: holder, isSigner: true, isWritable: true },
+      { pubkey: tokenAccount, isSigner: false, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_NFT_DATA_ACCOUNT_IX]),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from([truncate ? 1 : 0, debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// the data account is its own authority, so the holder of the NFT closes it in place of CloseDataAccount
+export const closeNftInstruction = (
+  programId: PublicKey,
+  holder: PublicKey,
+  tokenAccount: PublicKey,
+  mint: PublicKey,
+  rentReceiver: PublicKey,
+  debug?: boolean,
+  donations: IDonationsTable = { total: 0, top: [] }
+): TransactionInstruction => {
+  const dataKey = getNftDataKey(programId, mint);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: holder, isSigner: true, isWritable: false },
+      { pubkey: tokenAccount, isSigner: false, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: rentReceiver, isSigner: false, isWritable: true },
+      ...donationRefundKeys(programId, dataKey, donations),
+    ],
+    programId,
+    data: Buffer.from([CLOSE_NFT_DATA_ACCOUNT_IX, debug ? 1 : 0]),
+  });
+};
@@ -0,0 +1,38 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+
+const SET_NOTIFICATION_HINT_IX = 53;
+
+// the hint of a webhook URL or a topic id is its sha256 hash so the URL itself is not published on-chain
+export const notificationHint = (urlOrTopic: string): Buffer => {
+  return plaintextHash(Buffer.from(urlOrTopic, "utf8"));
+};
+
+// passing no hint clears it
+export const setNotificationHintInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  hint?: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SET_NOTIFICATION_HINT_IX]),
+      hint ? Buffer.concat([Buffer.from([1]), hint]) : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// routes a change notification of a data account to the registered webhooks (keyed by their hint in hex)
+export const routeNotification = <T>(meta: IDataAccountMeta, webhooks: Map<string, T>): T | undefined => {
+  return meta.notification_hint ? webhooks.get(meta.notification_hint.toString("hex")) : undefined;
+};
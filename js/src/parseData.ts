@@ -1,6 +1,168 @@
 import { Connection, PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
-import { IDataAccount, IDataAccountMeta } from "./common/types";
+import { GrowthPolicyOption, IDataAccount, IDataAccountMeta } from "./common/types";
+
+// reads borsh encoded fields of the metadata sequentially
+export class MetadataReader {
+  private offset = 0;
+
+  constructor(private readonly buffer: Buffer) {}
+
+  u8(): number {
+    return this.buffer.readUInt8(this.offset++);
+  }
+
+  bool(): boolean {
+    return this.u8() ? true : false;
+  }
+
+  u16(): number {
+    const value = this.buffer.readUInt16LE(this.offset);
+    this.offset += 2;
+    return value;
+  }
+
+  u32(): number {
+    const value = this.buffer.readUInt32LE(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  u64(): number {
+    const value = new BN(this.buffer.subarray(this.offset, this.offset + 8), "le").toNumber();
+    this.offset += 8;
+    return value;
+  }
+
+  bytes(len: number): Buffer {
+    const value = this.buffer.subarray(this.offset, this.offset + len);
+    this.offset += len;
+    return value;
+  }
+
+  string(): string {
+    return this.bytes(this.u32()).toString("utf8");
+  }
+
+  pubkey(): string {
+    return new PublicKey(this.bytes(32)).toBase58();
+  }
+
+  option<T>(read: () => T): T | undefined {
+    return this.bool() ? read() : undefined;
+  }
+}
+
+export const parseMetadata = (data: Buffer): IDataAccountMeta => {
+  const reader = new MetadataReader(data);
+  const account_meta = {} as IDataAccountMeta;
+  account_meta.data_status = reader.u8();
+  account_meta.serialization_status = reader.u8();
+  account_meta.authority = reader.pubkey();
+  account_meta.is_dynamic = reader.bool();
+  account_meta.data_version = reader.u8();
+  account_meta.data_type = reader.u8();
+  account_meta.bump_seed = reader.u8();
+  account_meta.user = reader.option(() => reader.pubkey());
+  account_meta.require_user_consent = reader.bool();
+  account_meta.compression = reader.u8();
+  account_meta.content_hash = reader.option(() => reader.bytes(32));
+  account_meta.content_len = reader.u64();
+  const growth_policy = reader.u8();
+  account_meta.growth_policy = {
+    kind: growth_policy,
+    value: growth_policy === GrowthPolicyOption.EXACT ? undefined : reader.u32(),
+  };
+  account_meta.hot_region = reader.option(() => ({ offset: reader.u8(), len: reader.u8() }));
+  account_meta.archive = reader.option(() => ({
+    hash: reader.bytes(32),
+    size: reader.u64(),
+    archived_at: reader.u64(),
+    pointer: reader.option(() => reader.string()),
+  }));
+  account_meta.governance = reader.option(() => ({
+    governance_program: reader.pubkey(),
+    governance: reader.pubkey(),
+  }));
+  account_meta.automation = reader.option(() => ({
+    delegate: reader.pubkey(),
+    min_interval_slots: reader.u64(),
+    last_heartbeat_slot: reader.u64(),
+  }));
+  account_meta.dead_man_switch = reader.option(() => ({
+    successor: reader.pubkey(),
+    inactivity_timeout_slots: reader.u64(),
+    last_active_slot: reader.u64(),
+  }));
+  account_meta.drop_box = reader.option(() => ({
+    start: reader.u64(),
+    fee_per_byte: reader.u64(),
+    max_message_len: reader.u16(),
+    min_interval_slots: reader.u64(),
+  }));
+  account_meta.time_lock = reader.option(() => ({
+    reveal_after_slot: reader.u64(),
+    plaintext_hash: reader.option(() => reader.bytes(32)),
+  }));
+  account_meta.license = reader.option(() => ({ kind: reader.u8(), value: reader.string() }));
+  account_meta.close_lock = reader.option(() => ({
+    cooldown_slots: reader.u64(),
+    unlocked_at: reader.option(() => reader.u64()),
+  }));
+  account_meta.content_keccak = reader.option(() => reader.bytes(32));
+  account_meta.record_count = reader.u64();
+  account_meta.upload = reader.option(() => ({
+    total_len: reader.u64(),
+    checksum: reader.bytes(32),
+    written: reader.u64(),
+  }));
+  account_meta.append_only = reader.bool();
+  account_meta.writer_sequences = Array.from({ length: reader.u32() }, () => ({
+    writer: reader.pubkey(),
+    sequence: reader.u64(),
+  }));
+  account_meta.nft_mint = reader.option(() => reader.pubkey());
+  account_meta.writers = Array.from({ length: reader.u32() }, () => reader.pubkey());
+  account_meta.expiry = reader.option(() => ({ expires_at: reader.u64(), rent_receiver: reader.pubkey() }));
+  account_meta.notification_hint = reader.option(() => reader.bytes(32));
+  account_meta.checksum = reader.option(() => reader.u32());
+  account_meta.attestation = reader.option(() => ({
+    hash: reader.bytes(32),
+    slot: reader.u64(),
+    interval_slots: reader.u64(),
+  }));
+  account_meta.realloc_count = reader.u64();
+  account_meta.rent_paid = reader.u64();
+  account_meta.freeze_authority = reader.option(() => reader.pubkey());
+  account_meta.frozen = reader.bool();
+  account_meta.compression_dictionary = reader.option(() => reader.pubkey());
+  account_meta.verification_cursor = reader.option(() => ({
+    offset: reader.u64(),
+    state: Array.from({ length: 8 }, () => reader.u32()),
+  }));
+  account_meta.generation = reader.u64();
+  account_meta.revision = reader.u64();
+  account_meta.write_delegate = reader.option(() => ({
+    delegate: reader.pubkey(),
+    expiry_slot: reader.u64(),
+  }));
+  account_meta.donated_lamports = reader.u64();
+  return account_meta;
+}
+
+// the hot region always lies within this many leading bytes of the data account
+export const HOT_REGION_MAX_END = 128;
+
+// fetches only the first page of the data account which contains the hot region
+export const parseHotRegion = async (connection: Connection, dataKey: PublicKey, meta: IDataAccountMeta): Promise<Buffer | undefined> => {
+  if (!meta.hot_region) return undefined;
+  const data_account = await connection.getAccountInfo(dataKey, {
+    commitment: "confirmed",
+    dataSlice: { offset: 0, length: HOT_REGION_MAX_END },
+  });
+  const { offset, len } = meta.hot_region;
+  return data_account?.data.subarray(offset, offset + len);
+}
   
 export const parseData = async (connection: Connection, dataKey: PublicKey, metaKey: PublicKey, debug?: boolean): Promise<IDataAccount> => {
   const data_account = await connection.getAccountInfo(dataKey, "confirmed");
@@ -13,31 +175,14 @@ export const parseData = async (connection: Connection, dataKey: PublicKey, meta
     console.log(data_account?.data);
   }
   
-  const account_meta = {} as IDataAccountMeta;
+  let account_meta = {} as IDataAccountMeta;
   if (meta_account && meta_account.data.length > 0) {
-    const data_account_metadata = meta_account.data;
-    account_meta.data_status = data_account_metadata.subarray(0, 1).readUInt8()
-    account_meta.serialization_status = data_account_metadata.subarray(1, 2).readUInt8()
-    account_meta.authority = new PublicKey(
-      data_account_metadata.subarray(2, 34)
-    ).toBase58();
-    account_meta.is_dynamic = data_account_metadata.subarray(34, 35).readUInt8() ? true : false;
-    account_meta.data_version = new BN(
-      data_account_metadata.subarray(35, 36),
-      "le"
-    ).toNumber();  
-    account_meta.data_type = new BN(
-      data_account_metadata.subarray(36, 37),
-      "le"
-    ).toNumber();
-    account_meta.bump_seed = new BN(
-      data_account_metadata.subarray(37, 38),
-      "le"
-    ).toNumber();
+    account_meta = parseMetadata(meta_account.data);
   }
 
   return {
     meta: account_meta,
-    data: data_account?.data
+    // only the first content_len bytes of the data account are meaningful
+    data: data_account?.data.subarray(0, account_meta.content_len),
   };
-}
\ No newline at end of file
+}
@@ -0,0 +1,53 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { GrowthPolicyOption, IGrowthPolicy } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+export const DATA_PDA_SEED = "data_account_pda";
+
+const INITIALIZE_DATA_ACCOUNT_PDA_IX = 56;
+
+// the data account pda of the authority and seed, recoverable from them alone
+export const getPdaDataKey = (programId: PublicKey, authority: PublicKey, seed: string): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DATA_PDA_SEED, "ascii"), authority.toBuffer(), Buffer.from(seed, "utf8")],
+    programId
+  )[0];
+};
+
+// the seed is at most 32 bytes and the authority signs along with the feePayer (which can be the authority)
+export const initializePdaInstruction = (
+  programId: PublicKey,
+  feePayer: PublicKey,
+  authority: PublicKey,
+  seed: string,
+  space: number,
+  isDynamic: boolean,
+  growthPolicy: IGrowthPolicy = { kind: GrowthPolicyOption.EXACT },
+  appendOnly?: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  const dataKey = getPdaDataKey(programId, authority, seed);
+  const seedBytes = Buffer.from(seed, "utf8");
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: feePayer, isSigner: true, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([INITIALIZE_DATA_ACCOUNT_PDA_IX]),
+      Buffer.from(new BN(seedBytes.length).toArray("le", 4)),
+      seedBytes,
+      Buffer.from(new BN(space).toArray("le", 8)),
+      Buffer.from([isDynamic ? 1 : 0, growthPolicy.kind]),
+      growthPolicy.kind === GrowthPolicyOption.EXACT
+        ? Buffer.alloc(0)
+        : Buffer.from(new BN(growthPolicy.value ?? 0).toArray("le", 4)),
+      Buffer.from([appendOnly ? 1 : 0, debug ? 1 : 0]),
+    ]),
+  });
+};
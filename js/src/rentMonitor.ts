@@ -0,0 +1,134 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const TOP_UP_RENT_IX = 66;
+
+// anyone can top up a data account to its rent-exempt minimum, computed on-chain when the instruction executes
+export const topUpRentInstruction = (
+  programId: PublicKey,
+  payer: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.from([TOP_UP_RENT_IX, debug ? 1 : 0]),
+  });
+};
+
+export type RentAlertKind = "threshold-changed" | "below-rent-exempt" | "missing";
+
+export interface IRentAlert {
+  kind: RentAlertKind;
+  epoch: number;
+  account: PublicKey;
+  // rent-exempt minimum for the current length of the account
+  required: number;
+  balance: number;
+  // rent-exempt minimum for the same length in the previous scanned epoch
+  previousRequired?: number;
+  // signature of the top-up transaction if one was sent
+  topUp?: string;
+}
+
+export interface IRentMonitorOptions {
+  onAlert: (alert: IRentAlert) => void;
+  // pays for the top-ups of accounts below the rent-exempt minimum when set
+  payer?: Keypair;
+  // extra lamports added on top of the deficit so small threshold changes don't retrigger alerts
+  headroom?: number;
+  pollIntervalMs?: number;
+}
+
+// scans the data and metadata accounts of an authority once per epoch for rent-exemption changes and balance drift
+export class RentMonitor {
+  private lastEpoch = -1;
+  private requiredBySize = new Map<number, number>();
+  private timer?: NodeJS.Timeout;
+
+  constructor(
+    private readonly connection: Connection,
+    private readonly programId: PublicKey,
+    private readonly dataKeys: PublicKey[],
+    private readonly options: IRentMonitorOptions
+  ) {}
+
+  start() {
+    const poll = async () => {
+      try {
+        const { epoch } = await this.connection.getEpochInfo("confirmed");
+        if (epoch !== this.lastEpoch) {
+          await this.scan(epoch);
+          this.lastEpoch = epoch;
+        }
+      } catch (e) {
+        console.error(e);
+      }
+      this.timer = setTimeout(poll, this.options.pollIntervalMs ?? 60_000);
+    };
+    poll();
+  }
+
+  stop() {
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+  }
+
+  async scan(epoch: number) {
+    const keys = this.dataKeys.flatMap((dataKey) => [dataKey, getMetadataKey(this.programId, dataKey)]);
+    const accounts = await this.connection.getMultipleAccountsInfo(keys, "confirmed");
+    const requiredBySize = new Map<number, number>();
+
+    for (const [i, account] of accounts.entries()) {
+      if (!account) {
+        this.options.onAlert({ kind: "missing", epoch, account: keys[i], required: 0, balance: 0 });
+        continue;
+      }
+      const size = account.data.length;
+      if (!requiredBySize.has(size)) {
+        requiredBySize.set(size, await this.connection.getMinimumBalanceForRentExemption(size, "confirmed"));
+      }
+      const required = requiredBySize.get(size) as number;
+      const previousRequired = this.requiredBySize.get(size);
+
+      if (previousRequired !== undefined && previousRequired !== required) {
+        this.options.onAlert({ kind: "threshold-changed", epoch, account: keys[i], required, balance: account.lamports, previousRequired });
+      }
+      if (account.lamports < required) {
+        const alert: IRentAlert = { kind: "below-rent-exempt", epoch, account: keys[i], required, balance: account.lamports };
+        if (this.options.payer) {
+          // keys alternate between data and metadata accounts
+          alert.topUp = await this.topUp(keys[i], i % 2 === 0, required - account.lamports + (this.options.headroom ?? 0));
+        }
+        this.options.onAlert(alert);
+      }
+    }
+    this.requiredBySize = requiredBySize;
+  }
+
+  // data accounts are topped up with TopUpRent unless headroom is requested,
+  // metadata accounts (and headroom) are credited via a plain transfer
+  private async topUp(account: PublicKey, isDataAccount: boolean, lamports: number): Promise<string> {
+    const payer = this.options.payer as Keypair;
+    const ix =
+      isDataAccount && !this.options.headroom
+        ? topUpRentInstruction(this.programId, payer.publicKey, account)
+        : SystemProgram.transfer({ fromPubkey: payer.publicKey, toPubkey: account, lamports });
+    return sendAndConfirmTransaction(this.connection, new Transaction().add(ix), [payer], { commitment: "confirmed" });
+  }
+}
@@ -0,0 +1,48 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const RESIZE_DATA_ACCOUNT_IX = 54;
+// mirrors MAX_PERMITTED_DATA_INCREASE, the most a data account can grow by per instruction
+export const MAX_RESIZE_INCREASE = 10 * 1024;
+
+// the authority funds the growth and is refunded the excess lamports on shrink
+export const resizeInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  space: number,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([RESIZE_DATA_ACCOUNT_IX]),
+      Buffer.from(new BN(space).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// pre-allocates space bytes for a bulk upload in as many steps as the realloc limit requires
+export const preallocateInstructions = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  currentLen: number,
+  space: number,
+  debug?: boolean
+): TransactionInstruction[] => {
+  const ixs: TransactionInstruction[] = [];
+  for (let len = currentLen; len < space; ) {
+    len = Math.min(len + MAX_RESIZE_INCREASE, space);
+    ixs.push(resizeInstruction(programId, authority, dataKey, len, debug));
+  }
+  return ixs;
+};
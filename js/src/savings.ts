@@ -0,0 +1,142 @@
+import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { CompressionOption, DataStatusOption, IDataAccountMeta } from "./common/types";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+const UPDATE_DATA_ACCOUNT_IX = 1;
+
+export type ShrinkKind = "unused-space" | "trailing-zeros";
+
+export interface IShrinkRecommendation {
+  dataKey: PublicKey;
+  kind: ShrinkKind;
+  dataLen: number;
+  contentLen: number;
+  // length of the data account after the shrink
+  newLen: number;
+  // rent refunded to the authority by the shrink
+  lamports: number;
+  instruction: TransactionInstruction;
+}
+
+export interface ISavingsReport {
+  recommendations: IShrinkRecommendation[];
+  // data accounts with reclaimable space that cannot be shrunk (e.g., static or sealed) along with the reason
+  skipped: { dataKey: PublicKey; reason: string }[];
+  totalLamports: number;
+}
+
+const countTrailingZeros = (content: Buffer): number => {
+  let end = content.length;
+  while (end > 0 && content[end - 1] === 0) end--;
+  return content.length - end;
+};
+
+// empty write at the new end of the content that releases the space beyond it
+// the data_type, compression and content_hash are passed through since the update overwrites them
+const shrinkInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  meta: IDataAccountMeta,
+  newLen: number,
+  truncate: boolean,
+  contentHash?: Buffer
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_DATA_ACCOUNT_IX, meta.data_type]),
+      Buffer.from(new BN(0).toArray("le", 4)),
+      Buffer.from(new BN(newLen).toArray("le", 8)),
+      // realloc_down, truncate, commit_flag, verify_flag
+      Buffer.from([1, truncate ? 1 : 0, 0, 0, meta.compression]),
+      contentHash ? Buffer.concat([Buffer.from([1]), contentHash]) : Buffer.from([0]),
+      // no sequence
+      Buffer.from([0]),
+      // no segments
+      Buffer.alloc(4),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([0]),
+    ]),
+  });
+};
+
+// inspects the data accounts of an authority and generates the shrinks that reclaim their rent
+export const analyzeSavings = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: PublicKey
+): Promise<ISavingsReport> => {
+  const accounts = await connection.getProgramAccounts(programId, { commitment: "confirmed" });
+  const byKey = new Map(accounts.map(({ pubkey, account }) => [pubkey.toBase58(), account]));
+  const rentCache = new Map<number, number>();
+  const rent = async (len: number): Promise<number> => {
+    if (!rentCache.has(len)) {
+      rentCache.set(len, await connection.getMinimumBalanceForRentExemption(len, "confirmed"));
+    }
+    return rentCache.get(len) as number;
+  };
+
+  const report: ISavingsReport = { recommendations: [], skipped: [], totalLamports: 0 };
+  for (const { pubkey: dataKey, account } of accounts) {
+    // data accounts are the program accounts whose metadata pda is also a program account
+    const metaAccount = byKey.get(getMetadataKey(programId, dataKey).toBase58());
+    if (!metaAccount) continue;
+    let meta: IDataAccountMeta;
+    try {
+      meta = parseMetadata(metaAccount.data);
+    } catch {
+      continue;
+    }
+    if (meta.authority !== authority.toBase58() || meta.data_status === DataStatusOption.ARCHIVED) continue;
+
+    const dataLen = account.data.length;
+    const contentLen = Math.min(meta.content_len, dataLen);
+    const content = account.data.subarray(0, contentLen);
+    // trailing zeros are only meaningless in uncompressed content
+    const trailingZeros = meta.compression === CompressionOption.NONE ? countTrailingZeros(content) : 0;
+    const hotRegionEnd = meta.hot_region ? meta.hot_region.offset + meta.hot_region.len : 0;
+    const newLen = Math.max(contentLen - trailingZeros, hotRegionEnd);
+    if (newLen >= dataLen) continue;
+
+    if (!meta.is_dynamic) {
+      report.skipped.push({ dataKey, reason: "static data accounts cannot be reallocated" });
+      continue;
+    }
+    if (meta.time_lock || meta.drop_box) {
+      report.skipped.push({ dataKey, reason: "sealed or drop box content cannot be rewritten by the authority" });
+      continue;
+    }
+    if (hotRegionEnd > contentLen) {
+      report.skipped.push({ dataKey, reason: "hot region extends past the content" });
+      continue;
+    }
+
+    const truncate = newLen < contentLen;
+    // the content_hash covers the entire content, so it is recomputed when the content is truncated
+    const contentHash = truncate && meta.content_hash ? plaintextHash(content.subarray(0, newLen)) : meta.content_hash;
+    const lamports = account.lamports - (await rent(newLen));
+    report.recommendations.push({
+      dataKey,
+      kind: truncate ? "trailing-zeros" : "unused-space",
+      dataLen,
+      contentLen,
+      newLen,
+      lamports,
+      instruction: shrinkInstruction(programId, authority, dataKey, meta, newLen, truncate, contentHash),
+    });
+    report.totalLamports += lamports;
+  }
+
+  report.recommendations.sort((a, b) => b.lamports - a.lamports);
+  return report;
+};
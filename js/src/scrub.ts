@@ -0,0 +1,38 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const SCRUB_RANGE_IX = 57;
+
+// mirrors ScrubFillOption in interface/src/state.rs
+export enum ScrubFillOption {
+  ZEROS,
+  RANDOM,
+}
+
+// overwrites len bytes of the content at offset, e.g. to redact sensitive content without closing the data account
+// the content_hash is cleared so a new one should be set once the redacted content is re-hashed
+export const scrubRangeInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  offset: number,
+  len: number,
+  fill: ScrubFillOption = ScrubFillOption.ZEROS,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SCRUB_RANGE_IX]),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(len).toArray("le", 8)),
+      Buffer.from([fill, debug ? 1 : 0]),
+    ]),
+  });
+};
@@ -0,0 +1,64 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+const UPDATE_DATA_ACCOUNT_IX = 1;
+// mirrors MAX_UPDATE_SEGMENTS in interface/src/state.rs
+export const MAX_UPDATE_SEGMENTS = 16;
+
+export interface IUpdateSegment {
+  offset: number;
+  data: Buffer;
+}
+
+const encodeSegment = (segment: IUpdateSegment): Buffer => {
+  return Buffer.concat([
+    Buffer.from(new BN(segment.offset).toArray("le", 8)),
+    Buffer.from(new BN(segment.data.length).toArray("le", 4)),
+    segment.data,
+  ]);
+};
+
+// patches scattered ranges of the content atomically in a single UpdateDataAccount, e.g. fields of a large structure
+// the first write is passed as the data of the update and the others (at most MAX_UPDATE_SEGMENTS) as its segments
+// writes must not overlap; the data_type and compression are kept and the content_hash covers the patched content
+export const updateSegmentsInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  meta: IDataAccountMeta,
+  writes: IUpdateSegment[],
+  contentHash?: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  if (writes.length === 0 || writes.length > MAX_UPDATE_SEGMENTS + 1) {
+    throw new Error(`expected between 1 and ${MAX_UPDATE_SEGMENTS + 1} writes`);
+  }
+  const [first, ...segments] = writes;
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_DATA_ACCOUNT_IX, meta.data_type]),
+      Buffer.from(new BN(first.data.length).toArray("le", 4)),
+      first.data,
+      Buffer.from(new BN(first.offset).toArray("le", 8)),
+      // realloc_down, truncate, commit_flag, verify_flag
+      Buffer.from([0, 0, 0, 0, meta.compression]),
+      contentHash ? Buffer.concat([Buffer.from([1]), contentHash]) : Buffer.from([0]),
+      // no sequence
+      Buffer.from([0]),
+      Buffer.from(new BN(segments.length).toArray("le", 4)),
+      ...segments.map(encodeSegment),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
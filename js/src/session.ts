@@ -0,0 +1,125 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { IDataAccountMeta } from "./common/types";
+import { getMetadataKey } from "./common/utils";
+
+export const SESSION_SEED = "data_account_session";
+
+const UPDATE_DATA_ACCOUNT_IX = 1;
+const CREATE_SESSION_IX = 33;
+const REVOKE_SESSION_IX = 34;
+
+export enum SessionInstructionOption {
+  UPDATE,
+  STAGE,
+  DELTA,
+}
+
+export interface ISessionScope {
+  // byte ranges of the data account (at most 8) a session key can write to
+  ranges: { offset: number; len: number }[];
+  instructions: SessionInstructionOption[];
+}
+
+export const getSessionKey = (programId: PublicKey, dataKey: PublicKey, sessionKey: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SESSION_SEED, "ascii"), dataKey.toBuffer(), sessionKey.toBuffer()],
+    programId
+  )[0];
+};
+
+// lets sessionKey (e.g., a keypair generated by a game client) sign writes within the scope until expirySlot
+export const createSessionInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  sessionKey: PublicKey,
+  expirySlot: number,
+  scope: ISessionScope,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: getSessionKey(programId, dataKey, sessionKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([CREATE_SESSION_IX]),
+      sessionKey.toBuffer(),
+      Buffer.from(new BN(expirySlot).toArray("le", 8)),
+      Buffer.from(new BN(scope.ranges.length).toArray("le", 4)),
+      ...scope.ranges.map(({ offset, len }) =>
+        Buffer.concat([Buffer.from(new BN(offset).toArray("le", 8)), Buffer.from(new BN(len).toArray("le", 8))])
+      ),
+      Buffer.from(new BN(scope.instructions.length).toArray("le", 4)),
+      Buffer.from(scope.instructions),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// signer can be the authority, the session key or anyone once the session has expired
+export const revokeSessionInstruction = (
+  programId: PublicKey,
+  signer: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  sessionKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: signer, isSigner: true, isWritable: false },
+      { pubkey: authority, isSigner: false, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: getSessionKey(programId, dataKey, sessionKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([Buffer.from([REVOKE_SESSION_IX]), Buffer.from([debug ? 1 : 0])]),
+  });
+};
+
+// UpdateDataAccount signed by the session key; the data_type and compression must be passed through unchanged
+// and the session key funds any growth of a dynamic data account
+export const sessionUpdateInstruction = (
+  programId: PublicKey,
+  sessionKey: PublicKey,
+  dataKey: PublicKey,
+  meta: IDataAccountMeta,
+  offset: number,
+  data: Buffer,
+  contentHash?: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: sessionKey, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: getSessionKey(programId, dataKey, sessionKey), isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_DATA_ACCOUNT_IX, meta.data_type]),
+      Buffer.from(new BN(data.length).toArray("le", 4)),
+      data,
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      // realloc_down, truncate, commit_flag, verify_flag
+      Buffer.from([0, 0, 0, 0, meta.compression]),
+      contentHash ? Buffer.concat([Buffer.from([1]), contentHash]) : Buffer.from([0]),
+      // no sequence
+      Buffer.from([0]),
+      // no segments
+      Buffer.alloc(4),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
@@ -0,0 +1,86 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { parseMetadata } from "./parseData";
+import { ISink } from "./worker";
+
+// a sink along with the data account it writes to, so its resulting content can be read back
+export interface IShadowTarget {
+  sink: ISink;
+  connection: Connection;
+  programId: PublicKey;
+  dataKey: PublicKey;
+}
+
+// the sha256 of the written content and of the content each data account ended up storing
+export interface IShadowReport {
+  expected: Buffer;
+  primary?: Buffer;
+  shadow?: Buffer;
+  shadowError?: unknown;
+  match: boolean;
+}
+
+export interface IShadowOptions {
+  onReport?: (report: IShadowReport) => void;
+  // throws once the shadow diverges instead of only reporting it, e.g. while validating in CI
+  failOnMismatch?: boolean;
+}
+
+// sha256 of the first content_len bytes stored in the data account
+export const storedContentHash = async (
+  connection: Connection,
+  programId: PublicKey,
+  dataKey: PublicKey
+): Promise<Buffer | undefined> => {
+  const [data_account, meta_account] = await connection.getMultipleAccountsInfo(
+    [dataKey, getMetadataKey(programId, dataKey)],
+    "confirmed"
+  );
+  if (!data_account || !meta_account || meta_account.data.length === 0) return undefined;
+  const meta = parseMetadata(meta_account.data);
+  return plaintextHash(data_account.data.subarray(0, Math.min(meta.content_len, data_account.data.length)));
+};
+
+// canary mode: writes every content with the trusted primary sink and again with the shadow sink
+// (e.g. a new uploader version writing to a second account or to an account on devnet),
+// then compares what both data accounts stored with the written content
+// a failing or diverging shadow is only reported so it never affects the primary write
+export class ShadowSink implements ISink {
+  constructor(
+    private readonly primary: IShadowTarget,
+    private readonly shadow: IShadowTarget,
+    private readonly options: IShadowOptions = {}
+  ) {}
+
+  async write(content: Buffer): Promise<void> {
+    await this.primary.sink.write(content);
+
+    const report: IShadowReport = { expected: plaintextHash(content), match: false };
+    try {
+      await this.shadow.sink.write(content);
+    } catch (err) {
+      report.shadowError = err;
+    }
+
+    const targets = [this.primary, this.shadow];
+    const [primary, shadow] = await Promise.all(
+      targets.map((target) => storedContentHash(target.connection, target.programId, target.dataKey))
+    );
+    report.primary = primary;
+    report.shadow = shadow;
+    report.match =
+      report.shadowError === undefined &&
+      primary !== undefined &&
+      shadow !== undefined &&
+      primary.equals(report.expected) &&
+      shadow.equals(report.expected);
+
+    this.options.onReport?.(report);
+    if (!report.match && this.options.failOnMismatch) {
+      throw new Error(
+        `shadow write diverged: expected ${report.expected.toString("hex")}, ` +
+          `primary ${primary?.toString("hex")}, shadow ${shadow?.toString("hex")}`
+      );
+    }
+  }
+}
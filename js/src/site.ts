@@ -0,0 +1,237 @@
+import {
+  ConfirmOptions,
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { readdirSync, readFileSync, statSync } from "fs";
+import { extname, join, relative, sep } from "path";
+import { CompressionOption, DataTypeOption } from "./common/types";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { dataTypeForMime } from "./mime";
+import { parseData } from "./parseData";
+
+const INITIALIZE_DATA_ACCOUNT_IX = 0;
+const UPDATE_DATA_ACCOUNT_IX = 1;
+const PART_SIZE = 881;
+
+const MIME_TYPES: Record<string, string> = {
+  ".html": "text/html",
+  ".htm": "text/html",
+  ".css": "text/css",
+  ".js": "text/javascript",
+  ".mjs": "text/javascript",
+  ".json": "application/json",
+  ".txt": "text/plain",
+  ".xml": "application/xml",
+  ".svg": "image/svg+xml",
+  ".png": "image/png",
+  ".jpg": "image/jpeg",
+  ".jpeg": "image/jpeg",
+  ".gif": "image/gif",
+  ".webp": "image/webp",
+  ".ico": "image/x-icon",
+  ".woff": "font/woff",
+  ".woff2": "font/woff2",
+  ".wasm": "application/wasm",
+};
+
+export interface IManifestEntry {
+  account: string;
+  // hex sha256 of the file
+  hash: string;
+  mime: string;
+}
+
+// payload of a DIRECTORY data account, see is_valid_manifest in interface/src/manifest.rs
+export interface IManifest {
+  files: Record<string, IManifestEntry>;
+}
+
+export const mimeType = (path: string): string => {
+  return MIME_TYPES[extname(path).toLowerCase()] ?? "application/octet-stream";
+};
+
+export const parseManifest = (data: Buffer): IManifest => {
+  return JSON.parse(data.toString("utf8")) as IManifest;
+};
+
+// maps a request path to its manifest entry, serving index.html for directories
+export const resolvePath = (manifest: IManifest, path: string): IManifestEntry | undefined => {
+  const normalized = "/" + path.split(/[?#]/)[0].replace(/^\/+/, "");
+  if (manifest.files[normalized]) return manifest.files[normalized];
+  return manifest.files[normalized.replace(/\/?$/, "/index.html")];
+};
+
+// fetches the file at path of a site and verifies it against the hash in the manifest
+export const fetchSitePath = async (
+  connection: Connection,
+  programId: PublicKey,
+  directoryKey: PublicKey,
+  path: string
+): Promise<{ entry: IManifestEntry; content: Buffer } | undefined> => {
+  const directory = await parseData(connection, directoryKey, getMetadataKey(programId, directoryKey));
+  if (directory.meta.data_type !== DataTypeOption.DIRECTORY || !directory.data) {
+    throw new Error(`${directoryKey.toBase58()} is not a DIRECTORY data account`);
+  }
+  const entry = resolvePath(parseManifest(directory.data), path);
+  if (!entry) return undefined;
+  const child = new PublicKey(entry.account);
+  const { data } = await parseData(connection, child, getMetadataKey(programId, child));
+  const content = data ?? Buffer.alloc(0);
+  if (plaintextHash(content).toString("hex") !== entry.hash) {
+    throw new Error(`content of ${path} does not match the manifest hash`);
+  }
+  return { entry, content };
+};
+
+const listFiles = (dir: string): string[] => {
+  return readdirSync(dir).flatMap((name) => {
+    const path = join(dir, name);
+    return statSync(path).isDirectory() ? listFiles(path) : [path];
+  });
+};
+
+// initializes an empty dynamic data account whose authority is the feepayer
+export const initializeInstruction = (
+  programId: PublicKey,
+  feePayer: PublicKey,
+  dataKey: PublicKey
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: feePayer, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: true, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([INITIALIZE_DATA_ACCOUNT_IX]),
+      feePayer.toBuffer(),
+      Buffer.from(new BN(0).toArray("le", 8)),
+      // is_dynamic, is_created, EXACT growth policy, append_only, idempotent, debug
+      Buffer.from([1, 0, 0, 0, 0, 0]),
+    ]),
+  });
+};
+
+const updateInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  dataType: DataTypeOption,
+  offset: number,
+  part: Buffer,
+  verify: boolean,
+  contentHash: Buffer
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([UPDATE_DATA_ACCOUNT_IX, dataType]),
+      Buffer.from(new BN(part.length).toArray("le", 4)),
+      part,
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      // realloc_down, truncate, commit_flag, verify_flag
+      Buffer.from([0, 0, verify ? 1 : 0, verify ? 1 : 0, CompressionOption.NONE]),
+      Buffer.concat([Buffer.from([1]), contentHash]),
+      // no sequence
+      Buffer.from([0]),
+      // no segments
+      Buffer.alloc(4),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([0]),
+    ]),
+  });
+};
+
+// creates a dynamic data account holding content, uploaded in transaction-sized parts
+const uploadContent = async (
+  connection: Connection,
+  programId: PublicKey,
+  feePayer: Keypair,
+  content: Buffer,
+  dataType: DataTypeOption,
+  verify: boolean
+): Promise<PublicKey> => {
+  const dataAccount = Keypair.generate();
+  const confirmOptions = { preflightCommitment: "confirmed", commitment: "confirmed" } as ConfirmOptions;
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(initializeInstruction(programId, feePayer.publicKey, dataAccount.publicKey)),
+    [feePayer, dataAccount],
+    confirmOptions
+  );
+  const hash = plaintextHash(content);
+  const parts = Math.max(Math.ceil(content.length / PART_SIZE), 1);
+  for (let current = 0; current < parts; ++current) {
+    const part = content.subarray(current * PART_SIZE, (current + 1) * PART_SIZE);
+    // only the last part completes the content, so only it is committed and verified
+    const last = current === parts - 1;
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        updateInstruction(
+          programId,
+          feePayer.publicKey,
+          dataAccount.publicKey,
+          dataType,
+          current * PART_SIZE,
+          part,
+          verify && last,
+          hash
+        )
+      ),
+      [feePayer],
+      confirmOptions
+    );
+  }
+  return dataAccount.publicKey;
+};
+
+// uploads every file under dir to its own data account and then the manifest to a DIRECTORY data account
+// e.g. uploadSite(connection, programId, feePayer, "./dist") for `upload-site ./dist`
+export const uploadSite = async (
+  connection: Connection,
+  programId: PublicKey,
+  feePayer: Keypair,
+  dir: string,
+  debug?: boolean
+): Promise<{ directoryKey: PublicKey; manifest: IManifest }> => {
+  const manifest: IManifest = { files: {} };
+  for (const file of listFiles(dir)) {
+    const path = "/" + relative(dir, file).split(sep).join("/");
+    const content = readFileSync(file);
+    const mime = mimeType(file);
+    const account = await uploadContent(connection, programId, feePayer, content, dataTypeForMime(mime), false);
+    manifest.files[path] = { account: account.toBase58(), hash: plaintextHash(content).toString("hex"), mime };
+    if (debug) {
+      console.log(`${path} (${mime}, ${content.length} bytes): ${account.toBase58()}`);
+    }
+  }
+  const directoryKey = await uploadContent(
+    connection,
+    programId,
+    feePayer,
+    Buffer.from(JSON.stringify(manifest), "utf8"),
+    DataTypeOption.DIRECTORY,
+    true
+  );
+  if (debug) {
+    console.log(`manifest of ${Object.keys(manifest.files).length} files: ${directoryKey.toBase58()}`);
+  }
+  return { directoryKey, manifest };
+};
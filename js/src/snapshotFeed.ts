@@ -0,0 +1,91 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const SNAPSHOT_PRICE_FEED_IX = 7;
+export const SNAPSHOT_HEADER_SIZE = 8 + 8 + 32;
+
+export interface ISnapshotConfig {
+  feedOffset: number;
+  feedLen: number;
+  // number of snapshots kept before wrapping around to the start of the data account
+  capacity: number;
+  intervalMs: number;
+  debug?: boolean;
+}
+
+export const snapshotPriceFeedInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  feedKey: PublicKey,
+  feedOffset: number,
+  feedLen: number,
+  offset: number,
+  debug?: boolean
+): TransactionInstruction => {
+  const metaKey = getMetadataKey(programId, dataKey);
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: metaKey, isSigner: false, isWritable: true },
+      { pubkey: feedKey, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SNAPSHOT_PRICE_FEED_IX]),
+      Buffer.from(new BN(feedOffset).toArray("le", 8)),
+      Buffer.from(new BN(feedLen).toArray("le", 8)),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// periodically snapshots the feed into the data account as a ring buffer of records
+export const startFeedSnapshots = (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataKey: PublicKey,
+  feedKey: PublicKey,
+  config: ISnapshotConfig
+): (() => void) => {
+  const recordSize = SNAPSHOT_HEADER_SIZE + config.feedLen;
+  let index = 0;
+  const snapshot = async () => {
+    const ix = snapshotPriceFeedInstruction(
+      programId,
+      authority.publicKey,
+      dataKey,
+      feedKey,
+      config.feedOffset,
+      config.feedLen,
+      index * recordSize,
+      config.debug
+    );
+    try {
+      const txid = await sendAndConfirmTransaction(connection, new Transaction().add(ix), [authority], {
+        commitment: "confirmed",
+      });
+      if (config.debug) {
+        console.log(`snapshot ${index} written: ${txid}`);
+      }
+      index = (index + 1) % config.capacity;
+    } catch (err) {
+      console.error(err);
+    }
+  };
+  const timer = setInterval(snapshot, config.intervalMs);
+  return () => clearInterval(timer);
+};
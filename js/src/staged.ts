@@ -0,0 +1,103 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const WRITE_STAGED_IX = 67;
+const COMMIT_STAGED_IX = 68;
+const CHUNK_SIZE = 900;
+// mirrors STAGED_BUFFER_SEED in interface/src/state.rs
+export const STAGED_BUFFER_SEED = "data_account_staged_buffer";
+
+export const getStagedBufferKey = (programId: PublicKey, dataKey: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync([Buffer.from(STAGED_BUFFER_SEED, "ascii"), dataKey.toBuffer()], programId)[0];
+};
+
+// writes the chunk at offset of the staged buffer, leaving the content of the data account untouched
+// reset starts the staged content over, ending at this chunk
+export const writeStagedInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  offset: number,
+  chunk: Buffer,
+  reset: boolean,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: false },
+      { pubkey: getStagedBufferKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([WRITE_STAGED_IX]),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(chunk.length).toArray("le", 4)),
+      chunk,
+      Buffer.from([reset ? 1 : 0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// swaps the staged buffer in as the content and bumps the generation in the metadata
+export const commitStagedInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: getStagedBufferKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([COMMIT_STAGED_IX]),
+      // no expected_revision
+      Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// stages the content in chunks and swaps it in at once, so readers never observe a half-written content
+// readers can compare the generation before and after reading to detect a swap in between
+export const uploadStaged = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataKey: PublicKey,
+  content: Buffer,
+  debug?: boolean
+): Promise<string> => {
+  const send = (ix: TransactionInstruction) =>
+    sendAndConfirmTransaction(connection, new Transaction().add(ix), [authority], { commitment: "confirmed" });
+
+  for (let offset = 0; offset < content.length; offset += CHUNK_SIZE) {
+    const chunk = content.subarray(offset, offset + CHUNK_SIZE);
+    // the first chunk discards anything left staged by an abandoned upload
+    const txid = await send(
+      writeStagedInstruction(programId, authority.publicKey, dataKey, offset, chunk, offset === 0, debug)
+    );
+    if (debug) {
+      console.log(`chunk at ${offset}/${content.length} staged: ${txid}`);
+    }
+  }
+  return send(commitStagedInstruction(programId, authority.publicKey, dataKey, debug));
+};
@@ -0,0 +1,66 @@
+import { Commitment, Connection, PublicKey } from "@solana/web3.js";
+
+// changed ranges closer than this are yielded as one range since each range costs more than the bytes in between
+const MERGE_GAP = 8;
+
+export interface IDataRange {
+  offset: number;
+  data: Buffer;
+}
+
+// the changes of the data account between two notifications
+export interface IDataDelta {
+  slot: number;
+  ranges: IDataRange[];
+  // length of the data account after the change (shorter than before if it was shrunk)
+  length: number;
+}
+
+// computes the byte ranges of next that differ from prev, including any bytes past the end of prev
+export const changedRanges = (prev: Buffer, next: Buffer): IDataRange[] => {
+  const ranges: [number, number][] = [];
+  for (let i = 0; i < next.length; ++i) {
+    if (i < prev.length && prev[i] === next[i]) continue;
+    const last = ranges[ranges.length - 1];
+    if (last && i - last[1] <= MERGE_GAP) {
+      last[1] = i + 1;
+    } else {
+      ranges.push([i, i + 1]);
+    }
+  }
+  // copies so the ranges don't keep the whole notification alive
+  return ranges.map(([start, end]) => ({ offset: start, data: Buffer.from(next.subarray(start, end)) }));
+};
+
+// applies a delta to a local copy of the data account
+export const applyDataDelta = (data: Buffer, delta: IDataDelta): Buffer => {
+  const next = Buffer.alloc(delta.length);
+  data.copy(next, 0, 0, Math.min(data.length, delta.length));
+  delta.ranges.forEach(({ offset, data: range }) => range.copy(next, offset));
+  return next;
+};
+
+// subscribes to the data account and calls onDelta with only the changed byte ranges of each change
+// the first notification after subscribing is diffed against initial (the whole account if not given)
+// returns the id to pass to connection.removeAccountChangeListener
+export const subscribeDataDeltas = (
+  connection: Connection,
+  dataKey: PublicKey,
+  onDelta: (delta: IDataDelta) => void,
+  initial: Buffer = Buffer.alloc(0),
+  commitment: Commitment = "confirmed"
+): number => {
+  let prev = initial;
+  return connection.onAccountChange(
+    dataKey,
+    (account, { slot }) => {
+      const ranges = changedRanges(prev, account.data);
+      const shrunk = account.data.length < prev.length;
+      prev = account.data;
+      if (ranges.length > 0 || shrunk) {
+        onDelta({ slot, ranges, length: account.data.length });
+      }
+    },
+    commitment
+  );
+};
@@ -0,0 +1,157 @@
+import { Connection, PartiallyDecodedInstruction, PublicKey } from "@solana/web3.js";
+import * as bs58 from "bs58";
+import { GrowthPolicyOption } from "./common/types";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { MetadataReader, parseData } from "./parseData";
+
+export const TAGS_SEED = "data_account_tags";
+
+// instructions whose effect on the content can be replayed from the transaction alone
+const INITIALIZE_DATA_ACCOUNT_IX = 0;
+const UPDATE_DATA_ACCOUNT_IX = 1;
+const INITIALIZE_USER_DATA_ACCOUNT_IX = 4;
+const SET_ALLOWLIST_ROOT_IX = 5;
+const STAGE_UPDATE_IX = 8;
+const FLUSH_STAGING_IX = 9;
+const RESTORE_FROM_ARCHIVE_IX = 12;
+
+export interface IRevisionTag {
+  label: string;
+  slot: number;
+  content_len: number;
+  hash: Buffer;
+}
+
+export const getTagsKey = (programId: PublicKey, dataKey: PublicKey): PublicKey => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(TAGS_SEED, "ascii"), dataKey.toBuffer()],
+    programId
+  )[0];
+};
+
+export const parseTags = async (connection: Connection, programId: PublicKey, dataKey: PublicKey): Promise<IRevisionTag[]> => {
+  const tags_account = await connection.getAccountInfo(getTagsKey(programId, dataKey), "confirmed");
+  if (!tags_account || tags_account.data.length === 0) return [];
+  const reader = new MetadataReader(tags_account.data);
+  const tags: IRevisionTag[] = [];
+  for (let len = reader.u32(); len > 0; --len) {
+    tags.push({
+      label: reader.string(),
+      slot: reader.u64(),
+      content_len: reader.u64(),
+      hash: reader.bytes(32),
+    });
+  }
+  return tags;
+};
+
+const write = (content: Buffer, offset: number, data: Buffer): Buffer => {
+  const end = offset + data.length;
+  const next = end > content.length ? Buffer.concat([content, Buffer.alloc(end - content.length)]) : content;
+  data.copy(next, offset);
+  return next;
+};
+
+// reconstructs the content of the data account as of the tagged slot by replaying its transactions
+export const getContentAsOfTag = async (connection: Connection, programId: PublicKey, dataKey: PublicKey, label: string): Promise<Buffer> => {
+  const tag = (await parseTags(connection, programId, dataKey)).find((tag) => tag.label === label);
+  if (!tag) {
+    throw new Error(`Tag ${label} not found`);
+  }
+
+  // the current content may still be the tagged content
+  const { data } = await parseData(connection, dataKey, getMetadataKey(programId, dataKey));
+  if (data && data.length === tag.content_len && plaintextHash(data).equals(tag.hash)) {
+    return data;
+  }
+
+  // collect all successful transactions up to the tagged slot, oldest first
+  const signatures: string[] = [];
+  let before: string | undefined;
+  for (;;) {
+    const page = await connection.getSignaturesForAddress(dataKey, { before }, "confirmed");
+    if (page.length === 0) break;
+    page.filter((sig) => !sig.err && sig.slot <= tag.slot).forEach((sig) => signatures.push(sig.signature));
+    before = page[page.length - 1].signature;
+  }
+  signatures.reverse();
+
+  let content = Buffer.alloc(0);
+  let staged: { offset: number; data: Buffer }[] = [];
+  let initialized = false;
+  for (const signature of signatures) {
+    const tx = await connection.getParsedTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+    const instructions = tx?.transaction.message.instructions ?? [];
+    for (const instruction of instructions) {
+      const ix = instruction as PartiallyDecodedInstruction;
+      if (!ix.data || !ix.programId.equals(programId)) continue;
+      const dataIdx = ix.accounts.findIndex((key) => key.equals(dataKey));
+      if (dataIdx < 0) continue;
+      const reader = new MetadataReader(Buffer.from(bs58.decode(ix.data)));
+      switch (reader.u8()) {
+        case INITIALIZE_DATA_ACCOUNT_IX: {
+          reader.bytes(32); // authority
+          reader.u64(); // space
+          reader.bool(); // is_dynamic
+          reader.bool(); // is_created
+          if (reader.u8() !== GrowthPolicyOption.EXACT) reader.u32();
+          reader.bool(); // append_only
+          // an idempotent initialize of an initialized data account leaves its content as is
+          if (reader.bool() && initialized) break;
+          content = Buffer.alloc(0);
+          staged = [];
+          initialized = true;
+          break;
+        }
+        case INITIALIZE_USER_DATA_ACCOUNT_IX:
+          content = Buffer.alloc(0);
+          staged = [];
+          initialized = true;
+          break;
+        case UPDATE_DATA_ACCOUNT_IX: {
+          reader.u8(); // data_type
+          const bytes = reader.bytes(reader.u32());
+          const offset = reader.u64();
+          reader.bool(); // realloc_down
+          const truncate = reader.bool();
+          reader.bytes(3); // commit_flag, verify_flag, compression
+          reader.option(() => reader.bytes(32)); // content_hash
+          reader.option(() => reader.u64()); // sequence
+          const writes = [{ offset, data: bytes }].concat(
+            Array.from({ length: reader.u32() }, () => ({ offset: reader.u64(), data: reader.bytes(reader.u32()) }))
+          );
+          writes.forEach((w) => (content = write(content, w.offset, w.data)));
+          if (truncate) {
+            // the content ends at the furthest write
+            content = content.subarray(0, writes.reduce((end, w) => Math.max(end, w.offset + w.data.length), 0));
+          }
+          break;
+        }
+        case SET_ALLOWLIST_ROOT_IX:
+          content = write(content, 0, reader.bytes(32));
+          break;
+        case STAGE_UPDATE_IX: {
+          const offset = reader.u64();
+          staged.push({ offset, data: reader.bytes(reader.u32()) });
+          break;
+        }
+        case FLUSH_STAGING_IX:
+          staged.forEach(({ offset, data }) => (content = write(content, offset, data)));
+          staged = [];
+          break;
+        case RESTORE_FROM_ARCHIVE_IX: {
+          const offset = reader.u64();
+          content = write(content, offset, reader.bytes(reader.u32()));
+          break;
+        }
+      }
+    }
+  }
+
+  // ensure the replayed content is exactly the tagged content
+  content = write(content, tag.content_len, Buffer.alloc(0)).subarray(0, tag.content_len);
+  if (!plaintextHash(content).equals(tag.hash)) {
+    throw new Error(`Content as of tag ${label} could not be reconstructed from the transaction history`);
+  }
+  return content;
+};
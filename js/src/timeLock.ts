@@ -0,0 +1,55 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+
+const SEAL_CONTENT_IX = 23;
+const REVEAL_CONTENT_IX = 24;
+
+// seals the current content until revealAfterSlot; pass plaintextHash (see plaintextHash in ./common/utils) to let anyone reveal
+export const sealContentInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  revealAfterSlot: number,
+  plaintextHash?: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([SEAL_CONTENT_IX]),
+      Buffer.from(new BN(revealAfterSlot).toArray("le", 8)),
+      plaintextHash ? Buffer.concat([Buffer.from([1]), plaintextHash]) : Buffer.from([0]),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const revealContentInstruction = (
+  programId: PublicKey,
+  revealer: PublicKey,
+  dataKey: PublicKey,
+  plaintext: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: revealer, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([REVEAL_CONTENT_IX]),
+      Buffer.from(new BN(plaintext.length).toArray("le", 4)),
+      plaintext,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
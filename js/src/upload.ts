@@ -0,0 +1,122 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+const BEGIN_UPLOAD_IX = 41;
+const APPEND_CHUNK_IX = 42;
+const COMMIT_UPLOAD_IX = 43;
+const CHUNK_SIZE = 900;
+
+export const beginUploadInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  totalLen: number,
+  checksum: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([BEGIN_UPLOAD_IX]),
+      Buffer.from(new BN(totalLen).toArray("le", 8)),
+      checksum,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const appendChunkInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  offset: number,
+  chunk: Buffer,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([APPEND_CHUNK_IX]),
+      Buffer.from(new BN(offset).toArray("le", 8)),
+      Buffer.from(new BN(chunk.length).toArray("le", 4)),
+      chunk,
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+export const commitUploadInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: true },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([COMMIT_UPLOAD_IX, debug ? 1 : 0]),
+  });
+};
+
+// uploads the content in chunks and commits it, resuming a partial upload of the same content if there is one
+export const uploadContent = async (
+  connection: Connection,
+  programId: PublicKey,
+  authority: Keypair,
+  dataKey: PublicKey,
+  content: Buffer,
+  debug?: boolean
+): Promise<string> => {
+  const send = (ix: TransactionInstruction) =>
+    sendAndConfirmTransaction(connection, new Transaction().add(ix), [authority], { commitment: "confirmed" });
+
+  const checksum = plaintextHash(content);
+  const meta_account = await connection.getAccountInfo(getMetadataKey(programId, dataKey), "confirmed");
+  if (!meta_account) {
+    throw new Error(`Metadata of ${dataKey.toBase58()} not found`);
+  }
+  const upload = parseMetadata(meta_account.data).upload;
+  let written = 0;
+  if (upload && upload.total_len === content.length && upload.checksum.equals(checksum)) {
+    written = upload.written;
+    if (debug) {
+      console.log(`resuming upload at ${written}/${content.length}`);
+    }
+  } else {
+    await send(beginUploadInstruction(programId, authority.publicKey, dataKey, content.length, checksum, debug));
+  }
+
+  for (; written < content.length; written += CHUNK_SIZE) {
+    const chunk = content.subarray(written, written + CHUNK_SIZE);
+    const txid = await send(appendChunkInstruction(programId, authority.publicKey, dataKey, written, chunk, debug));
+    if (debug) {
+      console.log(`chunk at ${written}/${content.length} appended: ${txid}`);
+    }
+  }
+  return send(commitUploadInstruction(programId, authority.publicKey, dataKey, debug));
+};
@@ -0,0 +1,79 @@
+import { Connection, Keypair, PublicKey, sendAndConfirmTransaction, Transaction, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { getMetadataKey } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+const VERIFY_DATA_ACCOUNT_IX = 44;
+const VERIFY_DATA_STEP_IX = 65;
+
+// largest window (a multiple of 64 bytes) hashed within the default compute limit of an instruction
+export const DEFAULT_VERIFY_WINDOW = 3_584;
+
+// anyone can verify the content of a data account against its content_hash
+export const verifyDataAccountInstruction = (
+  programId: PublicKey,
+  dataKey: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.from([VERIFY_DATA_ACCOUNT_IX, debug ? 1 : 0]),
+  });
+};
+
+// hashes the next window of the content, the last one sets the serialization_status
+export const verifyDataStepInstruction = (
+  programId: PublicKey,
+  dataKey: PublicKey,
+  window = DEFAULT_VERIFY_WINDOW,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([
+      Buffer.from([VERIFY_DATA_STEP_IX]),
+      Buffer.from(new BN(window).toArray("le", 8)),
+      Buffer.from([debug ? 1 : 0]),
+    ]),
+  });
+};
+
+// verifies content too large for a single instruction one window per transaction, resuming any verification
+// in progress, and returns whether the content matches its content_hash
+export const verifyInSteps = async (
+  connection: Connection,
+  programId: PublicKey,
+  payer: Keypair,
+  dataKey: PublicKey,
+  window = DEFAULT_VERIFY_WINDOW,
+  debug?: boolean
+): Promise<boolean> => {
+  for (;;) {
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(verifyDataStepInstruction(programId, dataKey, window, debug)),
+      [payer],
+      { commitment: "confirmed" }
+    );
+    const meta_account = await connection.getAccountInfo(getMetadataKey(programId, dataKey), "confirmed");
+    if (!meta_account) {
+      throw new Error(`metadata of ${dataKey.toBase58()} not found`);
+    }
+    const meta = parseMetadata(meta_account.data);
+    if (!meta.verification_cursor) {
+      // VERIFIED, see SerializationStatusOption
+      return meta.serialization_status === 1;
+    }
+    if (debug) {
+      console.log(`verified ${meta.verification_cursor.offset} of ${meta.content_len} bytes`);
+    }
+  }
+};
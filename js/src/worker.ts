@@ -0,0 +1,197 @@
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { CompressionOption, DataTypeOption } from "./common/types";
+import { getMetadataKey, plaintextHash } from "./common/utils";
+import { parseMetadata } from "./parseData";
+
+const UPDATE_DATA_ACCOUNT_IX = 1;
+const PART_SIZE = 881;
+
+// produces the latest value of an off-chain resource
+export interface ISource<T> {
+  read(): Promise<T>;
+}
+
+// writes a value somewhere, e.g. a data account
+export interface ISink {
+  write(content: Buffer): Promise<void>;
+}
+
+export type Transform<T> = (value: T) => Buffer | Promise<Buffer>;
+
+// polls a JSON endpoint
+export class HttpJsonSource implements ISource<unknown> {
+  constructor(private readonly url: string, private readonly init?: RequestInit) {}
+
+  async read(): Promise<unknown> {
+    const res = await fetch(this.url, this.init);
+    if (!res.ok) {
+      throw new Error(`${this.url} responded with ${res.status}`);
+    }
+    return res.json();
+  }
+}
+
+// serializes JSON with sorted keys so semantically equal values hash the same
+export const canonicalJSON: Transform<unknown> = (value) => {
+  const sort = (v: unknown): unknown => {
+    if (Array.isArray(v)) return v.map(sort);
+    if (v && typeof v === "object") {
+      return Object.keys(v as object)
+        .sort()
+        .reduce((sorted, key) => {
+          sorted[key] = sort((v as Record<string, unknown>)[key]);
+          return sorted;
+        }, {} as Record<string, unknown>);
+    }
+    return v;
+  };
+  return Buffer.from(JSON.stringify(sort(value)), "utf8");
+};
+
+// overwrites the content of a dynamic data account, releasing the space beyond it on the last part
+export class DataAccountSink implements ISink {
+  constructor(
+    private readonly connection: Connection,
+    private readonly programId: PublicKey,
+    private readonly authority: Keypair,
+    private readonly dataKey: PublicKey,
+    private readonly dataType: DataTypeOption = DataTypeOption.JSON,
+    private readonly debug?: boolean
+  ) {}
+
+  // sha256 of the content currently stored, if the data account records one
+  async currentHash(): Promise<Buffer | undefined> {
+    const meta_account = await this.connection.getAccountInfo(getMetadataKey(this.programId, this.dataKey), "confirmed");
+    if (!meta_account || meta_account.data.length === 0) return undefined;
+    return parseMetadata(meta_account.data).content_hash;
+  }
+
+  async write(content: Buffer): Promise<void> {
+    const hash = plaintextHash(content);
+    const parts = Math.max(Math.ceil(content.length / PART_SIZE), 1);
+    for (let current = 0; current < parts; ++current) {
+      const part = content.subarray(current * PART_SIZE, (current + 1) * PART_SIZE);
+      const first = current === 0;
+      const last = current === parts - 1;
+      const txid = await sendAndConfirmTransaction(
+        this.connection,
+        new Transaction().add(this.updateInstruction(current * PART_SIZE, part, first, last, hash)),
+        [this.authority],
+        { commitment: "confirmed" }
+      );
+      if (this.debug) {
+        console.log(`part ${current + 1}/${parts} written: ${txid}`);
+      }
+    }
+  }
+
+  // the first part truncates the old content so a shorter value does not keep its tail
+  private updateInstruction(
+    offset: number,
+    part: Buffer,
+    truncate: boolean,
+    reallocDown: boolean,
+    contentHash: Buffer
+  ): TransactionInstruction {
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: this.authority.publicKey, isSigner: true, isWritable: true },
+        { pubkey: this.dataKey, isSigner: false, isWritable: true },
+        { pubkey: getMetadataKey(this.programId, this.dataKey), isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data: Buffer.concat([
+        Buffer.from([UPDATE_DATA_ACCOUNT_IX, this.dataType]),
+        Buffer.from(new BN(part.length).toArray("le", 4)),
+        part,
+        Buffer.from(new BN(offset).toArray("le", 8)),
+        // realloc_down, truncate, commit_flag, verify_flag
+        Buffer.from([reallocDown ? 1 : 0, truncate ? 1 : 0, 0, 0, CompressionOption.NONE]),
+        Buffer.concat([Buffer.from([1]), contentHash]),
+        // no sequence
+        Buffer.from([0]),
+        // no segments
+        Buffer.alloc(4),
+        // no expected_revision
+        Buffer.from([0]),
+        Buffer.from([this.debug ? 1 : 0]),
+      ]),
+    });
+  }
+}
+
+export interface IWorkerOptions {
+  pollIntervalMs: number;
+  // a changed value is only written once it has been stable for this long, so bursts of changes cost one write
+  debounceMs?: number;
+  onWrite?: (hash: Buffer) => void;
+  onError?: (err: unknown) => void;
+}
+
+// polls the source, transforms each value and writes it to the sink only when its hash changes
+// e.g. new Worker(new HttpJsonSource(url), canonicalJSON, new DataAccountSink(...), { pollIntervalMs: 60_000 }).start()
+export class Worker<T> {
+  private timer?: NodeJS.Timeout;
+  private lastWritten?: string;
+  private pending?: { hash: string; content: Buffer; since: number };
+
+  constructor(
+    private readonly source: ISource<T>,
+    private readonly transform: Transform<T>,
+    private readonly sink: ISink,
+    private readonly options: IWorkerOptions,
+    // hash of the content already stored (e.g. DataAccountSink.currentHash) so a restart does not rewrite it
+    initialHash?: Buffer
+  ) {
+    this.lastWritten = initialHash?.toString("hex");
+  }
+
+  start() {
+    const poll = async () => {
+      try {
+        await this.tick(Date.now());
+      } catch (err) {
+        (this.options.onError ?? console.error)(err);
+      }
+      this.timer = setTimeout(poll, this.options.pollIntervalMs);
+    };
+    poll();
+  }
+
+  stop() {
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+  }
+
+  // runs a single poll, returns whether the sink was written to
+  async tick(now: number): Promise<boolean> {
+    const content = await this.transform(await this.source.read());
+    const hash = plaintextHash(content).toString("hex");
+    if (hash === this.lastWritten) {
+      this.pending = undefined;
+      return false;
+    }
+    if (!this.pending || this.pending.hash !== hash) {
+      this.pending = { hash, content, since: now };
+    }
+    if (now - this.pending.since < (this.options.debounceMs ?? 0)) {
+      return false;
+    }
+    await this.sink.write(this.pending.content);
+    this.lastWritten = hash;
+    this.pending = undefined;
+    this.options.onWrite?.(Buffer.from(hash, "hex"));
+    return true;
+  }
+}
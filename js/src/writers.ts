@@ -0,0 +1,41 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import { getMetadataKey } from "./common/utils";
+
+const ADD_WRITER_IX = 47;
+const REMOVE_WRITER_IX = 48;
+
+const writerInstruction = (
+  ix: number,
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  writer: PublicKey,
+  debug?: boolean
+): TransactionInstruction => {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: dataKey, isSigner: false, isWritable: false },
+      { pubkey: getMetadataKey(programId, dataKey), isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([Buffer.from([ix]), writer.toBuffer(), Buffer.from([debug ? 1 : 0])]),
+  });
+};
+
+// lets writer sign UpdateDataAccount for dataKey alongside the authority
+export const addWriterInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  writer: PublicKey,
+  debug?: boolean
+): TransactionInstruction => writerInstruction(ADD_WRITER_IX, programId, authority, dataKey, writer, debug);
+
+export const removeWriterInstruction = (
+  programId: PublicKey,
+  authority: PublicKey,
+  dataKey: PublicKey,
+  writer: PublicKey,
+  debug?: boolean
+): TransactionInstruction => writerInstruction(REMOVE_WRITER_IX, programId, authority, dataKey, writer, debug);
@@ -13,6 +13,7 @@ import * as dotenv from 'dotenv';
 import { loadPNGFromFile, loadKeypairFromFile, PDA_SEED } from '../../src/common/utils'
 import { DataTypeOption } from "../../src/common/types";
 import { parseJSON } from "../../src/parseJSON";
+import { ensureFaucetFunds, estimateUploadCost } from "../../src/faucet";
   
 dotenv.config();
 
@@ -100,9 +101,9 @@ const main = async () => {
   const feePayer = loadKeypairFromFile("/Users/nvsriram/code/solana-acount/program/target/deploy/feepayer-keypair.json");
   const dataAccount = loadKeypairFromFile("/Users/nvsriram/code/solana-acount/program/target/deploy/dataaccountpng-keypair.json");
 
-  console.log("Requesting Airdrop of 1 SOL...");
-  await connection.requestAirdrop(feePayer.publicKey, 7e9);
-  console.log("Airdrop received");
+  const old = loadPNGFromFile("/Users/nvsriram/code/solana-acount/js/tests/nfts/testNFT-min.png");
+  const required = await estimateUploadCost(connection, 5000, Math.ceil(old.length / PART_SIZE));
+  await ensureFaucetFunds(connection, feePayer.publicKey, required, { debug: true });
 
   const createIx = SystemProgram.createAccount(
     {
@@ -184,8 +185,7 @@ const main = async () => {
   );
   console.log(`https://explorer.solana.com/tx/${txid}?cluster=devnet`);
   await parseJSON(connection, dataAccount.publicKey, pda, true);
-    
-  const old = loadPNGFromFile("/Users/nvsriram/code/solana-acount/js/tests/nfts/testNFT-min.png");
+
   await uploadData(connection, programId, feePayer, dataAccount, old);
 };
 
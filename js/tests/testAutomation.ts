@@ -0,0 +1,54 @@
+import { Connection, Keypair, PublicKey, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import * as dotenv from "dotenv";
+import { loadKeypairFromFile } from "../src/common/utils";
+import {
+  automatedUpdateInstruction,
+  checkAutomationHealth,
+  getClockworkThreadKey,
+  setAutomationInstruction,
+} from "../src/automation";
+
+dotenv.config();
+
+// Example keeper for an already initialized static data account: the authority delegates scheduled
+// writes to a keeper key which then writes the current time every MIN_INTERVAL_SLOTS.
+// With Clockwork, set the delegate to getClockworkThreadKey(threadAuthority, threadId) instead and
+// use automatedUpdateInstruction (with the thread pda as delegate) as the thread instruction.
+const MIN_INTERVAL_SLOTS = 10;
+
+const main = async () => {
+  const programId = new PublicKey(process.env.PROGRAM_ID as string);
+  const dataKey = new PublicKey(process.env.DATA_ACCOUNT as string);
+
+  const connection = new Connection("http://localhost:8899", "confirmed");
+
+  const authority = loadKeypairFromFile(process.env.AUTHORITY_KEYPAIR as string);
+  const keeper = Keypair.generate();
+  console.log("Clockwork thread delegate would be", getClockworkThreadKey(authority.publicKey, "data-account").toBase58());
+
+  await connection.requestAirdrop(keeper.publicKey, 1e9);
+
+  const setIx = setAutomationInstruction(programId, authority.publicKey, dataKey, keeper.publicKey, MIN_INTERVAL_SLOTS, true);
+  await sendAndConfirmTransaction(connection, new Transaction().add(setIx), [authority]);
+
+  for (let i = 0; i < 5; i++) {
+    const timestamp = Buffer.alloc(8);
+    timestamp.writeBigInt64LE(BigInt(Date.now()));
+    const ix = automatedUpdateInstruction(programId, keeper.publicKey, dataKey, 0, timestamp, true);
+    await sendAndConfirmTransaction(connection, new Transaction().add(ix), [keeper]);
+
+    const { automation, stale } = await checkAutomationHealth(connection, programId, dataKey);
+    console.log(`heartbeat at slot ${automation?.last_heartbeat_slot} (stale: ${stale})`);
+
+    // wait for the rate limit (~400ms per slot)
+    await new Promise((resolve) => setTimeout(resolve, MIN_INTERVAL_SLOTS * 400));
+  }
+};
+
+main()
+  .then(() => {
+    console.log("Success");
+  })
+  .catch((e) => {
+    console.error(e);
+  });
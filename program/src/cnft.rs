@@ -0,0 +1,61 @@
+//! Data accounts bound to a compressed NFT (Bubblegum) whose content is written by its current owner
+//! The data account is the pda derived from the asset id and its authority is the data account itself.
+//! Ownership is established by recomputing the Bubblegum leaf of the owner and verifying it against
+//! the merkle tree via the account compression program, with the proof nodes as remaining accounts
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    pubkey::Pubkey,
+};
+
+pub use solana_data_account_interface::cnft::*;
+
+use crate::error::DataAccountError;
+
+/// Anchor discriminator of the verify_leaf instruction of the account compression program
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// Verifies that the owner currently owns the cNFT of the leaf by verifying its leaf hash
+/// against the merkle tree via the account compression program
+pub fn verify_owner<'a>(
+    compression_program: &AccountInfo<'a>,
+    merkle_tree: &AccountInfo<'a>,
+    proof: &[AccountInfo<'a>],
+    asset_id: &Pubkey,
+    owner: &Pubkey,
+    leaf: &CnftLeaf,
+) -> ProgramResult {
+    if *compression_program.key != ACCOUNT_COMPRESSION_PROGRAM_ID
+        || *merkle_tree.owner != ACCOUNT_COMPRESSION_PROGRAM_ID
+    {
+        return Err(DataAccountError::InvalidMerkleTree.into());
+    }
+
+    let mut accounts = vec![AccountMeta::new_readonly(*merkle_tree.key, false)];
+    accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node.key, false)),
+    );
+
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&leaf.root);
+    data.extend_from_slice(&leaf_hash(asset_id, owner, leaf));
+    data.extend_from_slice(&leaf.index.to_le_bytes());
+
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend(proof.iter().cloned());
+    account_infos.push(compression_program.clone());
+
+    invoke(
+        &Instruction {
+            program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )
+}
@@ -0,0 +1,36 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+pub const COMMITMENT_SEED: &[u8] = b"data_account_commitment";
+pub const COMMITMENT_SIZE: usize = 32 + 32 + 8 + 1;
+
+/// Hashes a value with its salt (an empty salt hashes the value alone)
+pub fn commitment_hash(value: &[u8], salt: &[u8]) -> [u8; 32] {
+    hashv(&[salt, value]).to_bytes()
+}
+
+/// Commitment to a hidden value that has to be revealed before a deadline
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct Commitment {
+    pub committer: Pubkey,
+    pub hash: [u8; 32],
+    pub reveal_deadline_slot: u64,
+    pub revealed: Option<Vec<u8>>,
+}
+
+impl Commitment {
+    /// Verifies that the value and salt are the preimage of the commitment
+    pub fn verify(&self, value: &[u8], salt: &[u8]) -> bool {
+        commitment_hash(value, salt) == self.hash
+    }
+
+    /// Verifies whether the commitment can still be revealed
+    pub fn is_open(&self, current_slot: u64) -> bool {
+        self.revealed.is_none() && current_slot <= self.reveal_deadline_slot
+    }
+
+    /// Verifies whether the commitment missed its reveal deadline
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        self.revealed.is_none() && current_slot > self.reveal_deadline_slot
+    }
+}
@@ -0,0 +1,127 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+use crate::state::DataAccountMetadata;
+
+pub const TREE_SEED: &[u8] = b"data_account_tree";
+pub const MAX_TREE_DEPTH: u8 = 24;
+pub const EMPTY_LEAF: [u8; 32] = [0; 32];
+
+/// Size of a metadata tree account of the given depth
+pub fn tree_size(max_depth: u8) -> usize {
+    32 + 1 + 8 + 32 + 4 + 32 * max_depth as usize
+}
+
+/// Hashes the metadata of a compressed data account into a leaf
+pub fn metadata_leaf(
+    data_account: &Pubkey,
+    metadata: &DataAccountMetadata,
+) -> Result<[u8; 32], std::io::Error> {
+    Ok(hashv(&[data_account.as_ref(), &metadata.try_to_vec()?]).to_bytes())
+}
+
+/// Hashes a pair of nodes in positional order so a proof is bound to its leaf index
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[1], left, right]).to_bytes()
+}
+
+/// Shared merkle tree of the metadata of an operator's compressed data accounts
+/// Leaves are appended on-chain via the filled subtrees and updated via proofs against the latest root
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct MetadataTree {
+    pub operator: Pubkey,
+    pub max_depth: u8,
+    pub num_leaves: u64,
+    pub root: [u8; 32],
+    /// latest left node at every level used to append the next leaf
+    pub filled_subtrees: Vec<[u8; 32]>,
+}
+
+impl MetadataTree {
+    pub fn new(operator: Pubkey, max_depth: u8) -> Self {
+        let zeros = zero_hashes(max_depth);
+        MetadataTree {
+            operator,
+            max_depth,
+            num_leaves: 0,
+            root: zeros[max_depth as usize],
+            filled_subtrees: zeros[..max_depth as usize].to_vec(),
+        }
+    }
+
+    /// Appends the leaf and returns its index, or None if the tree is full
+    pub fn append(&mut self, leaf: [u8; 32]) -> Option<u64> {
+        let index = self.num_leaves;
+        if index >= 1u64 << self.max_depth {
+            return None;
+        }
+        let zeros = zero_hashes(self.max_depth);
+        let mut node = leaf;
+        for (level, (filled, zero)) in self.filled_subtrees.iter_mut().zip(&zeros).enumerate() {
+            node = if (index >> level) & 1 == 0 {
+                *filled = node;
+                node_hash(&node, zero)
+            } else {
+                node_hash(filled, &node)
+            };
+        }
+        self.root = node;
+        self.num_leaves += 1;
+        Some(index)
+    }
+
+    /// Verifies the proof of the leaf at the index against the latest root and replaces the leaf
+    pub fn replace(
+        &mut self,
+        index: u64,
+        old_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        proof: &[[u8; 32]],
+    ) -> bool {
+        if index >= self.num_leaves || proof.len() != self.max_depth as usize {
+            return false;
+        }
+        let path = |leaf: [u8; 32]| {
+            proof
+                .iter()
+                .enumerate()
+                .scan(leaf, |node, (level, sibling)| {
+                    *node = if (index >> level) & 1 == 0 {
+                        node_hash(node, sibling)
+                    } else {
+                        node_hash(sibling, node)
+                    };
+                    Some(*node)
+                })
+                .collect::<Vec<_>>()
+        };
+        if path(old_leaf).last() != Some(&self.root) {
+            return false;
+        }
+
+        // keep the filled subtrees in sync if the new path passes through them
+        let new_path = path(new_leaf);
+        for level in 0..self.max_depth as usize {
+            let node = if level == 0 {
+                new_leaf
+            } else {
+                new_path[level - 1]
+            };
+            let next = self.num_leaves >> level;
+            if next & 1 == 1 && index >> level == next ^ 1 {
+                self.filled_subtrees[level] = node;
+            }
+        }
+        self.root = new_path[self.max_depth as usize - 1];
+        true
+    }
+}
+
+/// Hashes of empty subtrees at every level
+fn zero_hashes(max_depth: u8) -> Vec<[u8; 32]> {
+    let mut zeros = vec![EMPTY_LEAF];
+    for level in 0..max_depth as usize {
+        zeros.push(node_hash(&zeros[level], &zeros[level]));
+    }
+    zeros
+}
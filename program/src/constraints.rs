@@ -0,0 +1,75 @@
+//! Account constraints shared by all instruction handlers
+//! A failed constraint logs the offending account (regardless of the log level) before returning its error
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::DataAccountError;
+
+/// Ensures every account signed the transaction
+pub fn require_signer(accounts: &[&AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        if !account.is_signer {
+            msg!("account {} should be a signer", account.key);
+            return Err(DataAccountError::NotSigner.into());
+        }
+    }
+    Ok(())
+}
+
+/// Ensures every account is writable
+pub fn require_writable(accounts: &[&AccountInfo]) -> ProgramResult {
+    for account in accounts {
+        if !account.is_writable {
+            msg!("account {} should be writable", account.key);
+            return Err(DataAccountError::NotWriteable.into());
+        }
+    }
+    Ok(())
+}
+
+/// Ensures the account is owned by the owner program
+pub fn require_owner(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        msg!(
+            "account {} should be owned by {} but is owned by {}",
+            account.key,
+            owner,
+            account.owner
+        );
+        return Err(DataAccountError::InvalidOwner.into());
+    }
+    Ok(())
+}
+
+/// Ensures the account is the canonical pda of the seeds and returns its bump seed
+pub fn require_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (pda, bump_seed) = Pubkey::find_program_address(seeds, program_id);
+    if pda != *account.key {
+        msg!("account {} should be the pda {}", account.key, pda);
+        return Err(DataAccountError::InvalidPDA.into());
+    }
+    Ok(bump_seed)
+}
+
+/// Ensures the account is the pda of the seeds including a known bump seed
+/// Cheaper than require_pda as the bump seed does not have to be searched for
+pub fn require_pda_with_bump(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let pda = Pubkey::create_program_address(seeds, program_id)
+        .map_err(|_| ProgramError::from(DataAccountError::InvalidPDA))?;
+    if pda != *account.key {
+        msg!("account {} should be the pda {}", account.key, pda);
+        return Err(DataAccountError::InvalidPDA.into());
+    }
+    Ok(())
+}
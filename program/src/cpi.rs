@@ -0,0 +1,34 @@
+//! Helpers for other on-chain programs that consume data accounts passed to them
+//! Build the data program with the `no-entrypoint` feature to depend on it
+
+use std::cell::Ref;
+
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    constraints::require_owner, error::DataAccountError, processor::Processor, state::DATA_VERSION,
+};
+
+/// Verifies the data_account and metadata_account and borrows the content of the data_account without copying it
+/// Ensures both accounts are owned by the data program, the metadata_account is the pda of the data_account,
+/// the data_account is initialized, not archived and of the current DATA_VERSION
+/// The slice ends at the content_len of the metadata (the content is returned as stored, i.e., possibly compressed)
+pub fn verify_and_read<'a>(
+    data_account: &'a AccountInfo,
+    metadata_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<Ref<'a, [u8]>, ProgramError> {
+    // ensure data_account is owned by the data program
+    require_owner(data_account, program_id)?;
+
+    let account_metadata = Processor::load_metadata(program_id, data_account, metadata_account)?;
+
+    // ensure data_account has the expected data_version
+    if account_metadata.version() != DATA_VERSION {
+        return Err(DataAccountError::InvalidVersion.into());
+    }
+
+    let data = data_account.try_borrow_data()?;
+    let content_len = (account_metadata.content_len() as usize).min(data.len());
+    Ok(Ref::map(data, |data| &data[..content_len]))
+}
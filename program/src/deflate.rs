@@ -0,0 +1,39 @@
+//! On-chain expansion of deflate-compressed payloads
+//! The output is inflated into a single buffer of the caller's bounded size so the heap use is known upfront
+
+use miniz_oxide::inflate::{
+    core::{
+        decompress, inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF, DecompressorOxide,
+    },
+    TINFLStatus,
+};
+
+use crate::error::DataAccountError;
+
+/// Maximum number of bytes a payload can expand to in one instruction
+/// The output and the decompressor state (~11 KiB) must fit in the 32 KiB heap along with the instruction data
+pub const MAX_DECOMPRESSED_LEN: usize = 10 * 1024;
+
+/// Inflates the raw deflate (no zlib header) data into at most max_len bytes
+/// The data must be a complete deflate stream with no trailing bytes
+pub fn inflate(data: &[u8], max_len: usize) -> Result<Vec<u8>, DataAccountError> {
+    if max_len > MAX_DECOMPRESSED_LEN {
+        return Err(DataAccountError::InvalidCompressedData);
+    }
+
+    let mut decompressor = Box::<DecompressorOxide>::default();
+    let mut output = vec![0; max_len];
+    let (status, consumed, len) = decompress(
+        &mut decompressor,
+        data,
+        &mut output,
+        0,
+        TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF,
+    );
+    if status != TINFLStatus::Done || consumed != data.len() {
+        return Err(DataAccountError::InvalidCompressedData);
+    }
+
+    output.truncate(len);
+    Ok(output)
+}
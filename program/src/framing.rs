@@ -0,0 +1,62 @@
+//! Length-prefixed record framing of the content of a data account
+//! Each record is its length as a little-endian u32 followed by its bytes
+//! Shared by AppendFramedRecord and off-chain readers (mirrored in js/src/framing.ts)
+
+pub const RECORD_PREFIX_SIZE: usize = 4;
+
+/// Size of a framed record of the given length
+pub fn framed_len(record_len: usize) -> usize {
+    RECORD_PREFIX_SIZE + record_len
+}
+
+/// Writes the framed record at the start of buf and returns the number of bytes written
+/// Returns None if buf is too short or the record is too long to frame
+pub fn write_record(buf: &mut [u8], record: &[u8]) -> Option<usize> {
+    let len = u32::try_from(record.len()).ok()?;
+    let end = framed_len(record.len());
+    if buf.len() < end {
+        return None;
+    }
+    buf[..RECORD_PREFIX_SIZE].copy_from_slice(&len.to_le_bytes());
+    buf[RECORD_PREFIX_SIZE..end].copy_from_slice(record);
+    Some(end)
+}
+
+/// Iterator over the framed records of a content
+/// Stops at the first incomplete record, which is left in remainder
+pub struct Records<'a> {
+    remainder: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+    /// Get the bytes that have not been iterated over (non-empty at the end only if the framing is broken)
+    pub fn remainder(&self) -> &'a [u8] {
+        self.remainder
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = self.remainder.get(..RECORD_PREFIX_SIZE)?;
+        let len = u32::from_le_bytes(prefix.try_into().ok()?) as usize;
+        let record = self
+            .remainder
+            .get(RECORD_PREFIX_SIZE..RECORD_PREFIX_SIZE.checked_add(len)?)?;
+        self.remainder = &self.remainder[framed_len(len)..];
+        Some(record)
+    }
+}
+
+/// Iterates over the framed records of the content
+pub fn records(content: &[u8]) -> Records {
+    Records { remainder: content }
+}
+
+/// Verifies that the content is a sequence of complete framed records
+pub fn is_valid_framing(content: &[u8]) -> bool {
+    let mut records = records(content);
+    records.by_ref().for_each(drop);
+    records.remainder().is_empty()
+}
@@ -0,0 +1,24 @@
+use solana_program::{pubkey, pubkey::Pubkey};
+
+pub const SPL_GOVERNANCE_PROGRAM_ID: Pubkey =
+    pubkey!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+pub const NATIVE_TREASURY_SEED: &[u8] = b"native-treasury";
+
+/// Get the native treasury pda of the governance account
+pub fn native_treasury_address(governance: &Pubkey, governance_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[NATIVE_TREASURY_SEED, governance.as_ref()],
+        governance_program,
+    )
+    .0
+}
+
+/// Verifies that the authority can only sign through a proposal executed by the governance
+/// i.e., the authority is the governance account itself or its native treasury
+pub fn is_governance_authority(
+    authority: &Pubkey,
+    governance: &Pubkey,
+    governance_program: &Pubkey,
+) -> bool {
+    authority == governance || *authority == native_treasury_address(governance, governance_program)
+}
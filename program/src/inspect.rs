@@ -0,0 +1,951 @@
+//! Instruction parsing for transaction inspection tooling (e.g., explorers and wallets)
+//! The account roles mirror the shank annotations of DataAccountInstruction
+
+use std::fmt;
+
+use borsh::BorshDeserialize;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::instruction::DataAccountInstruction;
+
+/// Role of an account expected by an instruction
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct AccountRole {
+    pub name: &'static str,
+    pub desc: &'static str,
+    pub signer: bool,
+    pub writable: bool,
+    /// optional accounts are only passed in some cases (e.g., a co-signing user)
+    pub optional: bool,
+}
+
+impl AccountRole {
+    const fn new(
+        name: &'static str,
+        desc: &'static str,
+        signer: bool,
+        writable: bool,
+        optional: bool,
+    ) -> Self {
+        AccountRole {
+            name,
+            desc,
+            signer,
+            writable,
+            optional,
+        }
+    }
+}
+
+/// Name and account roles of an instruction
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct InstructionInfo {
+    pub name: &'static str,
+    pub accounts: &'static [AccountRole],
+}
+
+/// Every instruction of the Data program indexed by its discriminant (the first byte of the instruction data)
+pub const INSTRUCTIONS: &[InstructionInfo] = &[
+    InstructionInfo {
+        name: "InitializeDataAccount",
+        accounts: &[
+            AccountRole::new("feepayer", "Feepayer account", true, true, false),
+            AccountRole::new("data", "Data account data", true, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+            AccountRole::new(
+                "session",
+                "Session pda of the signer if it is a session key",
+                false,
+                false,
+                true,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateDataAccountAuthority",
+        accounts: &[
+            AccountRole::new("old_authority", "Old Authority", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("new_authority", "New Authority", true, false, false),
+            AccountRole::new("user", "User of the data account", true, false, true),
+        ],
+    },
+    InstructionInfo {
+        name: "CloseDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new(
+                "rent_receiver",
+                "Account refunded the lamports",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("user", "User of the data account", true, false, true),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeUserDataAccount",
+        accounts: &[
+            AccountRole::new("feepayer", "Feepayer account", true, true, false),
+            AccountRole::new("app_authority", "App Authority", true, false, false),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetAllowlistRoot",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "VerifyAllowlistInclusion",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SnapshotPriceFeed",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("feed", "Price feed account", false, false, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "StageUpdate",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("staging", "Data account staging pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+            AccountRole::new(
+                "session",
+                "Session pda of the signer if it is a session key",
+                false,
+                false,
+                true,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "FlushStaging",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("staging", "Data account staging pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetHotRegion",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ArchiveDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("user", "User of the data account", true, false, true),
+        ],
+    },
+    InstructionInfo {
+        name: "RestoreFromArchive",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "TagRevision",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("tags", "Data account tags pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetGovernance",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetAutomation",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "AutomatedUpdate",
+        accounts: &[
+            AccountRole::new("delegate", "Automation delegate", true, false, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ValidateDataAccount",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetDeadManSwitch",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ClaimDormantAuthority",
+        accounts: &[
+            AccountRole::new("successor", "Successor account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetDropBox",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "PostToDropBox",
+        accounts: &[
+            AccountRole::new("sender", "Sender account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("sender_pda", "Drop box sender pda", false, true, false),
+            AccountRole::new(
+                "authority",
+                "Authority account receiving the fee",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "Donate",
+        accounts: &[
+            AccountRole::new("donor", "Donor account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new(
+                "donations",
+                "Data account donations pda",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SealContent",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "RevealContent",
+        accounts: &[
+            AccountRole::new("revealer", "Revealer account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CommitValue",
+        accounts: &[
+            AccountRole::new("committer", "Committer account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("commitment", "Commitment pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "RevealValue",
+        accounts: &[
+            AccountRole::new("committer", "Committer account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("commitment", "Commitment pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ExpireCommitment",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("commitment", "Commitment pda", false, true, false),
+            AccountRole::new("authority", "Authority account", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetLicense",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeMetadataTree",
+        accounts: &[
+            AccountRole::new("operator", "Operator account", true, true, false),
+            AccountRole::new("tree", "Operator metadata tree pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeCompressedDataAccount",
+        accounts: &[
+            AccountRole::new("operator", "Operator account", true, true, false),
+            AccountRole::new("data", "Data account data", true, true, false),
+            AccountRole::new("tree", "Operator metadata tree pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateCompressedDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("tree", "Operator metadata tree pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CloseCompressedDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("tree", "Operator metadata tree pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CreateSession",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("session", "Data account session pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "RevokeSession",
+        accounts: &[
+            AccountRole::new(
+                "signer",
+                "Authority, session key or anyone once expired",
+                true,
+                false,
+                false,
+            ),
+            AccountRole::new("authority", "Authority account", false, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("session", "Data account session pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeCrdt",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ApplyDelta",
+        accounts: &[
+            AccountRole::new("writer", "Authority or session key", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+            AccountRole::new(
+                "session",
+                "Session pda of the signer if it is a session key",
+                false,
+                false,
+                true,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "LockClose",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UnlockClose",
+        accounts: &[
+            AccountRole::new("authority", "Authority or user account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "FinalizeDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "AppendFramedRecord",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "BeginUpload",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "AppendChunk",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CommitUpload",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "VerifyDataAccount",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeNftDataAccount",
+        accounts: &[
+            AccountRole::new("holder", "Holder of the NFT", true, true, false),
+            AccountRole::new(
+                "token_account",
+                "Token account of the holder",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("mint", "NFT mint", false, false, false),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateNftDataAccount",
+        accounts: &[
+            AccountRole::new("holder", "Holder of the NFT", true, true, false),
+            AccountRole::new(
+                "token_account",
+                "Token account of the holder",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "AddWriter",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "RemoveWriter",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeCnftDataAccount",
+        accounts: &[
+            AccountRole::new("owner", "Owner of the cNFT", true, true, false),
+            AccountRole::new(
+                "merkle_tree",
+                "Merkle tree of the cNFT",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new(
+                "compression_program",
+                "Account compression program",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateCnftDataAccount",
+        accounts: &[
+            AccountRole::new("owner", "Owner of the cNFT", true, true, false),
+            AccountRole::new(
+                "merkle_tree",
+                "Merkle tree of the cNFT",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new(
+                "compression_program",
+                "Account compression program",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetExpiry",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ExpireDataAccount",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new(
+                "rent_receiver",
+                "Rent receiver of the expiry",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("user", "User of the data account", true, false, true),
+        ],
+    },
+    InstructionInfo {
+        name: "SetNotificationHint",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ResizeDataAccount",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "UpdateDataAccountCompressed",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+            AccountRole::new(
+                "session",
+                "Session pda of the signer if it is a session key",
+                false,
+                false,
+                true,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "InitializeDataAccountPda",
+        accounts: &[
+            AccountRole::new("feepayer", "Feepayer account", true, true, false),
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ScrubRange",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "MigrateMetadata",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "Attest",
+        accounts: &[
+            AccountRole::new(
+                "attester",
+                "Authority or automation delegate account",
+                true,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetFreezeAuthority",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "FreezeDataAccount",
+        accounts: &[
+            AccountRole::new(
+                "freeze_authority",
+                "Freeze authority account",
+                true,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "ThawDataAccount",
+        accounts: &[
+            AccountRole::new(
+                "freeze_authority",
+                "Freeze authority account",
+                true,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CloneDataAccount",
+        accounts: &[
+            AccountRole::new("feepayer", "Feepayer account", true, true, false),
+            AccountRole::new(
+                "source_data",
+                "Source data account data",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("source_pda", "Source data account pda", false, false, false),
+            AccountRole::new("data", "Target data account data", true, true, false),
+            AccountRole::new("pda", "Target data account pda", false, true, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "SetCompressionDictionary",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new(
+                "dictionary_data",
+                "Dictionary data account data (when setting)",
+                false,
+                false,
+                true,
+            ),
+            AccountRole::new(
+                "dictionary_pda",
+                "Dictionary data account pda (when setting)",
+                false,
+                false,
+                true,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "VerifyDataStep",
+        accounts: &[
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "TopUpRent",
+        accounts: &[
+            AccountRole::new("payer", "Payer of the top-up", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "WriteStaged",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, false, false),
+            AccountRole::new(
+                "staged_buffer",
+                "Data account staged buffer pda",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CommitStaged",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, true, false),
+            AccountRole::new("data", "Data account data", false, true, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+            AccountRole::new(
+                "staged_buffer",
+                "Data account staged buffer pda",
+                false,
+                true,
+                false,
+            ),
+            AccountRole::new("system_program", "System program", false, false, false),
+        ],
+    },
+    InstructionInfo {
+        name: "DelegateWriteAuthority",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "RevokeDelegate",
+        accounts: &[
+            AccountRole::new("authority", "Authority account", true, false, false),
+            AccountRole::new("data", "Data account data", false, false, false),
+            AccountRole::new("pda", "Data account pda", false, true, false),
+        ],
+    },
+    InstructionInfo {
+        name: "CloseNftDataAccount",
+        accounts: &[
+            AccountRole::new("holder", "Holder of the NFT", true, false, false),
+            AccountRole::new(
+                "token_account",
+                "Token account of the holder",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new(
+                "rent_receiver",
+                "Account refunded the lamports",
+                false,
+                true,
+                false,
+            ),
+        ],
+    },
+    InstructionInfo {
+        name: "CloseCnftDataAccount",
+        accounts: &[
+            AccountRole::new("owner", "Owner of the cNFT", true, false, false),
+            AccountRole::new(
+                "merkle_tree",
+                "Merkle tree of the cNFT",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new(
+                "compression_program",
+                "Account compression program",
+                false,
+                false,
+                false,
+            ),
+            AccountRole::new("data", "Data account pda", false, true, false),
+            AccountRole::new("pda", "Data account metadata pda", false, true, false),
+            AccountRole::new(
+                "rent_receiver",
+                "Account refunded the lamports",
+                false,
+                true,
+                false,
+            ),
+        ],
+    },
+];
+
+/// Get the discriminant of the instruction with the given name
+pub fn discriminant(name: &str) -> Option<u8> {
+    INSTRUCTIONS
+        .iter()
+        .position(|info| info.name == name)
+        .map(|index| index as u8)
+}
+
+/// Instruction of the Data program decoded from its instruction data
+#[derive(Clone)]
+pub struct ParsedDataInstruction {
+    pub discriminant: u8,
+    pub instruction: DataAccountInstruction,
+}
+
+impl ParsedDataInstruction {
+    /// Get the name of the instruction
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+    /// Get the roles of the accounts expected by the instruction in order
+    pub fn account_roles(&self) -> &'static [AccountRole] {
+        self.info().accounts
+    }
+    /// Pairs the accounts of a transaction instruction with their roles
+    /// Trailing accounts beyond the expected ones (e.g., optional co-signers) are left unlabeled
+    pub fn label_accounts<'a>(
+        &self,
+        accounts: &'a [Pubkey],
+    ) -> Vec<(Option<&'static AccountRole>, &'a Pubkey)> {
+        let roles = self.account_roles();
+        accounts
+            .iter()
+            .enumerate()
+            .map(|(index, account)| (roles.get(index), account))
+            .collect()
+    }
+    fn info(&self) -> &'static InstructionInfo {
+        &INSTRUCTIONS[self.discriminant as usize]
+    }
+}
+
+impl fmt::Display for ParsedDataInstruction {
+    /// Formats the instruction as its name followed by its account roles
+    /// e.g., SetLicense(authority: signer, data, pda: writable)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name())?;
+        for (index, role) in self.account_roles().iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", role.name)?;
+            let flags: Vec<&str> = [
+                (role.signer, "signer"),
+                (role.writable, "writable"),
+                (role.optional, "optional"),
+            ]
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, flag)| *flag)
+            .collect();
+            if !flags.is_empty() {
+                write!(f, ": {}", flags.join(" "))?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Decodes the instruction data of a Data program instruction
+pub fn parse_instruction(data: &[u8]) -> Result<ParsedDataInstruction, ProgramError> {
+    let instruction = DataAccountInstruction::try_from_slice(data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok(ParsedDataInstruction {
+        discriminant: data[0],
+        instruction,
+    })
+}
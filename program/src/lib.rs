@@ -1,5 +1,19 @@
+pub mod cnft;
+pub mod commit_reveal;
+pub mod compressed;
+pub mod constraints;
+pub mod cpi;
+pub mod deflate;
 pub mod entrypoint;
-pub mod error;
-pub mod instruction;
+pub mod framing;
+pub mod governance;
+pub mod inspect;
+pub mod nft;
+pub mod oracle;
 pub mod processor;
-pub mod state;
+pub mod test_vectors;
+
+pub use solana_data_account_interface::{
+    checksum, compute, crdt, error, events, instruction, manifest, merkle, sha256, state,
+    validation,
+};
@@ -0,0 +1,30 @@
+//! Data accounts bound to an NFT mint whose content is written by whoever currently holds the NFT
+//! The data account is the pda derived from the mint and its authority is the data account itself,
+//! so it can only be written through the NFT instructions which verify the holder via their token account
+
+use solana_program::{account_info::AccountInfo, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::{Account, Mint};
+
+pub use solana_data_account_interface::nft::*;
+
+/// Verifies that the mint account is an NFT i.e., an SPL token mint with no decimals and a supply of 1
+pub fn is_nft_mint(mint: &AccountInfo) -> bool {
+    if *mint.owner != spl_token::id() {
+        return false;
+    }
+    match Mint::unpack(&mint.data.borrow()) {
+        Ok(mint) => mint.decimals == 0 && mint.supply == 1,
+        Err(_) => false,
+    }
+}
+
+/// Verifies that the token account of the holder holds the token of the mint
+pub fn is_token_holder(token_account: &AccountInfo, mint: &Pubkey, holder: &Pubkey) -> bool {
+    if *token_account.owner != spl_token::id() {
+        return false;
+    }
+    match Account::unpack(&token_account.data.borrow()) {
+        Ok(account) => account.mint == *mint && account.owner == *holder && account.amount > 0,
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,23 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{clock::UnixTimestamp, pubkey, pubkey::Pubkey};
+
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+pub const PYTH_DEVNET_PROGRAM_ID: Pubkey = pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+pub const SWITCHBOARD_V2_PROGRAM_ID: Pubkey =
+    pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+pub const SNAPSHOT_HEADER_SIZE: usize = 8 + 8 + 32;
+
+/// Header written in front of every snapshot of a price feed
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct SnapshotHeader {
+    pub slot: u64,
+    pub unix_timestamp: UnixTimestamp,
+    pub feed: Pubkey,
+}
+
+/// Verifies that the feed account is owned by a supported oracle program
+pub fn is_supported_feed_owner(owner: &Pubkey) -> bool {
+    *owner == PYTH_PROGRAM_ID
+        || *owner == PYTH_DEVNET_PROGRAM_ID
+        || *owner == SWITCHBOARD_V2_PROGRAM_ID
+}
@@ -1,24 +1,81 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint::ProgramResult,
+    borsh::try_from_slice_unchecked,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    hash::{hash, hashv},
+    keccak,
+    log::sol_log_data,
     msg,
-    program::{invoke, invoke_signed},
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
-    pubkey::Pubkey,
+    pubkey::{Pubkey, MAX_SEED_LEN},
     system_instruction,
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 
 use crate::{
+    checksum::{crc32, crc32_update},
+    cnft::{cnft_asset_id, verify_owner, CNFT_SEED},
+    commit_reveal::{commitment_hash, Commitment, COMMITMENT_SEED, COMMITMENT_SIZE},
+    compressed::{metadata_leaf, tree_size, MetadataTree, EMPTY_LEAF, MAX_TREE_DEPTH, TREE_SEED},
+    compute::{check_compute, checksum_cost, copy_cost, hash_cost, software_hash_cost},
+    constraints::{
+        require_owner, require_pda, require_pda_with_bump, require_signer, require_writable,
+    },
+    crdt::CrdtState,
+    deflate::inflate,
     error::DataAccountError,
+    events::DataAccountEvent,
+    framing::{framed_len, write_record},
+    governance::is_governance_authority,
     instruction::DataAccountInstruction,
+    merkle::{verify_proof, ROOT_SIZE},
+    nft::{is_nft_mint, is_token_holder, NFT_SEED},
+    oracle::{is_supported_feed_owner, SnapshotHeader, SNAPSHOT_HEADER_SIZE},
+    sha256::{sha256_finalize, sha256_update, BLOCK_LEN, INITIAL_STATE},
     state::{
-        DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption,
-        DATA_VERSION, METADATA_SIZE, PDA_SEED,
+        AddWriterArgs, AppendChunkArgs, AppendFramedRecordArgs, ApplyDeltaArgs,
+        ArchiveDataAccountArgs, ArchiveInfo, AttestArgs, Attestation, AutomatedUpdateArgs,
+        AutomationConfig, BeginUploadArgs, ClaimDormantAuthorityArgs, CloneDataAccountArgs,
+        CloseCnftDataAccountArgs, CloseCompressedDataAccountArgs, CloseLock,
+        CloseNftDataAccountArgs, CommitStagedArgs, CommitUploadArgs, CommitValueArgs,
+        CompressionOption, CreateSessionArgs, DataAccountMetadata, DataStatusOption,
+        DataTypeOption, DeadManSwitch, DelegateWriteAuthorityArgs, DonateArgs, DonationsTable,
+        DropBoxMessage, ExpireCommitmentArgs, ExpireDataAccountArgs, FinalizeDataAccountArgs,
+        FlushStagingArgs, FreezeDataAccountArgs, InitializeCnftDataAccountArgs,
+        InitializeCompressedDataAccountArgs, InitializeCrdtArgs, InitializeDataAccountPdaArgs,
+        InitializeMetadataTreeArgs, InitializeNftDataAccountArgs, InitializeUserDataAccountArgs,
+        LockCloseArgs, LogLevel, MigrateMetadataArgs, PostToDropBoxArgs, RemoveWriterArgs,
+        ResizeDataAccountArgs, RestoreFromArchiveArgs, RevealContentArgs, RevealValueArgs,
+        RevisionTag, RevokeDelegateArgs, RevokeSessionArgs, ScrubFillOption, ScrubRangeArgs,
+        SealContentArgs, SerializationStatusOption, Session, SessionInstructionOption,
+        SetAllowlistRootArgs, SetAutomationArgs, SetCompressionDictionaryArgs,
+        SetDeadManSwitchArgs, SetDropBoxArgs, SetExpiryArgs, SetFreezeAuthorityArgs,
+        SetGovernanceArgs, SetHotRegionArgs, SetLicenseArgs, SetNotificationHintArgs,
+        SnapshotPriceFeedArgs, StageUpdateArgs, StagedWrite, TagRevisionArgs, ThawDataAccountArgs,
+        TimeLock, TopUpRentArgs, UnlockCloseArgs, UpdateCnftDataAccountArgs,
+        UpdateCompressedDataAccountArgs, UpdateDataAccountCompressedArgs, UpdateNftDataAccountArgs,
+        UpdateSegment, UploadProgress, ValidateDataAccountArgs, VerificationCursor,
+        VerifyAllowlistInclusionArgs, VerifyDataAccountArgs, VerifyDataStepArgs, VersionedMetadata,
+        WriteDelegate, WriteStagedArgs, WriterSequence, ARCHIVE_POINTER_MAX_LEN, DATA_PDA_SEED,
+        DATA_VERSION, DONATIONS_SEED, DONATIONS_SIZE, DROP_BOX_SEED, HOT_REGION_MAX_END,
+        MAX_SEQUENCED_WRITERS, MAX_TAGS, MAX_WRITERS, METADATA_SIZE, PDA_SEED, SESSION_SEED,
+        STAGED_BUFFER_SEED, STAGING_SEED, TAGS_SEED, TAG_LABEL_MAX_LEN,
     },
+    validation::{validate_content, validation_cost},
 };
 
+/// Logs a structured `event key=value ...` line if verbose logs are enabled
+/// The values are only evaluated and formatted when the line is logged
+macro_rules! log_kv {
+    ($level:expr, $event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $level.verbose() {
+            msg!(concat!($event $(, " ", stringify!($key), "={}")*) $(, $value)*);
+        }
+    };
+}
+
 pub struct Processor {}
 
 impl Processor {
@@ -32,7 +89,7 @@ impl Processor {
 
         match instruction {
             DataAccountInstruction::InitializeDataAccount(args) => {
-                if args.debug {
+                if args.debug.info() {
                     msg!("InitializeDataAccount");
                 }
 
@@ -42,6 +99,34 @@ impl Processor {
                 let metadata_account = next_account_info(accounts_iter)?;
                 let system_program = next_account_info(accounts_iter)?;
 
+                // an idempotent initialize of a data_account already initialized with the same authority is a
+                // no-op so that deployment scripts can be rerun
+                if args.idempotent && !metadata_account.data_is_empty() {
+                    require_owner(data_account, program_id)?;
+
+                    let account_metadata = Self::read_metadata(program_id, metadata_account)?;
+                    require_pda_with_bump(
+                        metadata_account,
+                        &[
+                            PDA_SEED,
+                            data_account.key.as_ref(),
+                            &[account_metadata.bump_seed()],
+                        ],
+                        program_id,
+                    )?;
+                    if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED
+                        || *account_metadata.authority() != args.authority
+                    {
+                        return Err(DataAccountError::AlreadyInitialized.into());
+                    }
+
+                    if args.debug.info() {
+                        msg!("data account already initialized");
+                    }
+
+                    return Ok(());
+                }
+
                 // create a data_account of given space if not done so already
                 if !args.is_created {
                     let space = args.space as usize;
@@ -63,56 +148,43 @@ impl Processor {
                         ],
                     )?;
 
-                    if args.debug {
+                    if args.debug.info() {
                         msg!("account of space: {} created", space);
                     }
+                    log_kv!(
+                        args.debug,
+                        "create",
+                        data_account = data_account.key,
+                        space = space,
+                        rent = rent_exemption_amount,
+                    );
                 }
                 // else set data program as the owner of the data_account
                 else {
                     let assign_ix = system_instruction::assign(&data_account.key, &program_id);
                     invoke(&assign_ix, &[data_account.clone(), system_program.clone()])?;
 
-                    if args.debug {
+                    if args.debug.info() {
                         msg!("account owner updated");
                     }
                 }
                 data_account.data.borrow_mut().fill(0);
 
                 // create data_account pda to store metadata
-                let (pda, bump_seed) = Pubkey::find_program_address(
-                    &[PDA_SEED, data_account.key.as_ref()],
+                let bump_seed = Self::create_metadata_account(
                     program_id,
-                );
-                // ensure the pda is valid
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
-                // create pda account
-                let rent_exemption_amount = Rent::get()?.minimum_balance(METADATA_SIZE);
-                let create_pda_ix = system_instruction::create_account(
-                    &feepayer.key,
-                    &metadata_account.key,
-                    rent_exemption_amount,
-                    METADATA_SIZE as u64,
-                    &program_id,
-                );
-                invoke_signed(
-                    &create_pda_ix,
-                    &[
-                        feepayer.clone(),
-                        data_account.clone(),
-                        metadata_account.clone(),
-                        system_program.clone(),
-                    ],
-                    &[&[PDA_SEED, data_account.key.as_ref(), &[bump_seed]]],
+                    feepayer,
+                    data_account,
+                    metadata_account,
+                    system_program,
                 )?;
 
-                if args.debug {
+                if args.debug.info() {
                     msg!("metadata pda created");
                 }
 
                 // create initial state for data_account metadata and write to it
-                let account_metadata = DataAccountMetadata::new(
+                let mut account_metadata = DataAccountMetadata::new(
                     DataStatusOption::INITIALIZED,
                     SerializationStatusOption::UNVERIFIED,
                     args.authority,
@@ -121,12 +193,228 @@ impl Processor {
                     DataTypeOption::CUSTOM,
                     bump_seed,
                 );
+                account_metadata.set_growth_policy(args.growth_policy);
+                account_metadata.set_append_only(args.append_only);
                 account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
 
+                DataAccountEvent::Initialized {
+                    data_account: *data_account.key,
+                    authority: args.authority,
+                    space: data_account.data_len() as u64,
+                }
+                .emit()?;
+
                 Ok(())
             }
+            DataAccountInstruction::InitializeUserDataAccount(args) => {
+                Self::process_initialize_user_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetAllowlistRoot(args) => {
+                Self::process_set_allowlist_root(program_id, accounts, args)
+            }
+            DataAccountInstruction::VerifyAllowlistInclusion(args) => {
+                Self::process_verify_allowlist_inclusion(program_id, accounts, args)
+            }
+            DataAccountInstruction::SnapshotPriceFeed(args) => {
+                Self::process_snapshot_price_feed(program_id, accounts, args)
+            }
+            DataAccountInstruction::StageUpdate(args) => {
+                Self::process_stage_update(program_id, accounts, args)
+            }
+            DataAccountInstruction::FlushStaging(args) => {
+                Self::process_flush_staging(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetHotRegion(args) => {
+                Self::process_set_hot_region(program_id, accounts, args)
+            }
+            DataAccountInstruction::ArchiveDataAccount(args) => {
+                Self::process_archive_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::RestoreFromArchive(args) => {
+                Self::process_restore_from_archive(program_id, accounts, args)
+            }
+            DataAccountInstruction::TagRevision(args) => {
+                Self::process_tag_revision(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetGovernance(args) => {
+                Self::process_set_governance(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetAutomation(args) => {
+                Self::process_set_automation(program_id, accounts, args)
+            }
+            DataAccountInstruction::AutomatedUpdate(args) => {
+                Self::process_automated_update(program_id, accounts, args)
+            }
+            DataAccountInstruction::ValidateDataAccount(args) => {
+                Self::process_validate_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetDeadManSwitch(args) => {
+                Self::process_set_dead_man_switch(program_id, accounts, args)
+            }
+            DataAccountInstruction::ClaimDormantAuthority(args) => {
+                Self::process_claim_dormant_authority(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetDropBox(args) => {
+                Self::process_set_drop_box(program_id, accounts, args)
+            }
+            DataAccountInstruction::PostToDropBox(args) => {
+                Self::process_post_to_drop_box(program_id, accounts, args)
+            }
+            DataAccountInstruction::Donate(args) => {
+                Self::process_donate(program_id, accounts, args)
+            }
+            DataAccountInstruction::SealContent(args) => {
+                Self::process_seal_content(program_id, accounts, args)
+            }
+            DataAccountInstruction::RevealContent(args) => {
+                Self::process_reveal_content(program_id, accounts, args)
+            }
+            DataAccountInstruction::CommitValue(args) => {
+                Self::process_commit_value(program_id, accounts, args)
+            }
+            DataAccountInstruction::RevealValue(args) => {
+                Self::process_reveal_value(program_id, accounts, args)
+            }
+            DataAccountInstruction::ExpireCommitment(args) => {
+                Self::process_expire_commitment(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetLicense(args) => {
+                Self::process_set_license(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeMetadataTree(args) => {
+                Self::process_initialize_metadata_tree(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeCompressedDataAccount(args) => {
+                Self::process_initialize_compressed_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::UpdateCompressedDataAccount(args) => {
+                Self::process_update_compressed_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::CloseCompressedDataAccount(args) => {
+                Self::process_close_compressed_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::CreateSession(args) => {
+                Self::process_create_session(program_id, accounts, args)
+            }
+            DataAccountInstruction::RevokeSession(args) => {
+                Self::process_revoke_session(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeCrdt(args) => {
+                Self::process_initialize_crdt(program_id, accounts, args)
+            }
+            DataAccountInstruction::ApplyDelta(args) => {
+                Self::process_apply_delta(program_id, accounts, args)
+            }
+            DataAccountInstruction::LockClose(args) => {
+                Self::process_lock_close(program_id, accounts, args)
+            }
+            DataAccountInstruction::UnlockClose(args) => {
+                Self::process_unlock_close(program_id, accounts, args)
+            }
+            DataAccountInstruction::FinalizeDataAccount(args) => {
+                Self::process_finalize_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::AppendFramedRecord(args) => {
+                Self::process_append_framed_record(program_id, accounts, args)
+            }
+            DataAccountInstruction::BeginUpload(args) => {
+                Self::process_begin_upload(program_id, accounts, args)
+            }
+            DataAccountInstruction::AppendChunk(args) => {
+                Self::process_append_chunk(program_id, accounts, args)
+            }
+            DataAccountInstruction::CommitUpload(args) => {
+                Self::process_commit_upload(program_id, accounts, args)
+            }
+            DataAccountInstruction::VerifyDataAccount(args) => {
+                Self::process_verify_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeNftDataAccount(args) => {
+                Self::process_initialize_nft_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::UpdateNftDataAccount(args) => {
+                Self::process_update_nft_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::AddWriter(args) => {
+                Self::process_add_writer(program_id, accounts, args)
+            }
+            DataAccountInstruction::RemoveWriter(args) => {
+                Self::process_remove_writer(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeCnftDataAccount(args) => {
+                Self::process_initialize_cnft_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::UpdateCnftDataAccount(args) => {
+                Self::process_update_cnft_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetExpiry(args) => {
+                Self::process_set_expiry(program_id, accounts, args)
+            }
+            DataAccountInstruction::ExpireDataAccount(args) => {
+                Self::process_expire_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetNotificationHint(args) => {
+                Self::process_set_notification_hint(program_id, accounts, args)
+            }
+            DataAccountInstruction::ResizeDataAccount(args) => {
+                Self::process_resize_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::UpdateDataAccountCompressed(args) => {
+                Self::process_update_data_account_compressed(program_id, accounts, args)
+            }
+            DataAccountInstruction::InitializeDataAccountPda(args) => {
+                Self::process_initialize_data_account_pda(program_id, accounts, args)
+            }
+            DataAccountInstruction::ScrubRange(args) => {
+                Self::process_scrub_range(program_id, accounts, args)
+            }
+            DataAccountInstruction::MigrateMetadata(args) => {
+                Self::process_migrate_metadata(program_id, accounts, args)
+            }
+            DataAccountInstruction::Attest(args) => {
+                Self::process_attest(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetFreezeAuthority(args) => {
+                Self::process_set_freeze_authority(program_id, accounts, args)
+            }
+            DataAccountInstruction::FreezeDataAccount(args) => {
+                Self::process_freeze_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::ThawDataAccount(args) => {
+                Self::process_thaw_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::CloneDataAccount(args) => {
+                Self::process_clone_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::SetCompressionDictionary(args) => {
+                Self::process_set_compression_dictionary(program_id, accounts, args)
+            }
+            DataAccountInstruction::VerifyDataStep(args) => {
+                Self::process_verify_data_step(program_id, accounts, args)
+            }
+            DataAccountInstruction::TopUpRent(args) => {
+                Self::process_top_up_rent(program_id, accounts, args)
+            }
+            DataAccountInstruction::WriteStaged(args) => {
+                Self::process_write_staged(program_id, accounts, args)
+            }
+            DataAccountInstruction::CommitStaged(args) => {
+                Self::process_commit_staged(program_id, accounts, args)
+            }
+            DataAccountInstruction::DelegateWriteAuthority(args) => {
+                Self::process_delegate_write_authority(program_id, accounts, args)
+            }
+            DataAccountInstruction::RevokeDelegate(args) => {
+                Self::process_revoke_delegate(program_id, accounts, args)
+            }
+            DataAccountInstruction::CloseNftDataAccount(args) => {
+                Self::process_close_nft_data_account(program_id, accounts, args)
+            }
+            DataAccountInstruction::CloseCnftDataAccount(args) => {
+                Self::process_close_cnft_data_account(program_id, accounts, args)
+            }
             DataAccountInstruction::UpdateDataAccount(args) => {
-                if args.debug {
+                if args.debug.info() {
                     msg!("UpdateDataAccount");
                 }
 
@@ -137,38 +425,28 @@ impl Processor {
                 let system_program = next_account_info(accounts_iter)?;
 
                 // ensure authority is signer
-                if !authority.is_signer {
-                    return Err(DataAccountError::NotSigner.into());
-                }
+                require_signer(&[authority])?;
 
                 // ensure authority, data_account, and metadata_account are writable
-                if !authority.is_writable
-                    || !data_account.is_writable
-                    || !metadata_account.is_writable
-                {
-                    return Err(DataAccountError::NotWriteable.into());
-                }
-
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
+                require_writable(&[authority, data_account, metadata_account])?;
 
-                let mut account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+                let mut account_metadata = Self::read_metadata(program_id, metadata_account)?;
 
                 // ensure data_account is initialized
                 if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
                     return Err(DataAccountError::NotInitialized.into());
                 }
 
-                // ensure data_account is being written to by valid authority
-                if account_metadata.authority() != authority.key {
-                    return Err(DataAccountError::InvalidAuthority.into());
+                // ensure data_account is not archived
+                if *account_metadata.data_status() == DataStatusOption::ARCHIVED {
+                    return Err(DataAccountError::Archived.into());
                 }
 
+                let is_authority = account_metadata.authority() == authority.key;
+
                 // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
+                require_pda_with_bump(
+                    metadata_account,
                     &[
                         PDA_SEED,
                         data_account.key.as_ref(),
@@ -176,89 +454,267 @@ impl Processor {
                     ],
                     program_id,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
 
                 let old_len = data_account.data_len();
-                let end_len = args.offset as usize + args.data.len();
+
+                // ensure the segments are few and bounded and overlap neither each other nor the data
+                let writes = UpdateSegment::writes(args.offset, &args.data, &args.segments)
+                    .ok_or(DataAccountError::InvalidSegments)?;
+                let written_len: usize = writes.iter().map(|(_, data)| data.len()).sum();
+                let start = writes.iter().map(|(offset, _)| *offset).min().unwrap_or(0);
+                let end_len = writes
+                    .iter()
+                    .map(|(offset, data)| offset + data.len())
+                    .max()
+                    .unwrap_or(0);
+
+                // ensure data_account is being written to by its authority, one of its writers, its unexpired
+                // delegate or a session key within its scope
+                // the writes of an update with segments must all lie within a single range of the session
+                let is_writer = Self::authorize_writer(
+                    program_id,
+                    &account_metadata,
+                    authority,
+                    data_account,
+                    accounts_iter,
+                    start as u64,
+                    end_len as u64,
+                )?;
+
+                // ensure a session key neither truncates, releases space nor changes how the content is interpreted
+                if !is_writer
+                    && (args.truncate
+                        || args.realloc_down
+                        || args.data_type != *account_metadata.data_type()
+                        || args.compression != *account_metadata.compression())
+                {
+                    return Err(DataAccountError::InvalidSession.into());
+                }
+
+                // ensure the content is not sealed and the drop box region (or the content of an append-only
+                // data_account) is neither overwritten nor truncated
+                for (offset, data) in writes.iter() {
+                    Self::check_content_write(&account_metadata, *offset, offset + data.len())?;
+                }
+                if args.truncate {
+                    Self::check_content_write(
+                        &account_metadata,
+                        end_len,
+                        account_metadata.content_len() as usize,
+                    )?;
+                }
 
                 // ensure static data_account has sufficient space
                 if !account_metadata.dynamic() && old_len < end_len {
                     return Err(DataAccountError::InsufficientSpace.into());
                 }
 
-                if args.debug {
+                // ensure the content has not been updated since the writer read the expected revision
+                Self::check_expected_revision(&account_metadata, args.expected_revision)?;
+
+                // ensure a retried or reordered write of a sequenced writer cannot regress the content
+                Self::advance_writer_sequence(&mut account_metadata, authority.key, args.sequence)?;
+
+                // ensure enough compute is left to copy and audit the writes instead of aborting mid-write
+                check_compute(
+                    writes
+                        .iter()
+                        .map(|(_, data)| {
+                            copy_cost(data.len()).saturating_add(2 * hash_cost(data.len()))
+                        })
+                        .sum(),
+                )?;
+
+                if args.debug.info() {
                     msg!("account checks passed");
                 }
 
+                // the content ends at the write only if it is explicitly truncated, otherwise it can only grow
+                let old_content_len = account_metadata.content_len() as usize;
+                let content_len = if args.truncate {
+                    end_len
+                } else {
+                    old_content_len.max(end_len)
+                };
+
+                // the checksum of the content rolls over an append and is otherwise recomputed once written
+                let appended_checksum = match account_metadata.checksum() {
+                    Some(checksum)
+                        if args.offset as usize == old_content_len && writes.len() == 1 =>
+                    {
+                        Some(crc32_update(checksum, &args.data))
+                    }
+                    _ => None,
+                };
+                check_compute(checksum_cost(if appended_checksum.is_some() {
+                    args.data.len()
+                } else {
+                    content_len
+                }))?;
+
+                // the keccak256 hash of uncompressed content is computed on-chain along with a client content_hash
+                let compute_keccak =
+                    args.content_hash.is_some() && args.compression == CompressionOption::NONE;
+                if compute_keccak {
+                    check_compute(copy_cost(written_len).saturating_add(hash_cost(content_len)))?;
+                }
+
+                // the final content is validated against its data_type only if it is stored uncompressed
+                let validate = args.verify_flag && args.compression == CompressionOption::NONE;
+                if validate {
+                    check_compute(
+                        copy_cost(written_len)
+                            .saturating_add(validation_cost(&args.data_type, content_len)),
+                    )?;
+                }
+
+                // realloc_down only releases the space beyond the content
                 let new_len = if !account_metadata.dynamic() {
                     old_len
                 } else if args.realloc_down {
-                    end_len
+                    content_len
                 } else {
-                    old_len.max(end_len)
+                    account_metadata.growth_policy().grow(old_len, end_len)
                 };
 
+                // ensure the hot region is not truncated
+                if let Some(hot_region) = account_metadata.hot_region() {
+                    if new_len < hot_region.end() {
+                        return Err(DataAccountError::InvalidHotRegion.into());
+                    }
+                }
+
+                log_kv!(
+                    args.debug,
+                    "update",
+                    data_account = data_account.key,
+                    offset = args.offset,
+                    len = args.data.len(),
+                    segments = args.segments.len(),
+                    old_len = old_len,
+                    new_len = new_len,
+                    content_len = content_len,
+                    realloc = match new_len.cmp(&old_len) {
+                        std::cmp::Ordering::Greater => "grow",
+                        std::cmp::Ordering::Less => "shrink",
+                        std::cmp::Ordering::Equal => "none",
+                    },
+                );
+
                 // update the metadata_account
+                // the content_hash is computed by the client over the plaintext of the entire content
+                Self::transition_data_status(
+                    &mut account_metadata,
+                    if args.commit_flag {
+                        DataStatusOption::COMMITTED
+                    } else {
+                        DataStatusOption::UPDATED
+                    },
+                )?;
                 account_metadata.set_data_type(args.data_type);
-                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+                account_metadata.set_compression(args.compression);
+                Self::finish_content_write(
+                    &mut account_metadata,
+                    args.content_hash,
+                    content_len as u64,
+                );
+                if args.truncate {
+                    // the truncated content is no longer made of the appended records
+                    account_metadata.set_record_count(0);
+                }
+                if is_authority {
+                    account_metadata.record_activity(Clock::get()?.slot);
+                }
 
                 // ensure data_account has enough space by reallocing if needed
                 if old_len != new_len {
-                    let new_space = new_len;
-                    let new_minimum_balance = Rent::get()?.minimum_balance(new_space);
-                    let lamports_diff = if old_len < new_len {
-                        new_minimum_balance.saturating_sub(data_account.lamports())
-                    } else {
-                        data_account.lamports().saturating_sub(new_minimum_balance)
-                    };
-
-                    if old_len < new_len {
-                        let transfer_ix = system_instruction::transfer(
-                            authority.key,
-                            data_account.key,
-                            lamports_diff,
+                    let old_lamports = data_account.lamports();
+                    Self::realloc_data_account(
+                        &mut account_metadata,
+                        authority,
+                        data_account,
+                        system_program,
+                        new_len,
+                    )?;
+
+                    if args.debug.info() {
+                        msg!("realloc-ed {}", new_len);
+                    }
+                    log_kv!(
+                        args.debug,
+                        "realloc",
+                        data_account = data_account.key,
+                        new_len = new_len,
+                        rent_delta = data_account.lamports() as i128 - old_lamports as i128,
+                    );
+                }
+                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+                // update the data_account, one write at a time since none of them overlap
+                for (offset, data) in writes.iter() {
+                    let write_end = offset + data.len();
+
+                    if args.debug.verbose() {
+                        msg!(
+                            "replaced {:?} with {:?}",
+                            data,
+                            &data_account.data.borrow()[*offset..write_end]
                         );
-                        invoke(
-                            &transfer_ix,
-                            &[
-                                authority.clone(),
-                                data_account.clone(),
-                                system_program.clone(),
-                            ],
-                        )?;
-                    } else {
-                        let authority_lamports = authority.lamports();
-                        **authority.lamports.borrow_mut() = authority_lamports
-                            .checked_add(lamports_diff)
-                            .ok_or(DataAccountError::Overflow)?;
-                        **data_account.lamports.borrow_mut() = new_minimum_balance;
                     }
 
-                    data_account.realloc(new_space, false)?;
+                    Self::copy_content(
+                        data_account,
+                        *offset,
+                        old_len,
+                        data,
+                        account_metadata.revision(),
+                    )?;
+                }
+
+                // discard the truncated content that is still within the data_account
+                if old_content_len > content_len {
+                    Self::discard_content(data_account, content_len, old_content_len);
 
-                    if args.debug {
-                        msg!("realloc-ed {}", new_space);
+                    if args.debug.info() {
+                        msg!("truncated {} bytes", old_content_len - content_len);
                     }
                 }
 
-                // update the data_account
-                if args.debug {
-                    msg!(
-                        "replaced {:?} with {:?}",
-                        &args.data,
-                        &data_account.data.borrow()[args.offset as usize..end_len]
-                    );
+                // checksum the content once it has been written
+                let checksum = match appended_checksum {
+                    Some(checksum) => checksum,
+                    None => crc32(&data_account.data.borrow()[..content_len]),
+                };
+                account_metadata.set_checksum(Some(checksum));
+                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+                // hash the content once it has been written
+                if compute_keccak {
+                    let content_keccak =
+                        keccak::hash(&data_account.data.borrow()[..content_len]).to_bytes();
+                    account_metadata.set_content_keccak(Some(content_keccak));
+                    account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
                 }
 
-                data_account.data.borrow_mut()[args.offset as usize..end_len]
-                    .copy_from_slice(&args.data);
+                // validate the final content once it has been written
+                if validate {
+                    let status = validate_content(
+                        &data_account.data.borrow()[..content_len],
+                        args.data_type.clone(),
+                    );
+
+                    if args.debug.info() {
+                        msg!("content {:?}", status);
+                    }
+
+                    account_metadata.set_serialization_status(status);
+                    account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+                }
 
                 Ok(())
             }
             DataAccountInstruction::UpdateDataAccountAuthority(args) => {
-                if args.debug {
+                if args.debug.info() {
                     msg!("UpdateDataAccountAuthority");
                 }
 
@@ -269,22 +725,12 @@ impl Processor {
                 let new_authority = next_account_info(accounts_iter)?;
 
                 // ensure authority and new_authority are signer
-                if !authority.is_signer || !new_authority.is_signer {
-                    return Err(DataAccountError::NotSigner.into());
-                }
+                require_signer(&[authority, new_authority])?;
 
                 // ensure metadata_account is writable
-                if !metadata_account.is_writable {
-                    return Err(DataAccountError::NotWriteable.into());
-                }
-
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
+                require_writable(&[metadata_account])?;
 
-                let mut account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+                let mut account_metadata = Self::read_metadata(program_id, metadata_account)?;
 
                 // ensure data_account is initialized
                 if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
@@ -297,7 +743,8 @@ impl Processor {
                 }
 
                 // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
+                require_pda_with_bump(
+                    metadata_account,
                     &[
                         PDA_SEED,
                         data_account.key.as_ref(),
@@ -305,21 +752,34 @@ impl Processor {
                     ],
                     program_id,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
 
-                if args.debug {
+                // ensure the user has co-signed if their consent is required
+                Self::check_user_consent(&account_metadata, authority, accounts_iter)?;
+
+                if args.debug.info() {
                     msg!("account checks passed")
                 }
 
+                if args.debug.info() {
+                    msg!("authority updated to {}", new_authority.key);
+                }
+
                 // update the authority
                 account_metadata.set_authority(*new_authority.key);
+                account_metadata.record_activity(Clock::get()?.slot);
+                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+                DataAccountEvent::AuthorityChanged {
+                    data_account: *data_account.key,
+                    old_authority: *authority.key,
+                    new_authority: *new_authority.key,
+                }
+                .emit()?;
 
                 Ok(())
             }
             DataAccountInstruction::CloseDataAccount(args) => {
-                if args.debug {
+                if args.debug.info() {
                     msg!("CloseDataAccount");
                 }
 
@@ -327,40 +787,46 @@ impl Processor {
                 let authority = next_account_info(accounts_iter)?;
                 let data_account = next_account_info(accounts_iter)?;
                 let metadata_account = next_account_info(accounts_iter)?;
+                let rent_receiver = next_account_info(accounts_iter)?;
 
                 // ensure authority is signer
-                if !authority.is_signer {
-                    return Err(DataAccountError::NotSigner.into());
-                }
+                require_signer(&[authority])?;
 
-                // ensure authority, data_account, and metadata_account are writable
-                if !authority.is_writable
-                    || !data_account.is_writable
-                    || !metadata_account.is_writable
-                {
-                    return Err(DataAccountError::NotWriteable.into());
-                }
+                // ensure authority, data_account, metadata_account and rent_receiver are writable
+                require_writable(&[authority, data_account, metadata_account, rent_receiver])?;
 
-                // ensure length is not 0
-                if data_account.data_is_empty() || metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
+                // ensure the refund is not zeroed along with the closed accounts
+                if rent_receiver.key == data_account.key
+                    || rent_receiver.key == metadata_account.key
+                {
+                    return Err(DataAccountError::InvalidRentReceiver.into());
                 }
 
-                let account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+                let mut account_metadata = Self::read_metadata(program_id, metadata_account)?;
 
                 // ensure data_account is initialized
                 if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
                     return Err(DataAccountError::NotInitialized.into());
                 }
 
-                // ensure data_account is being closed by valid authority
-                if account_metadata.authority() != authority.key {
+                // ensure data_account is owned by the data program unless only the archival stub is left
+                // zero-length data_accounts (e.g., initialized with space 0) can still be closed
+                if data_account.owner != program_id
+                    && *account_metadata.data_status() != DataStatusOption::ARCHIVED
+                {
+                    return Err(DataAccountError::InvalidOwner.into());
+                }
+
+                // ensure data_account is being closed by valid authority or the user it was derived for
+                if account_metadata.authority() != authority.key
+                    && account_metadata.user() != Some(authority.key)
+                {
                     return Err(DataAccountError::InvalidAuthority.into());
                 }
 
                 // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
+                require_pda_with_bump(
+                    metadata_account,
                     &[
                         PDA_SEED,
                         data_account.key.as_ref(),
@@ -368,36 +834,5806 @@ impl Processor {
                     ],
                     program_id,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
+
+                // ensure the user has co-signed if their consent is required
+                Self::check_user_consent(&account_metadata, authority, accounts_iter)?;
+
+                // ensure the data_account is not paused by its freeze_authority
+                if account_metadata.frozen() {
+                    return Err(DataAccountError::Frozen.into());
+                }
+
+                // ensure a close locked data_account was unlocked at least the cool-down ago
+                if let Some(close_lock) = account_metadata.close_lock() {
+                    if !close_lock.is_closable(Clock::get()?.slot) {
+                        return Err(DataAccountError::CloseLocked.into());
+                    }
+                }
+
+                // refund the unspent donations to the donors rather than the rent_receiver
+                Self::refund_donations(
+                    program_id,
+                    &mut account_metadata,
+                    data_account,
+                    rent_receiver,
+                    accounts_iter,
+                )?;
+
+                DataAccountEvent::Closed {
+                    data_account: *data_account.key,
+                    rent_receiver: *rent_receiver.key,
                 }
+                .emit()?;
+                log_kv!(
+                    args.debug,
+                    "close",
+                    data_account = data_account.key,
+                    metadata_account = metadata_account.key,
+                    rent_receiver = rent_receiver.key,
+                    refund = metadata_account.lamports() as u128 + data_account.lamports() as u128,
+                );
 
-                // transfer metadata_account lamports back to authority and reset metadata_account
-                let curr_lamports = authority.lamports();
-                **authority.lamports.borrow_mut() = curr_lamports
+                // transfer metadata_account lamports to rent_receiver and reset metadata_account
+                let curr_lamports = rent_receiver.lamports();
+                **rent_receiver.lamports.borrow_mut() = curr_lamports
                     .checked_add(metadata_account.lamports())
                     .ok_or(DataAccountError::Overflow)?;
                 **metadata_account.lamports.borrow_mut() = 0;
                 metadata_account.data.borrow_mut().fill(0);
 
-                if args.debug {
-                    msg!("{} transfered to authority for metadata pda", curr_lamports);
+                if args.debug.info() {
+                    msg!(
+                        "{} transfered to rent receiver for metadata pda",
+                        curr_lamports
+                    );
                 }
 
-                // transfer data_account lamports back to authority and reset data_account
-                let curr_lamports = authority.lamports();
-                **authority.lamports.borrow_mut() = curr_lamports
+                // transfer data_account lamports to rent_receiver and reset data_account
+                let curr_lamports = rent_receiver.lamports();
+                **rent_receiver.lamports.borrow_mut() = curr_lamports
                     .checked_add(data_account.lamports())
                     .ok_or(DataAccountError::Overflow)?;
                 **data_account.lamports.borrow_mut() = 0;
                 data_account.data.borrow_mut().fill(0);
 
-                if args.debug {
-                    msg!("{} transfered to authority for data account", curr_lamports);
+                if args.debug.info() {
+                    msg!(
+                        "{} transfered to rent receiver for data account",
+                        curr_lamports
+                    );
                 }
 
                 Ok(())
             }
         }
     }
+
+    fn process_initialize_user_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeUserDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeUserDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let feepayer = next_account_info(accounts_iter)?;
+        let app_authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure feepayer and app_authority are signer
+        require_signer(&[feepayer, app_authority])?;
+
+        // ensure name can be used as a seed
+        if args.name.is_empty() || args.name.len() > MAX_SEED_LEN {
+            return Err(DataAccountError::InvalidName.into());
+        }
+
+        // ensure the data_account is the pda derived from the app_authority, user and name
+        let bump_seed = require_pda(
+            data_account,
+            &[
+                app_authority.key.as_ref(),
+                args.user.as_ref(),
+                args.name.as_bytes(),
+            ],
+            program_id,
+        )?;
+
+        // create the data_account pda of given space
+        let space = args.space as usize;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            feepayer.key,
+            data_account.key,
+            rent_exemption_amount,
+            space as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[
+                feepayer.clone(),
+                data_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                app_authority.key.as_ref(),
+                args.user.as_ref(),
+                args.name.as_bytes(),
+                &[bump_seed],
+            ]],
+        )?;
+
+        if args.debug.info() {
+            msg!("user account of space: {} created", space);
+        }
+
+        // create data_account pda to store metadata
+        let bump_seed = Self::create_metadata_account(
+            program_id,
+            feepayer,
+            data_account,
+            metadata_account,
+            system_program,
+        )?;
+
+        if args.debug.info() {
+            msg!("metadata pda created");
+        }
+
+        // app_authority controls writes while the user can always close
+        let mut account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            *app_authority.key,
+            args.is_dynamic,
+            DATA_VERSION,
+            DataTypeOption::CUSTOM,
+            bump_seed,
+        );
+        account_metadata.set_user(Some(args.user));
+        account_metadata.set_require_user_consent(args.require_user_consent);
+        account_metadata.set_growth_policy(args.growth_policy);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: *app_authority.key,
+            space: space as u64,
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_allowlist_root(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetAllowlistRootArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetAllowlistRoot");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, 0, ROOT_SIZE)?;
+
+        // ensure data_account has space for the root by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < ROOT_SIZE {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, ROOT_SIZE);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata_account and store the root
+        account_metadata.set_data_type(DataTypeOption::ALLOWLIST);
+        account_metadata.set_compression(CompressionOption::NONE);
+        let content_len = account_metadata.content_len().max(ROOT_SIZE as u64);
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+        Self::copy_content(
+            data_account,
+            0,
+            old_len,
+            &args.root,
+            account_metadata.revision(),
+        )?;
+
+        if args.debug.info() {
+            msg!("allowlist root set to {:?}", &args.root);
+        }
+
+        Ok(())
+    }
+
+    fn process_verify_allowlist_inclusion(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: VerifyAllowlistInclusionArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("VerifyAllowlistInclusion");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account stores an allowlist root
+        if *account_metadata.data_type() != DataTypeOption::ALLOWLIST
+            || data_account.data_len() < ROOT_SIZE
+        {
+            return Err(DataAccountError::InvalidDataType.into());
+        }
+
+        let mut root = [0u8; ROOT_SIZE];
+        root.copy_from_slice(&data_account.data.borrow()[..ROOT_SIZE]);
+        if !verify_proof(&root, &args.entry, &args.proof) {
+            return Err(DataAccountError::InvalidProof.into());
+        }
+
+        if args.debug.info() {
+            msg!("entry included in allowlist");
+        }
+
+        Ok(())
+    }
+
+    fn process_snapshot_price_feed(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SnapshotPriceFeedArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SnapshotPriceFeed");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let feed = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the feed is owned by a supported oracle and has the requested window
+        if !is_supported_feed_owner(feed.owner) {
+            return Err(DataAccountError::InvalidFeed.into());
+        }
+        let feed_start = args.feed_offset as usize;
+        let feed_end = feed_start
+            .checked_add(args.feed_len as usize)
+            .ok_or(DataAccountError::Overflow)?;
+        if feed_end > feed.data_len() {
+            return Err(DataAccountError::InvalidFeed.into());
+        }
+
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(SNAPSHOT_HEADER_SIZE + args.feed_len as usize)
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure data_account has enough space by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug.info() {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        // the previous content_hash no longer covers the content
+        let content_len = account_metadata.content_len().max(end_len as u64);
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // write the snapshot header followed by the feed window
+        let clock = Clock::get()?;
+        let header = SnapshotHeader {
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            feed: *feed.key,
+        };
+        let mut snapshot = header.try_to_vec()?;
+        snapshot.extend_from_slice(&feed.data.borrow()[feed_start..feed_end]);
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &snapshot,
+            account_metadata.revision(),
+        )?;
+
+        if args.debug.info() {
+            msg!("snapshot of {} at slot {} written", feed.key, clock.slot);
+        }
+
+        Ok(())
+    }
+
+    fn process_stage_update(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: StageUpdateArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("StageUpdate");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let staging_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority and staging_account are writable
+        require_writable(&[authority, staging_account])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority or a session key within its scope
+        if account_metadata.authority() != authority.key {
+            let end_len = args
+                .offset
+                .checked_add(args.data.len() as u64)
+                .ok_or(DataAccountError::Overflow)?;
+            Self::check_session(
+                program_id,
+                authority,
+                data_account,
+                accounts_iter,
+                &SessionInstructionOption::STAGE,
+                args.offset,
+                end_len,
+            )?;
+        }
+
+        // ensure the staging_account corresponds to the data_account
+        let bump_seed = require_pda(
+            staging_account,
+            &[STAGING_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let staged_write = StagedWrite {
+            offset: args.offset,
+            data: args.data,
+        }
+        .try_to_vec()?;
+
+        // create the staging_account on the first queued write, otherwise grow it
+        let old_len = staging_account.data_len();
+        let new_len = old_len + staged_write.len();
+        if staging_account.data_is_empty() {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(new_len);
+            let create_staging_ix = system_instruction::create_account(
+                authority.key,
+                staging_account.key,
+                rent_exemption_amount,
+                new_len as u64,
+                program_id,
+            );
+            invoke_signed(
+                &create_staging_ix,
+                &[
+                    authority.clone(),
+                    staging_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[STAGING_SEED, data_account.key.as_ref(), &[bump_seed]]],
+            )?;
+        } else {
+            Self::realloc_account(authority, staging_account, system_program, new_len, 0)?;
+        }
+
+        staging_account.data.borrow_mut()[old_len..new_len].copy_from_slice(&staged_write);
+
+        if args.debug.info() {
+            msg!("staged write of {} bytes", staged_write.len());
+        }
+
+        Ok(())
+    }
+
+    fn process_flush_staging(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: FlushStagingArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("FlushStaging");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let staging_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, metadata_account and staging_account are writable
+        require_writable(&[authority, data_account, metadata_account, staging_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the staging_account corresponds to the data_account
+        require_pda(
+            staging_account,
+            &[STAGING_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+        if staging_account.data_is_empty() {
+            return Err(DataAccountError::EmptyStaging.into());
+        }
+
+        // read back all queued writes
+        let mut staged_writes = Vec::new();
+        {
+            let staging_data = staging_account.data.borrow();
+            let mut buf: &[u8] = &staging_data;
+            while !buf.is_empty() {
+                staged_writes.push(StagedWrite::deserialize(&mut buf)?);
+            }
+        }
+        let start = staged_writes
+            .iter()
+            .map(|write| write.offset as usize)
+            .min()
+            .unwrap_or(0);
+        let end_len = staged_writes
+            .iter()
+            .map(|write| (write.offset as usize).checked_add(write.data.len()))
+            .try_fold(0usize, |end, write_end| {
+                write_end.map(|write_end| end.max(write_end))
+            })
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure enough compute is left to apply and audit every queued write
+        check_compute(
+            staged_writes
+                .iter()
+                .map(|write| {
+                    copy_cost(write.data.len()).saturating_add(2 * hash_cost(write.data.len()))
+                })
+                .sum(),
+        )?;
+
+        // ensure data_account has enough space by reallocing once if needed
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug.info() {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        // update the metadata_account
+        let content_len = account_metadata.content_len().max(end_len as u64);
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // apply the queued writes in order
+        for write in staged_writes.iter() {
+            Self::copy_content(
+                data_account,
+                write.offset as usize,
+                old_len,
+                &write.data,
+                account_metadata.revision(),
+            )?;
+        }
+
+        if args.debug.info() {
+            msg!("flushed {} staged writes", staged_writes.len());
+        }
+
+        // close the staging_account and return its lamports to the authority
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(staging_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **staging_account.lamports.borrow_mut() = 0;
+        staging_account.realloc(0, false)?;
+
+        Ok(())
+    }
+
+    fn process_set_hot_region(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetHotRegionArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetHotRegion");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the hot region is non-empty and lies within the first page of the data_account
+        if let Some(hot_region) = args.hot_region.as_ref() {
+            if hot_region.len == 0
+                || hot_region.end() > HOT_REGION_MAX_END
+                || hot_region.end() > data_account.data_len()
+            {
+                return Err(DataAccountError::InvalidHotRegion.into());
+            }
+        }
+
+        if args.debug.info() {
+            msg!("hot region set to {:?}", args.hot_region);
+        }
+
+        account_metadata.set_hot_region(args.hot_region);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_archive_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ArchiveDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ArchiveDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being archived by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure data_account is not finalized as restoring it would make it writable again
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::Finalized.into());
+        }
+
+        // ensure the user has co-signed if their consent is required
+        Self::check_user_consent(&account_metadata, authority, accounts_iter)?;
+
+        // ensure the data_account is not paused by its freeze_authority
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+
+        // ensure a close locked data_account was unlocked at least the cool-down ago
+        if let Some(close_lock) = account_metadata.close_lock() {
+            if !close_lock.is_closable(Clock::get()?.slot) {
+                return Err(DataAccountError::CloseLocked.into());
+            }
+        }
+
+        // ensure the pointer fits in the stub
+        if let Some(pointer) = args.pointer.as_ref() {
+            if pointer.len() > ARCHIVE_POINTER_MAX_LEN {
+                return Err(DataAccountError::InvalidPointer.into());
+            }
+        }
+
+        // record the archival stub of the content
+        let size = (account_metadata.content_len() as usize).min(data_account.data_len());
+        check_compute(hash_cost(size))?;
+        let archive = ArchiveInfo {
+            hash: hash(&data_account.data.borrow()[..size]).to_bytes(),
+            size: size as u64,
+            archived_at: Clock::get()?.slot,
+            pointer: args.pointer,
+        };
+
+        if args.debug.info() {
+            msg!("archived {:?}", archive);
+        }
+
+        // refund the unspent donations to the donors rather than the authority
+        Self::refund_donations(
+            program_id,
+            &mut account_metadata,
+            data_account,
+            authority,
+            accounts_iter,
+        )?;
+
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::ARCHIVED)?;
+        account_metadata.set_archive(Some(archive));
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // transfer data_account lamports back to authority and reset data_account
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(data_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **data_account.lamports.borrow_mut() = 0;
+        data_account.realloc(0, false)?;
+
+        Ok(())
+    }
+
+    fn process_restore_from_archive(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RestoreFromArchiveArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RestoreFromArchive");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::read_metadata(program_id, metadata_account)?;
+
+        // ensure data_account is archived
+        let archive = match account_metadata.archive() {
+            Some(archive) if *account_metadata.data_status() == DataStatusOption::ARCHIVED => {
+                archive.clone()
+            }
+            _ => return Err(DataAccountError::NotArchived.into()),
+        };
+
+        // ensure data_account is being restored by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the metadata_account corresponds to the data_account
+        require_pda_with_bump(
+            metadata_account,
+            &[
+                PDA_SEED,
+                data_account.key.as_ref(),
+                &[account_metadata.bump_seed()],
+            ],
+            program_id,
+        )?;
+
+        // recreate the data_account with the archived size on the first chunk
+        let size = archive.size as usize;
+        if data_account.owner != program_id {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(size);
+            let create_account_ix = system_instruction::create_account(
+                authority.key,
+                data_account.key,
+                rent_exemption_amount,
+                size as u64,
+                program_id,
+            );
+            let create_accounts = [
+                authority.clone(),
+                data_account.clone(),
+                system_program.clone(),
+            ];
+            match &args.seeds {
+                // ensure the data_account is the pda of the seeds and sign for it
+                Some(seeds) => {
+                    let mut seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+                    let bump_seed = [require_pda(data_account, &seeds, program_id)?];
+                    seeds.push(&bump_seed);
+                    invoke_signed(&create_account_ix, &create_accounts, &[seeds.as_slice()])?;
+                }
+                None => invoke(&create_account_ix, &create_accounts)?,
+            }
+
+            if args.debug.info() {
+                msg!("account of space: {} recreated", size);
+            }
+        }
+
+        // write the chunk of content
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(args.data.len())
+            .ok_or(DataAccountError::Overflow)?;
+        if end_len > data_account.data_len() {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        // ensure enough compute is left to copy and audit the chunk and hash the content when finalizing
+        let finalize_cost = if args.finalize { hash_cost(size) } else { 0 };
+        check_compute(
+            copy_cost(args.data.len())
+                .saturating_add(2 * hash_cost(args.data.len()))
+                .saturating_add(finalize_cost),
+        )?;
+
+        let old_len = data_account.data_len();
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &args.data,
+            account_metadata.revision(),
+        )?;
+
+        if !args.finalize {
+            return Ok(());
+        }
+
+        // ensure the restored content matches the archived hash
+        if hash(&data_account.data.borrow()[..size]).to_bytes() != archive.hash {
+            return Err(DataAccountError::HashMismatch.into());
+        }
+
+        if args.debug.info() {
+            msg!("restored content matches archived hash");
+        }
+
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        account_metadata.set_archive(None);
+        Self::finish_content_write(&mut account_metadata, Some(archive.hash), archive.size);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_tag_revision(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: TagRevisionArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("TagRevision");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let tags_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority and tags_account are writable
+        require_writable(&[authority, tags_account])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being tagged by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the tags_account corresponds to the data_account
+        let bump_seed = require_pda(
+            tags_account,
+            &[TAGS_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let mut tags: Vec<RevisionTag> = if tags_account.data_is_empty() {
+            Vec::new()
+        } else {
+            try_from_slice_unchecked(&tags_account.try_borrow_data()?)?
+        };
+
+        // ensure the label is valid and unique
+        if args.label.is_empty()
+            || args.label.len() > TAG_LABEL_MAX_LEN
+            || tags.iter().any(|tag| tag.label == args.label)
+        {
+            return Err(DataAccountError::InvalidTag.into());
+        }
+        if tags.len() >= MAX_TAGS {
+            return Err(DataAccountError::TooManyTags.into());
+        }
+
+        // tag the current content
+        let content_len = (account_metadata.content_len() as usize).min(data_account.data_len());
+        check_compute(hash_cost(content_len))?;
+        let tag = RevisionTag {
+            label: args.label,
+            slot: Clock::get()?.slot,
+            content_len: content_len as u64,
+            hash: hash(&data_account.data.borrow()[..content_len]).to_bytes(),
+        };
+
+        if args.debug.info() {
+            msg!("tagged {:?}", tag);
+        }
+
+        tags.push(tag);
+        let tags_data = tags.try_to_vec()?;
+
+        // create the tags_account on the first tag, otherwise grow it
+        if tags_account.data_is_empty() {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(tags_data.len());
+            let create_tags_ix = system_instruction::create_account(
+                authority.key,
+                tags_account.key,
+                rent_exemption_amount,
+                tags_data.len() as u64,
+                program_id,
+            );
+            invoke_signed(
+                &create_tags_ix,
+                &[
+                    authority.clone(),
+                    tags_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[TAGS_SEED, data_account.key.as_ref(), &[bump_seed]]],
+            )?;
+        } else {
+            Self::realloc_account(authority, tags_account, system_program, tags_data.len(), 0)?;
+        }
+
+        tags_account.data.borrow_mut().copy_from_slice(&tags_data);
+
+        Ok(())
+    }
+
+    fn process_set_governance(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetGovernanceArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetGovernance");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure governance mode is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the authority can only sign through proposals of the governance
+        if let Some(governance) = args.governance.as_ref() {
+            if !is_governance_authority(
+                authority.key,
+                &governance.governance,
+                &governance.governance_program,
+            ) {
+                return Err(DataAccountError::InvalidGovernance.into());
+            }
+        }
+
+        if args.debug.info() {
+            msg!("governance set to {:?}", args.governance);
+        }
+
+        account_metadata.set_governance(args.governance);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_set_automation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetAutomationArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetAutomation");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure automation is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        let automation = args.delegate.map(|delegate| AutomationConfig {
+            delegate,
+            min_interval_slots: args.min_interval_slots,
+            last_heartbeat_slot: 0,
+        });
+
+        if args.debug.info() {
+            msg!("automation set to {:?}", automation);
+        }
+
+        account_metadata.set_automation(automation);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_automated_update(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: AutomatedUpdateArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("AutomatedUpdate");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let delegate = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure delegate is signer
+        require_signer(&[delegate])?;
+
+        // ensure data_account and metadata_account are writable
+        require_writable(&[data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by the automation delegate
+        let mut automation = match account_metadata.automation() {
+            Some(automation) if automation.delegate == *delegate.key => automation.clone(),
+            _ => return Err(DataAccountError::InvalidDelegate.into()),
+        };
+
+        // ensure the minimum interval has passed since the last scheduled write
+        let slot = Clock::get()?.slot;
+        if automation.last_heartbeat_slot != 0
+            && slot
+                < automation
+                    .last_heartbeat_slot
+                    .saturating_add(automation.min_interval_slots)
+        {
+            return Err(DataAccountError::RateLimited.into());
+        }
+
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(args.data.len())
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure the write fits within the data_account as the delegate cannot fund a realloc
+        if end_len > data_account.data_len() {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        // ensure enough compute is left to copy and audit the data instead of aborting mid-write
+        check_compute(copy_cost(args.data.len()).saturating_add(2 * hash_cost(args.data.len())))?;
+
+        let content_len = account_metadata.content_len().max(end_len as u64);
+
+        log_kv!(
+            args.debug,
+            "automated_update",
+            data_account = data_account.key,
+            offset = args.offset,
+            len = args.data.len(),
+            content_len = content_len,
+            slot = slot,
+        );
+
+        // record the heartbeat and update the metadata_account
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        automation.last_heartbeat_slot = slot;
+        account_metadata.set_automation(Some(automation));
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        let old_len = data_account.data_len();
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &args.data,
+            account_metadata.revision(),
+        )?;
+
+        if args.debug.info() {
+            msg!("heartbeat at slot {}", slot);
+        }
+
+        Ok(())
+    }
+
+    fn process_validate_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ValidateDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ValidateDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure data_account and metadata_account are owned by the data program
+        require_owner(data_account, program_id)?;
+        require_owner(metadata_account, program_id)?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account has the expected data_version
+        if account_metadata.version() != args.data_version {
+            return Err(DataAccountError::InvalidVersion.into());
+        }
+
+        // ensure data_account has the expected authority
+        if let Some(authority) = args.authority {
+            if *account_metadata.authority() != authority {
+                return Err(DataAccountError::InvalidAuthority.into());
+            }
+        }
+
+        // ensure data_account is committed if required
+        if args.require_committed && *account_metadata.data_status() != DataStatusOption::COMMITTED
+        {
+            return Err(DataAccountError::NotCommitted.into());
+        }
+
+        set_return_data(&account_metadata.try_to_vec()?);
+
+        if args.debug.info() {
+            msg!("data account validated");
+        }
+
+        Ok(())
+    }
+
+    fn process_set_dead_man_switch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetDeadManSwitchArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetDeadManSwitch");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure dead man switch is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the successor is not the authority itself and the timeout is non-zero
+        if args.successor == Some(*authority.key) || args.inactivity_timeout_slots == 0 {
+            return Err(DataAccountError::InvalidSuccessor.into());
+        }
+
+        let slot = Clock::get()?.slot;
+        let dead_man_switch = args.successor.map(|successor| DeadManSwitch {
+            successor,
+            inactivity_timeout_slots: args.inactivity_timeout_slots,
+            last_active_slot: slot,
+        });
+
+        if args.debug.info() {
+            msg!("dead man switch set to {:?}", dead_man_switch);
+        }
+
+        account_metadata.set_dead_man_switch(dead_man_switch);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim_dormant_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ClaimDormantAuthorityArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ClaimDormantAuthority");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let successor = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure successor is signer
+        require_signer(&[successor])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure authority is being claimed by the successor
+        let dead_man_switch = match account_metadata.dead_man_switch() {
+            Some(dead_man_switch) if dead_man_switch.successor == *successor.key => {
+                dead_man_switch.clone()
+            }
+            _ => return Err(DataAccountError::InvalidSuccessor.into()),
+        };
+
+        // ensure the authority has been inactive for the whole window
+        let slot = Clock::get()?.slot;
+        if !dead_man_switch.is_dormant(slot) {
+            return Err(DataAccountError::NotDormant.into());
+        }
+
+        DataAccountEvent::AuthorityChanged {
+            data_account: *data_account.key,
+            old_authority: *account_metadata.authority(),
+            new_authority: *successor.key,
+        }
+        .emit()?;
+
+        // transfer authority to the successor and disarm the switch
+        // the governance-gated mode belonged to the previous authority
+        account_metadata.set_authority(*successor.key);
+        account_metadata.set_governance(None);
+        account_metadata.set_dead_man_switch(None);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        if args.debug.info() {
+            msg!("authority claimed by {}", successor.key);
+        }
+
+        Ok(())
+    }
+
+    fn process_set_drop_box(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetDropBoxArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetDropBox");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure drop box is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the drop box region starts after the content and the hot region
+        if let Some(drop_box) = &args.drop_box {
+            let hot_region_end = account_metadata
+                .hot_region()
+                .map_or(0, |hot_region| hot_region.end());
+            if drop_box.start < account_metadata.content_len()
+                || (drop_box.start as usize) < hot_region_end
+                || drop_box.max_message_len == 0
+            {
+                return Err(DataAccountError::InvalidDropBox.into());
+            }
+        }
+
+        if args.debug.info() {
+            msg!("drop box set to {:?}", args.drop_box);
+        }
+
+        account_metadata.set_drop_box(args.drop_box);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_post_to_drop_box(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: PostToDropBoxArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("PostToDropBox");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let sender = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let sender_account = next_account_info(accounts_iter)?;
+        let authority = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure sender is signer
+        require_signer(&[sender])?;
+
+        // ensure sender, data_account, metadata_account, sender_account and authority are writable
+        require_writable(&[
+            sender,
+            data_account,
+            metadata_account,
+            sender_account,
+            authority,
+        ])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the fee is paid to the valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content is neither finalized, frozen nor sealed
+        // check_content_write does not apply since the message is meant to be written in the drop box region
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::Finalized.into());
+        }
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+        if account_metadata.time_lock().is_some() {
+            return Err(DataAccountError::Sealed.into());
+        }
+
+        // ensure the drop box is enabled and the message is within its max length
+        let drop_box = account_metadata
+            .drop_box()
+            .cloned()
+            .ok_or(DataAccountError::InvalidDropBox)?;
+        if args.message.is_empty() || args.message.len() > drop_box.max_message_len as usize {
+            return Err(DataAccountError::InvalidDropBox.into());
+        }
+
+        // ensure the sender_account corresponds to the data_account and the sender
+        let bump_seed = require_pda(
+            sender_account,
+            &[
+                DROP_BOX_SEED,
+                data_account.key.as_ref(),
+                sender.key.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // ensure the sender respects the minimum interval since its last message
+        let slot = Clock::get()?.slot;
+        if sender_account.data_is_empty() {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(8);
+            let create_sender_ix = system_instruction::create_account(
+                sender.key,
+                sender_account.key,
+                rent_exemption_amount,
+                8,
+                program_id,
+            );
+            invoke_signed(
+                &create_sender_ix,
+                &[
+                    sender.clone(),
+                    sender_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[
+                    DROP_BOX_SEED,
+                    data_account.key.as_ref(),
+                    sender.key.as_ref(),
+                    &[bump_seed],
+                ]],
+            )?;
+        } else {
+            let last_post_slot = u64::try_from_slice(&sender_account.data.borrow()[..8])?;
+            if slot < last_post_slot.saturating_add(drop_box.min_interval_slots) {
+                return Err(DataAccountError::RateLimited.into());
+            }
+        }
+        sender_account.data.borrow_mut()[..8].copy_from_slice(&slot.to_le_bytes());
+
+        // pay the fee per byte to the authority
+        let fee = drop_box
+            .fee_per_byte
+            .checked_mul(args.message.len() as u64)
+            .ok_or(DataAccountError::Overflow)?;
+        if fee > 0 {
+            let transfer_ix = system_instruction::transfer(sender.key, authority.key, fee);
+            invoke(
+                &transfer_ix,
+                &[sender.clone(), authority.clone(), system_program.clone()],
+            )?;
+        }
+
+        let entry = DropBoxMessage {
+            sender: *sender.key,
+            slot,
+            message: args.message,
+        }
+        .try_to_vec()?;
+
+        // append the message after the content, growing the data_account at the sender's expense if needed
+        let start = (account_metadata.content_len() as usize).max(drop_box.start as usize);
+        let end_len = start
+            .checked_add(entry.len())
+            .ok_or(DataAccountError::Overflow)?;
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                sender,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata_account
+        Self::finish_content_write(&mut account_metadata, None, end_len as u64);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &entry,
+            account_metadata.revision(),
+        )?;
+
+        if args.debug.info() {
+            msg!("message of {} bytes posted at {}", entry.len(), start);
+        }
+
+        Ok(())
+    }
+
+    fn process_donate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: DonateArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("Donate");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let donor = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let donations_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure donor is signer
+        require_signer(&[donor])?;
+
+        // ensure donor, data_account, metadata_account and donations_account are writable
+        require_writable(&[donor, data_account, metadata_account, donations_account])?;
+
+        // ensure donation is non-zero
+        if args.amount == 0 {
+            return Err(DataAccountError::InvalidDonation.into());
+        }
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the donations_account corresponds to the data_account
+        let bump_seed = require_pda(
+            donations_account,
+            &[DONATIONS_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+
+        // create the donations_account on the first donation
+        let mut donations = if donations_account.data_is_empty() {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(DONATIONS_SIZE);
+            let create_donations_ix = system_instruction::create_account(
+                donor.key,
+                donations_account.key,
+                rent_exemption_amount,
+                DONATIONS_SIZE as u64,
+                program_id,
+            );
+            invoke_signed(
+                &create_donations_ix,
+                &[
+                    donor.clone(),
+                    donations_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[DONATIONS_SEED, data_account.key.as_ref(), &[bump_seed]]],
+            )?;
+            DonationsTable::default()
+        } else {
+            try_from_slice_unchecked::<DonationsTable>(&donations_account.try_borrow_data()?)?
+        };
+
+        // the donated lamports are drawn on before the authority whenever the data_account grows
+        // and are kept from the authority on shrink until they are refunded to the donors on close
+        let transfer_ix = system_instruction::transfer(donor.key, data_account.key, args.amount);
+        invoke(
+            &transfer_ix,
+            &[donor.clone(), data_account.clone(), system_program.clone()],
+        )?;
+        let donated_lamports = account_metadata
+            .donated_lamports()
+            .checked_add(args.amount)
+            .ok_or(DataAccountError::Overflow)?;
+        account_metadata.set_donated_lamports(donated_lamports);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        donations
+            .credit(*donor.key, args.amount)
+            .ok_or(DataAccountError::Overflow)?;
+        donations.serialize(&mut &mut donations_account.data.borrow_mut()[..])?;
+
+        if args.debug.info() {
+            msg!("{} donated {} lamports", donor.key, args.amount);
+        }
+
+        Ok(())
+    }
+
+    fn process_seal_content(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SealContentArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SealContent");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure content is being sealed by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content is not finalized
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::Finalized.into());
+        }
+
+        // ensure the content is not already sealed
+        if account_metadata.time_lock().is_some() {
+            return Err(DataAccountError::Sealed.into());
+        }
+
+        // ensure the content of an append-only data_account is not swapped on reveal
+        if account_metadata.append_only() {
+            return Err(DataAccountError::AppendOnly.into());
+        }
+
+        let time_lock = TimeLock {
+            reveal_after_slot: args.reveal_after_slot,
+            plaintext_hash: args.plaintext_hash,
+        };
+
+        if args.debug.info() {
+            msg!("content sealed with {:?}", time_lock);
+        }
+
+        account_metadata.set_time_lock(Some(time_lock));
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_reveal_content(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RevealContentArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RevealContent");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let revealer = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure revealer is signer
+        require_signer(&[revealer])?;
+
+        // ensure revealer, data_account and metadata_account are writable
+        require_writable(&[revealer, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the content is sealed and past its reveal slot
+        let slot = Clock::get()?.slot;
+        let time_lock = match account_metadata.time_lock() {
+            Some(time_lock) if slot >= time_lock.reveal_after_slot => time_lock.clone(),
+            _ => return Err(DataAccountError::NotRevealable.into()),
+        };
+
+        // ensure enough compute is left to hash, copy and audit the plaintext
+        check_compute(
+            (3 * hash_cost(args.plaintext.len())).saturating_add(copy_cost(args.plaintext.len())),
+        )?;
+
+        // ensure the plaintext matches the committed hash if any
+        // without a committed hash only the authority can reveal
+        let plaintext_hash = commitment_hash(&args.plaintext, &[]);
+        match time_lock.plaintext_hash {
+            Some(committed_hash) if committed_hash != plaintext_hash => {
+                return Err(DataAccountError::HashMismatch.into());
+            }
+            None if account_metadata.authority() != revealer.key => {
+                return Err(DataAccountError::InvalidAuthority.into());
+            }
+            _ => {}
+        }
+
+        // the reveal unseals the content, ensure the plaintext is otherwise writable
+        let end_len = args.plaintext.len();
+        account_metadata.set_time_lock(None);
+        Self::check_content_write(&account_metadata, 0, end_len)?;
+
+        // ensure data_account has space for the plaintext, the revealer funds any growth
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                revealer,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata_account
+        let old_content_len = (account_metadata.content_len() as usize).min(old_len);
+        account_metadata.set_compression(CompressionOption::NONE);
+        Self::finish_content_write(&mut account_metadata, Some(plaintext_hash), end_len as u64);
+        if account_metadata.authority() == revealer.key {
+            account_metadata.record_activity(slot);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // swap the sealed content with the plaintext
+        Self::copy_content(
+            data_account,
+            0,
+            old_len,
+            &args.plaintext,
+            account_metadata.revision(),
+        )?;
+        Self::discard_content(data_account, end_len, old_content_len);
+
+        if args.debug.info() {
+            msg!("revealed {} bytes", end_len);
+        }
+
+        Ok(())
+    }
+
+    fn process_commit_value(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CommitValueArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CommitValue");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let committer = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let commitment_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure committer is signer
+        require_signer(&[committer])?;
+
+        // ensure committer and commitment_account are writable
+        require_writable(&[committer, commitment_account])?;
+
+        Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the commitment_account corresponds to the data_account and the committer
+        let bump_seed = require_pda(
+            commitment_account,
+            &[
+                COMMITMENT_SEED,
+                data_account.key.as_ref(),
+                committer.key.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // ensure the committer has no commitment yet and the deadline is in the future
+        let slot = Clock::get()?.slot;
+        if !commitment_account.data_is_empty() || args.reveal_deadline_slot < slot {
+            return Err(DataAccountError::InvalidCommitment.into());
+        }
+
+        let rent_exemption_amount = Rent::get()?.minimum_balance(COMMITMENT_SIZE);
+        let create_commitment_ix = system_instruction::create_account(
+            committer.key,
+            commitment_account.key,
+            rent_exemption_amount,
+            COMMITMENT_SIZE as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_commitment_ix,
+            &[
+                committer.clone(),
+                commitment_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                COMMITMENT_SEED,
+                data_account.key.as_ref(),
+                committer.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )?;
+
+        let commitment = Commitment {
+            committer: *committer.key,
+            hash: args.hash,
+            reveal_deadline_slot: args.reveal_deadline_slot,
+            revealed: None,
+        };
+        commitment.serialize(&mut &mut commitment_account.data.borrow_mut()[..])?;
+
+        if args.debug.info() {
+            msg!("committed {:?}", commitment);
+        }
+
+        Ok(())
+    }
+
+    fn process_reveal_value(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RevealValueArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RevealValue");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let committer = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let commitment_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure committer is signer
+        require_signer(&[committer])?;
+
+        // ensure committer and commitment_account are writable
+        require_writable(&[committer, commitment_account])?;
+
+        // ensure the commitment_account corresponds to the data_account and the committer
+        require_pda(
+            commitment_account,
+            &[
+                COMMITMENT_SEED,
+                data_account.key.as_ref(),
+                committer.key.as_ref(),
+            ],
+            program_id,
+        )?;
+        if commitment_account.data_is_empty() {
+            return Err(DataAccountError::InvalidPDA.into());
+        }
+
+        let mut commitment =
+            try_from_slice_unchecked::<Commitment>(&commitment_account.try_borrow_data()?)?;
+
+        // ensure the commitment is still open and the value is its preimage
+        if !commitment.is_open(Clock::get()?.slot) {
+            return Err(DataAccountError::InvalidCommitment.into());
+        }
+        if !commitment.verify(&args.value, &args.salt) {
+            return Err(DataAccountError::HashMismatch.into());
+        }
+
+        // write the revealed value to the commitment_account at the committer's expense
+        commitment.revealed = Some(args.value);
+        let commitment_data = commitment.try_to_vec()?;
+        Self::realloc_account(
+            committer,
+            commitment_account,
+            system_program,
+            commitment_data.len(),
+            0,
+        )?;
+        commitment_account
+            .data
+            .borrow_mut()
+            .copy_from_slice(&commitment_data);
+
+        if args.debug.info() {
+            msg!("revealed {:?}", commitment);
+        }
+
+        Ok(())
+    }
+
+    fn process_expire_commitment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ExpireCommitmentArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ExpireCommitment");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let commitment_account = next_account_info(accounts_iter)?;
+        let authority = next_account_info(accounts_iter)?;
+
+        // ensure commitment_account and authority are writable
+        require_writable(&[commitment_account, authority])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the forfeited lamports go to the valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure commitment_account is owned by the data program and is non-empty
+        require_owner(commitment_account, program_id)?;
+        if commitment_account.data_is_empty() {
+            return Err(DataAccountError::InvalidPDA.into());
+        }
+
+        let commitment =
+            try_from_slice_unchecked::<Commitment>(&commitment_account.try_borrow_data()?)?;
+
+        // ensure the commitment_account corresponds to the data_account
+        require_pda(
+            commitment_account,
+            &[
+                COMMITMENT_SEED,
+                data_account.key.as_ref(),
+                commitment.committer.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // ensure the commitment missed its reveal deadline
+        if !commitment.is_expired(Clock::get()?.slot) {
+            return Err(DataAccountError::NotExpired.into());
+        }
+
+        // forfeit the commitment_account lamports to the authority and reset commitment_account
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(commitment_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **commitment_account.lamports.borrow_mut() = 0;
+        commitment_account.data.borrow_mut().fill(0);
+
+        if args.debug.info() {
+            msg!("commitment of {} expired", commitment.committer);
+        }
+
+        Ok(())
+    }
+
+    fn process_set_license(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetLicenseArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetLicense");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure license is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure license is valid
+        if let Some(license) = &args.license {
+            if !license.is_valid() {
+                return Err(DataAccountError::InvalidLicense.into());
+            }
+        }
+
+        if args.debug.info() {
+            msg!("license set to {:?}", args.license);
+        }
+
+        account_metadata.set_license(args.license);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_initialize_metadata_tree(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeMetadataTreeArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeMetadataTree");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let operator = next_account_info(accounts_iter)?;
+        let tree_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure operator is signer
+        require_signer(&[operator])?;
+
+        // ensure operator and tree_account are writable
+        require_writable(&[operator, tree_account])?;
+
+        // ensure depth is valid
+        if args.max_depth == 0 || args.max_depth > MAX_TREE_DEPTH {
+            return Err(DataAccountError::InvalidTree.into());
+        }
+
+        // ensure the tree_account corresponds to the operator and is not initialized yet
+        let bump_seed = require_pda(
+            tree_account,
+            &[TREE_SEED, operator.key.as_ref()],
+            program_id,
+        )?;
+        if !tree_account.data_is_empty() {
+            return Err(DataAccountError::AlreadyInitialized.into());
+        }
+
+        let size = tree_size(args.max_depth);
+        let rent_exemption_amount = Rent::get()?.minimum_balance(size);
+        let create_tree_ix = system_instruction::create_account(
+            operator.key,
+            tree_account.key,
+            rent_exemption_amount,
+            size as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_tree_ix,
+            &[
+                operator.clone(),
+                tree_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[TREE_SEED, operator.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        MetadataTree::new(*operator.key, args.max_depth)
+            .serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+        if args.debug.info() {
+            msg!("metadata tree of depth {} created", args.max_depth);
+        }
+
+        Ok(())
+    }
+
+    fn process_initialize_compressed_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeCompressedDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeCompressedDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let operator = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let tree_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure operator and data_account are signer
+        require_signer(&[operator, data_account])?;
+
+        // ensure tree_account is writable
+        require_writable(&[tree_account])?;
+
+        let mut tree = Self::load_tree(program_id, tree_account)?;
+
+        // ensure leaves are only appended by the operator of the tree
+        if tree.operator != *operator.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // create a data_account of given space
+        let space = args.space as usize;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            operator.key,
+            data_account.key,
+            rent_exemption_amount,
+            space as u64,
+            program_id,
+        );
+        invoke(
+            &create_account_ix,
+            &[
+                operator.clone(),
+                data_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        // append the metadata of the data_account as a leaf instead of creating a metadata pda
+        let account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            args.authority,
+            args.is_dynamic,
+            DATA_VERSION,
+            DataTypeOption::CUSTOM,
+            0,
+        );
+        let leaf_index = tree
+            .append(metadata_leaf(data_account.key, &account_metadata)?)
+            .ok_or(DataAccountError::InvalidTree)?;
+        tree.serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+        Self::log_compressed_metadata(data_account.key, leaf_index, &account_metadata)?;
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: args.authority,
+            space: space as u64,
+        }
+        .emit()?;
+
+        if args.debug.info() {
+            msg!("compressed data account appended at leaf {}", leaf_index);
+        }
+
+        Ok(())
+    }
+
+    fn process_update_compressed_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UpdateCompressedDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("UpdateCompressedDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let tree_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and tree_account are writable
+        require_writable(&[authority, data_account, tree_account])?;
+
+        let mut tree = Self::load_tree(program_id, tree_account)?;
+        let mut account_metadata = args.metadata;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        let old_leaf = metadata_leaf(data_account.key, &account_metadata)?;
+        let old_len = data_account.data_len();
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(args.data.len())
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is neither finalized, frozen nor sealed and the write respects an append-only content
+        // and the drop box region
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure static data_account has sufficient space
+        if !account_metadata.dynamic() && old_len < end_len {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        // ensure enough compute is left to copy and audit the data instead of aborting mid-write
+        check_compute(copy_cost(args.data.len()).saturating_add(2 * hash_cost(args.data.len())))?;
+
+        // ensure data_account has enough space by reallocing if needed, recording the realloc in the leaf
+        if old_len < end_len {
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata leaf
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        let content_len = account_metadata.content_len().max(end_len as u64);
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        let new_leaf = metadata_leaf(data_account.key, &account_metadata)?;
+        if !tree.replace(args.leaf_index, old_leaf, new_leaf, &args.proof) {
+            return Err(DataAccountError::InvalidMetadataProof.into());
+        }
+        tree.serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &args.data,
+            account_metadata.revision(),
+        )?;
+
+        Self::log_compressed_metadata(data_account.key, args.leaf_index, &account_metadata)?;
+
+        if args.debug.info() {
+            msg!(
+                "compressed data account at leaf {} updated",
+                args.leaf_index
+            );
+        }
+
+        Ok(())
+    }
+
+    fn process_close_compressed_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CloseCompressedDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CloseCompressedDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let tree_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and tree_account are writable
+        require_writable(&[authority, data_account, tree_account])?;
+
+        let mut tree = Self::load_tree(program_id, tree_account)?;
+
+        // ensure data_account is being closed by valid authority
+        if args.metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // empty the leaf of the data_account
+        let old_leaf = metadata_leaf(data_account.key, &args.metadata)?;
+        if !tree.replace(args.leaf_index, old_leaf, EMPTY_LEAF, &args.proof) {
+            return Err(DataAccountError::InvalidMetadataProof.into());
+        }
+        tree.serialize(&mut &mut tree_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Closed {
+            data_account: *data_account.key,
+            rent_receiver: *authority.key,
+        }
+        .emit()?;
+
+        // transfer data_account lamports back to authority and reset data_account
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(data_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **data_account.lamports.borrow_mut() = 0;
+        data_account.data.borrow_mut().fill(0);
+
+        sol_log_data(&[
+            b"compressed_close",
+            data_account.key.as_ref(),
+            &args.leaf_index.to_le_bytes(),
+        ]);
+
+        if args.debug.info() {
+            msg!("compressed data account at leaf {} closed", args.leaf_index);
+        }
+
+        Ok(())
+    }
+
+    fn process_create_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CreateSessionArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CreateSession");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let session_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority and session_account are writable
+        require_writable(&[authority, session_account])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the session is created by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the session_account corresponds to the data_account and the session key
+        let bump_seed = require_pda(
+            session_account,
+            &[
+                SESSION_SEED,
+                data_account.key.as_ref(),
+                args.session_key.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // ensure the session key has no session yet and the scope is valid
+        if !session_account.data_is_empty()
+            || !args.scope.is_valid()
+            || args.expiry_slot < Clock::get()?.slot
+        {
+            return Err(DataAccountError::InvalidSessionScope.into());
+        }
+
+        let session = Session {
+            data_account: *data_account.key,
+            session_key: args.session_key,
+            expiry_slot: args.expiry_slot,
+            scope: args.scope,
+        };
+        let session_data = session.try_to_vec()?;
+
+        let rent_exemption_amount = Rent::get()?.minimum_balance(session_data.len());
+        let create_session_ix = system_instruction::create_account(
+            authority.key,
+            session_account.key,
+            rent_exemption_amount,
+            session_data.len() as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_session_ix,
+            &[
+                authority.clone(),
+                session_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                SESSION_SEED,
+                data_account.key.as_ref(),
+                args.session_key.as_ref(),
+                &[bump_seed],
+            ]],
+        )?;
+        session_account
+            .data
+            .borrow_mut()
+            .copy_from_slice(&session_data);
+
+        if args.debug.info() {
+            msg!("session created {:?}", session);
+        }
+
+        Ok(())
+    }
+
+    fn process_revoke_session(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RevokeSessionArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RevokeSession");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let signer = next_account_info(accounts_iter)?;
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let session_account = next_account_info(accounts_iter)?;
+
+        // ensure signer is signer
+        require_signer(&[signer])?;
+
+        // ensure authority and session_account are writable
+        require_writable(&[authority, session_account])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the session lamports go back to the valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure session_account is owned by the data program and is non-empty
+        require_owner(session_account, program_id)?;
+        if session_account.data_is_empty() {
+            return Err(DataAccountError::InvalidPDA.into());
+        }
+
+        let session = try_from_slice_unchecked::<Session>(&session_account.try_borrow_data()?)?;
+
+        // ensure the session_account corresponds to the data_account
+        require_pda(
+            session_account,
+            &[
+                SESSION_SEED,
+                data_account.key.as_ref(),
+                session.session_key.as_ref(),
+            ],
+            program_id,
+        )?;
+
+        // ensure the session is revoked by the authority or the session key unless it has expired
+        if signer.key != authority.key
+            && *signer.key != session.session_key
+            && !session.is_expired(Clock::get()?.slot)
+        {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // transfer session_account lamports back to authority and reset session_account
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(session_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **session_account.lamports.borrow_mut() = 0;
+        session_account.data.borrow_mut().fill(0);
+
+        if args.debug.info() {
+            msg!("session of {} revoked", session.session_key);
+        }
+
+        Ok(())
+    }
+
+    fn process_initialize_crdt(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeCrdtArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeCrdt");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        let payload = CrdtState::new(&args.kind).try_to_vec()?;
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, 0, payload.len())?;
+
+        // ensure data_account has space for the payload by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < payload.len() {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata
+                .growth_policy()
+                .grow(old_len, payload.len());
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata_account
+        let old_content_len = account_metadata.content_len() as usize;
+        account_metadata.set_data_type(DataTypeOption::CRDT);
+        account_metadata.set_compression(CompressionOption::NONE);
+        Self::finish_content_write(&mut account_metadata, None, payload.len() as u64);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // write the empty payload and zero the discarded content
+        Self::copy_content(
+            data_account,
+            0,
+            old_len,
+            &payload,
+            account_metadata.revision(),
+        )?;
+        Self::discard_content(data_account, payload.len(), old_content_len);
+
+        if args.debug.info() {
+            msg!("crdt initialized as {:?}", args.kind);
+        }
+
+        Ok(())
+    }
+
+    fn process_apply_delta(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ApplyDeltaArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ApplyDelta");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let writer = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure writer is signer
+        require_signer(&[writer])?;
+
+        // ensure writer, data_account, and metadata_account are writable
+        require_writable(&[writer, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account stores a CRDT payload
+        if *account_metadata.data_type() != DataTypeOption::CRDT {
+            return Err(DataAccountError::InvalidDataType.into());
+        }
+
+        // merge the delta into the payload
+        let content_len = account_metadata.content_len() as usize;
+        let mut state = CrdtState::try_from_slice(&data_account.data.borrow()[..content_len])?;
+        if !state.apply(writer.key, args.delta) {
+            return Err(DataAccountError::InvalidDelta.into());
+        }
+        let payload = state.try_to_vec()?;
+
+        // ensure the payload is written by valid authority or a session key whose scope covers it
+        let is_authority = account_metadata.authority() == writer.key;
+        if !is_authority {
+            Self::check_session(
+                program_id,
+                writer,
+                data_account,
+                accounts_iter,
+                &SessionInstructionOption::DELTA,
+                0,
+                payload.len() as u64,
+            )?;
+        }
+
+        // ensure the content is not sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, 0, payload.len())?;
+
+        // ensure data_account has space for the merged payload by reallocing at the writer's expense if needed
+        let old_len = data_account.data_len();
+        if old_len < payload.len() {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata
+                .growth_policy()
+                .grow(old_len, payload.len());
+            Self::realloc_data_account(
+                &mut account_metadata,
+                writer,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // update the metadata_account
+        // the previous content_hash no longer covers the payload
+        Self::finish_content_write(&mut account_metadata, None, payload.len() as u64);
+        if is_authority {
+            account_metadata.record_activity(Clock::get()?.slot);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Self::copy_content(
+            data_account,
+            0,
+            old_len,
+            &payload,
+            account_metadata.revision(),
+        )?;
+
+        if args.debug.info() {
+            msg!("delta applied, payload of {} bytes", payload.len());
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes the metadata tree and ensures it is owned by the data program
+    fn load_tree(
+        program_id: &Pubkey,
+        tree_account: &AccountInfo,
+    ) -> Result<MetadataTree, ProgramError> {
+        require_owner(tree_account, program_id)?;
+        if tree_account.data_is_empty() {
+            return Err(DataAccountError::InvalidTree.into());
+        }
+        let tree = try_from_slice_unchecked::<MetadataTree>(&tree_account.try_borrow_data()?)?;
+
+        // ensure the tree_account is the pda of its operator
+        require_pda(
+            tree_account,
+            &[TREE_SEED, tree.operator.as_ref()],
+            program_id,
+        )?;
+
+        Ok(tree)
+    }
+
+    /// Logs the metadata leaf so indexers can rebuild the metadata tree and serve proofs
+    fn log_compressed_metadata(
+        data_account: &Pubkey,
+        leaf_index: u64,
+        account_metadata: &DataAccountMetadata,
+    ) -> ProgramResult {
+        sol_log_data(&[
+            b"compressed_metadata",
+            data_account.as_ref(),
+            &leaf_index.to_le_bytes(),
+            &account_metadata.try_to_vec()?,
+        ]);
+        Ok(())
+    }
+
+    /// Ensures the signer can update [start, end) of the data_account, i.e., it is the authority, one of the writers,
+    /// the unexpired write_delegate or a session key whose scope covers the write (see check_session)
+    /// Returns whether the signer is a writer rather than a session key
+    fn authorize_writer<'a, 'b: 'a>(
+        program_id: &Pubkey,
+        account_metadata: &DataAccountMetadata,
+        signer: &AccountInfo<'b>,
+        data_account: &AccountInfo<'b>,
+        accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+        start: u64,
+        end: u64,
+    ) -> Result<bool, ProgramError> {
+        let is_delegate = match account_metadata.write_delegate() {
+            Some(write_delegate) if write_delegate.delegate == *signer.key => {
+                !write_delegate.is_expired(Clock::get()?.slot)
+            }
+            _ => false,
+        };
+        if account_metadata.authority() == signer.key
+            || account_metadata.writers().contains(signer.key)
+            || is_delegate
+        {
+            return Ok(true);
+        }
+
+        Self::check_session(
+            program_id,
+            signer,
+            data_account,
+            accounts_iter,
+            &SessionInstructionOption::UPDATE,
+            start,
+            end,
+        )?;
+        Ok(false)
+    }
+
+    /// Ensures the signer is the session key of an unexpired session of the data_account
+    /// whose scope covers the instruction and the write of [start, end)
+    /// The session pda is the next account, a missing session is treated as an invalid authority
+    fn check_session<'a, 'b: 'a>(
+        program_id: &Pubkey,
+        signer: &AccountInfo<'b>,
+        data_account: &AccountInfo<'b>,
+        accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+        instruction: &SessionInstructionOption,
+        start: u64,
+        end: u64,
+    ) -> ProgramResult {
+        let session_account = next_account_info(accounts_iter)
+            .map_err(|_| ProgramError::from(DataAccountError::InvalidAuthority))?;
+
+        // ensure the session_account is the session pda of the signer
+        require_pda(
+            session_account,
+            &[SESSION_SEED, data_account.key.as_ref(), signer.key.as_ref()],
+            program_id,
+        )?;
+        require_owner(session_account, program_id)?;
+
+        let session = try_from_slice_unchecked::<Session>(&session_account.try_borrow_data()?)?;
+        if session.is_expired(Clock::get()?.slot) || !session.scope.allows(instruction, start, end)
+        {
+            return Err(DataAccountError::InvalidSession.into());
+        }
+
+        Ok(())
+    }
+
+    /// Ensures a write of start..end_len is allowed
+    /// i.e., the content is neither finalized nor sealed, the write does not touch the append-only drop box region
+    /// and does not overwrite the content of an append-only data_account
+    fn check_content_write(
+        account_metadata: &DataAccountMetadata,
+        start: usize,
+        end_len: usize,
+    ) -> ProgramResult {
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::Finalized.into());
+        }
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+        if account_metadata.append_only() && start < account_metadata.content_len() as usize {
+            return Err(DataAccountError::AppendOnly.into());
+        }
+        if account_metadata.time_lock().is_some() {
+            return Err(DataAccountError::Sealed.into());
+        }
+        if let Some(drop_box) = account_metadata.drop_box() {
+            if end_len > drop_box.start as usize {
+                return Err(DataAccountError::DropBoxRegion.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the data_status to the next one, ensuring it is one of the allowed transitions
+    fn transition_data_status(
+        account_metadata: &mut DataAccountMetadata,
+        next: DataStatusOption,
+    ) -> ProgramResult {
+        if !account_metadata.data_status().can_transition_to(&next) {
+            return Err(DataAccountError::InvalidStatusTransition.into());
+        }
+        account_metadata.set_data_status(next);
+        Ok(())
+    }
+
+    /// Ensures the content has not been changed since the writer read the expected revision (if any)
+    fn check_expected_revision(
+        account_metadata: &DataAccountMetadata,
+        expected_revision: Option<u64>,
+    ) -> ProgramResult {
+        match expected_revision {
+            Some(expected_revision) if expected_revision != account_metadata.revision() => {
+                Err(DataAccountError::StaleRevision.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Ensures the write of a sequenced writer has a sequence number greater than its latest one and records it
+    /// A writer becomes sequenced with its first sequenced write, after which its unsequenced writes are rejected
+    fn advance_writer_sequence(
+        account_metadata: &mut DataAccountMetadata,
+        writer: &Pubkey,
+        sequence: Option<u64>,
+    ) -> ProgramResult {
+        let mut writer_sequences = account_metadata.writer_sequences().to_vec();
+        let latest = writer_sequences
+            .iter()
+            .position(|writer_sequence| writer_sequence.writer == *writer);
+        match (sequence, latest) {
+            (None, None) => return Ok(()),
+            (Some(sequence), Some(idx)) if sequence > writer_sequences[idx].sequence => {
+                writer_sequences[idx].sequence = sequence;
+            }
+            (Some(sequence), None) => {
+                if writer_sequences.len() >= MAX_SEQUENCED_WRITERS {
+                    return Err(DataAccountError::TooManyWriters.into());
+                }
+                writer_sequences.push(WriterSequence {
+                    writer: *writer,
+                    sequence,
+                });
+            }
+            _ => return Err(DataAccountError::StaleSequence.into()),
+        }
+        account_metadata.set_writer_sequences(writer_sequences);
+        Ok(())
+    }
+
+    fn process_lock_close(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: LockCloseArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("LockClose");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure close lock is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure cool-down is non-zero and does not weaken an existing close lock
+        let current_cooldown = account_metadata
+            .close_lock()
+            .map_or(0, |close_lock| close_lock.cooldown_slots);
+        if args.cooldown_slots == 0 || args.cooldown_slots < current_cooldown {
+            return Err(DataAccountError::InvalidCloseLock.into());
+        }
+
+        if args.debug.info() {
+            msg!(
+                "close locked with a cool-down of {} slots",
+                args.cooldown_slots
+            );
+        }
+
+        account_metadata.set_close_lock(Some(CloseLock {
+            cooldown_slots: args.cooldown_slots,
+            unlocked_at: None,
+        }));
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_unlock_close(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UnlockCloseArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("UnlockClose");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        // archived data_accounts can be unlocked as they can still be closed
+        let mut account_metadata = Self::read_metadata(program_id, metadata_account)?;
+
+        // ensure data_account is initialized
+        if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
+            return Err(DataAccountError::NotInitialized.into());
+        }
+
+        // ensure the metadata_account corresponds to the data_account
+        require_pda_with_bump(
+            metadata_account,
+            &[
+                PDA_SEED,
+                data_account.key.as_ref(),
+                &[account_metadata.bump_seed()],
+            ],
+            program_id,
+        )?;
+
+        // ensure data_account is being unlocked by valid authority or the user it was derived for
+        let is_authority = account_metadata.authority() == authority.key;
+        if !is_authority && account_metadata.user() != Some(authority.key) {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure data_account is close locked
+        let mut close_lock = account_metadata
+            .close_lock()
+            .cloned()
+            .ok_or(DataAccountError::InvalidCloseLock)?;
+
+        let current_slot = Clock::get()?.slot;
+        if args.debug.info() {
+            msg!(
+                "close unlocked at slot {}, closable from slot {}",
+                current_slot,
+                current_slot.saturating_add(close_lock.cooldown_slots)
+            );
+        }
+
+        close_lock.unlocked_at = Some(current_slot);
+        account_metadata.set_close_lock(Some(close_lock));
+        if is_authority {
+            account_metadata.record_activity(current_slot);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_finalize_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: FinalizeDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("FinalizeDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being finalized by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure data_account is not already finalized
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::Finalized.into());
+        }
+
+        // ensure the content is not sealed as it could never be revealed
+        if account_metadata.time_lock().is_some() {
+            return Err(DataAccountError::Sealed.into());
+        }
+
+        if args.debug.info() {
+            msg!("finalized {} content bytes", account_metadata.content_len());
+        }
+
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::FINALIZED)?;
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Finalized {
+            data_account: *data_account.key,
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_append_framed_record(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: AppendFramedRecordArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("AppendFramedRecord");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // the record is appended right after the content
+        let start = account_metadata.content_len() as usize;
+        let end_len = start
+            .checked_add(framed_len(args.record.len()))
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is neither finalized nor sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure enough compute is left to frame, copy and audit the record instead of aborting mid-write
+        let frame_len = end_len - start;
+        check_compute((2 * copy_cost(frame_len)).saturating_add(2 * hash_cost(frame_len)))?;
+
+        // ensure data_account has enough space by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug.info() {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        let mut frame = vec![0; frame_len];
+        write_record(&mut frame, &args.record).ok_or(DataAccountError::Overflow)?;
+
+        let record_count = account_metadata
+            .record_count()
+            .checked_add(1)
+            .ok_or(DataAccountError::Overflow)?;
+
+        log_kv!(
+            args.debug,
+            "append_record",
+            data_account = data_account.key,
+            offset = start,
+            len = args.record.len(),
+            record_count = record_count,
+        );
+
+        // the previous content_hash no longer covers the content
+        Self::finish_content_write(&mut account_metadata, None, end_len as u64);
+        account_metadata.set_record_count(record_count);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &frame,
+            account_metadata.revision(),
+        )?;
+
+        Ok(())
+    }
+
+    fn process_begin_upload(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: BeginUploadArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("BeginUpload");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data is being uploaded by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content is neither finalized nor sealed and the drop box region is not overwritten
+        Self::check_content_write(&account_metadata, 0, args.total_len as usize)?;
+
+        // ensure static data_account has sufficient space
+        if !account_metadata.dynamic() && data_account.data_len() < args.total_len as usize {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        if args.debug.info() {
+            msg!("upload of {} bytes started", args.total_len);
+        }
+
+        // the content is overwritten chunk by chunk so the previous content_hash no longer covers it
+        account_metadata.set_content_hash(None);
+        account_metadata.set_upload(Some(UploadProgress {
+            total_len: args.total_len,
+            checksum: args.checksum,
+            written: 0,
+        }));
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_append_chunk(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: AppendChunkArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("AppendChunk");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data is being uploaded by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the chunk is the next one of the upload in progress and fits in its total length
+        // a resumed upload continues from the written offset recorded in the metadata
+        let mut upload = account_metadata
+            .upload()
+            .cloned()
+            .ok_or(DataAccountError::InvalidUpload)?;
+        let written = upload
+            .written
+            .checked_add(args.data.len() as u64)
+            .ok_or(DataAccountError::Overflow)?;
+        if args.offset != upload.written || written > upload.total_len {
+            return Err(DataAccountError::InvalidUpload.into());
+        }
+        let start = args.offset as usize;
+        let end_len = written as usize;
+
+        // ensure the content has not been finalized or sealed since the upload began
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure enough compute is left to copy and audit the chunk instead of aborting mid-write
+        check_compute(copy_cost(args.data.len()).saturating_add(2 * hash_cost(args.data.len())))?;
+
+        // ensure data_account has enough space by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug.info() {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            &args.data,
+            account_metadata.revision(),
+        )?;
+
+        log_kv!(
+            args.debug,
+            "append_chunk",
+            data_account = data_account.key,
+            offset = start,
+            len = args.data.len(),
+            written = written,
+            total_len = upload.total_len,
+        );
+
+        upload.written = written;
+        account_metadata.set_upload(Some(upload));
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_commit_upload(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CommitUploadArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CommitUpload");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure data_account and metadata_account are writable
+        require_writable(&[data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data is being uploaded by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure every chunk of the upload in progress has been appended
+        let upload = account_metadata
+            .upload()
+            .cloned()
+            .ok_or(DataAccountError::InvalidUpload)?;
+        if upload.written != upload.total_len {
+            return Err(DataAccountError::InvalidUpload.into());
+        }
+        let total_len = upload.total_len as usize;
+
+        // ensure the content has not been finalized or sealed since the upload began
+        Self::check_content_write(&account_metadata, 0, total_len)?;
+
+        // ensure the uploaded content matches the checksum
+        check_compute(hash_cost(total_len))?;
+        if hash(&data_account.data.borrow()[..total_len]).to_bytes() != upload.checksum {
+            return Err(DataAccountError::UploadChecksumMismatch.into());
+        }
+
+        // discard the previous content beyond the upload that is still within the data_account
+        Self::discard_content(
+            data_account,
+            total_len,
+            account_metadata.content_len() as usize,
+        );
+
+        if args.debug.info() {
+            msg!("upload of {} bytes committed", total_len);
+        }
+
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        Self::finish_content_write(
+            &mut account_metadata,
+            Some(upload.checksum),
+            upload.total_len,
+        );
+        account_metadata.set_record_count(0);
+        account_metadata.set_upload(None);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_verify_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: VerifyDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("VerifyDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure there is a content_hash that covers the stored bytes (i.e., of uncompressed content)
+        let content_hash = *account_metadata
+            .content_hash()
+            .ok_or(DataAccountError::NotVerifiable)?;
+        if *account_metadata.compression() != CompressionOption::NONE {
+            return Err(DataAccountError::NotVerifiable.into());
+        }
+        let content_len = account_metadata.content_len() as usize;
+        if content_len > data_account.data_len() {
+            return Err(DataAccountError::NotVerifiable.into());
+        }
+
+        // ensure enough compute is left to hash the content
+        check_compute(hash_cost(content_len))?;
+        let status = if hash(&data_account.data.borrow()[..content_len]).to_bytes() == content_hash
+        {
+            SerializationStatusOption::VERIFIED
+        } else {
+            SerializationStatusOption::FAILED
+        };
+
+        if args.debug.info() {
+            msg!("content {:?}", status);
+        }
+
+        account_metadata.set_serialization_status(status);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_initialize_nft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeNftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeNftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let holder = next_account_info(accounts_iter)?;
+        let token_account = next_account_info(accounts_iter)?;
+        let mint = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure holder is signer
+        require_signer(&[holder])?;
+
+        // ensure the mint is an NFT held by the holder
+        if !is_nft_mint(mint) {
+            return Err(DataAccountError::InvalidNftMint.into());
+        }
+        if !is_token_holder(token_account, mint.key, holder.key) {
+            return Err(DataAccountError::NotTokenHolder.into());
+        }
+
+        // ensure the data_account is the pda derived from the mint
+        let bump_seed = require_pda(data_account, &[NFT_SEED, mint.key.as_ref()], program_id)?;
+
+        // create the data_account pda of given space
+        let space = args.space as usize;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            holder.key,
+            data_account.key,
+            rent_exemption_amount,
+            space as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[holder.clone(), data_account.clone(), system_program.clone()],
+            &[&[NFT_SEED, mint.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        if args.debug.info() {
+            msg!("nft account of space: {} created", space);
+        }
+
+        // create data_account pda to store metadata
+        let bump_seed = Self::create_metadata_account(
+            program_id,
+            holder,
+            data_account,
+            metadata_account,
+            system_program,
+        )?;
+
+        if args.debug.info() {
+            msg!("metadata pda created");
+        }
+
+        // the data_account is its own authority so it can only be written by the holder of the NFT
+        let mut account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            *data_account.key,
+            args.is_dynamic,
+            DATA_VERSION,
+            DataTypeOption::CUSTOM,
+            bump_seed,
+        );
+        account_metadata.set_growth_policy(args.growth_policy);
+        account_metadata.set_nft_mint(Some(*mint.key));
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: *data_account.key,
+            space: data_account.data_len() as u64,
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_update_nft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UpdateNftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("UpdateNftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let holder = next_account_info(accounts_iter)?;
+        let token_account = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure holder is signer
+        require_signer(&[holder])?;
+
+        // ensure holder, data_account, and metadata_account are writable
+        require_writable(&[holder, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being written to by the current holder of its NFT
+        let mint = *account_metadata
+            .nft_mint()
+            .ok_or(DataAccountError::NotNftBound)?;
+        if !is_token_holder(token_account, &mint, holder.key) {
+            return Err(DataAccountError::NotTokenHolder.into());
+        }
+
+        let old_len = data_account.data_len();
+        let content_len = Self::write_content(
+            &mut account_metadata,
+            holder,
+            data_account,
+            system_program,
+            &args.data,
+            args.offset,
+            args.truncate,
+        )?;
+
+        if args.debug.info() && data_account.data_len() != old_len {
+            msg!("realloc-ed {}", data_account.data_len());
+        }
+
+        log_kv!(
+            args.debug,
+            "nft_update",
+            data_account = data_account.key,
+            holder = holder.key,
+            offset = args.offset,
+            len = args.data.len(),
+            content_len = content_len,
+        );
+
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_add_writer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: AddWriterArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("AddWriter");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure writer is being added by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the writer is not listed yet and the list has room for it
+        let mut writers = account_metadata.writers().to_vec();
+        if writers.contains(&args.writer) {
+            return Err(DataAccountError::InvalidWriter.into());
+        }
+        if writers.len() >= MAX_WRITERS {
+            return Err(DataAccountError::WriterListFull.into());
+        }
+
+        if args.debug.info() {
+            msg!("writer {} added", args.writer);
+        }
+
+        writers.push(args.writer);
+        account_metadata.set_writers(writers);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_remove_writer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RemoveWriterArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RemoveWriter");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure writer is being removed by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the writer is listed
+        let mut writers = account_metadata.writers().to_vec();
+        let idx = writers
+            .iter()
+            .position(|writer| *writer == args.writer)
+            .ok_or(DataAccountError::InvalidWriter)?;
+
+        if args.debug.info() {
+            msg!("writer {} removed", args.writer);
+        }
+
+        writers.remove(idx);
+        account_metadata.set_writers(writers);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_initialize_cnft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeCnftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeCnftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let merkle_tree = next_account_info(accounts_iter)?;
+        let compression_program = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure owner is signer
+        require_signer(&[owner])?;
+
+        // ensure the owner currently owns the cNFT of the leaf (the remaining accounts are the proof)
+        let asset_id = cnft_asset_id(merkle_tree.key, args.leaf.nonce);
+        verify_owner(
+            compression_program,
+            merkle_tree,
+            accounts_iter.as_slice(),
+            &asset_id,
+            owner.key,
+            &args.leaf,
+        )?;
+
+        // ensure the data_account is the pda derived from the asset id
+        let bump_seed = require_pda(data_account, &[CNFT_SEED, asset_id.as_ref()], program_id)?;
+
+        // create the data_account pda of given space
+        let space = args.space as usize;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            owner.key,
+            data_account.key,
+            rent_exemption_amount,
+            space as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[owner.clone(), data_account.clone(), system_program.clone()],
+            &[&[CNFT_SEED, asset_id.as_ref(), &[bump_seed]]],
+        )?;
+
+        if args.debug.info() {
+            msg!("cnft account of space: {} created", space);
+        }
+
+        // create data_account pda to store metadata
+        let bump_seed = Self::create_metadata_account(
+            program_id,
+            owner,
+            data_account,
+            metadata_account,
+            system_program,
+        )?;
+
+        if args.debug.info() {
+            msg!("metadata pda created");
+        }
+
+        // the data_account is its own authority so it can only be written by the owner of the cNFT
+        let mut account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            *data_account.key,
+            args.is_dynamic,
+            DATA_VERSION,
+            DataTypeOption::CUSTOM,
+            bump_seed,
+        );
+        account_metadata.set_growth_policy(args.growth_policy);
+        account_metadata.set_nft_mint(Some(asset_id));
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: *data_account.key,
+            space: data_account.data_len() as u64,
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_update_cnft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UpdateCnftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("UpdateCnftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let merkle_tree = next_account_info(accounts_iter)?;
+        let compression_program = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure owner is signer
+        require_signer(&[owner])?;
+
+        // ensure owner, data_account, and metadata_account are writable
+        require_writable(&[owner, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the leaf is of the cNFT the data_account is bound to
+        let asset_id = *account_metadata
+            .nft_mint()
+            .ok_or(DataAccountError::NotNftBound)?;
+        if asset_id != cnft_asset_id(merkle_tree.key, args.leaf.nonce) {
+            return Err(DataAccountError::InvalidMerkleTree.into());
+        }
+        require_pda(data_account, &[CNFT_SEED, asset_id.as_ref()], program_id)?;
+
+        // ensure data_account is being written to by the current owner of its cNFT
+        verify_owner(
+            compression_program,
+            merkle_tree,
+            accounts_iter.as_slice(),
+            &asset_id,
+            owner.key,
+            &args.leaf,
+        )?;
+
+        let old_len = data_account.data_len();
+        let content_len = Self::write_content(
+            &mut account_metadata,
+            owner,
+            data_account,
+            system_program,
+            &args.data,
+            args.offset,
+            args.truncate,
+        )?;
+
+        if args.debug.info() && data_account.data_len() != old_len {
+            msg!("realloc-ed {}", data_account.data_len());
+        }
+
+        log_kv!(
+            args.debug,
+            "cnft_update",
+            data_account = data_account.key,
+            owner = owner.key,
+            offset = args.offset,
+            len = args.data.len(),
+            content_len = content_len,
+        );
+
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_set_expiry(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetExpiryArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetExpiry");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure expiry is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the refund is not zeroed along with the expired accounts
+        if let Some(expiry) = &args.expiry {
+            if expiry.rent_receiver == *data_account.key
+                || expiry.rent_receiver == *metadata_account.key
+            {
+                return Err(DataAccountError::InvalidRentReceiver.into());
+            }
+        }
+
+        log_kv!(
+            args.debug,
+            "set_expiry",
+            data_account = data_account.key,
+            expires_at = args.expiry.as_ref().map_or(0, |expiry| expiry.expires_at),
+        );
+
+        account_metadata.set_expiry(args.expiry);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_expire_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ExpireDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ExpireDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let rent_receiver = next_account_info(accounts_iter)?;
+
+        // ensure data_account, metadata_account and rent_receiver are writable
+        require_writable(&[data_account, metadata_account, rent_receiver])?;
+
+        // ensure data_account is owned by the data program so its lamports can be moved
+        require_owner(data_account, program_id)?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account has expired and is refunded to the pre-configured rent_receiver
+        let expiry = account_metadata
+            .expiry()
+            .ok_or(DataAccountError::ExpiryNotReached)?;
+        if Clock::get()?.unix_timestamp < expiry.expires_at {
+            return Err(DataAccountError::ExpiryNotReached.into());
+        }
+        if expiry.rent_receiver != *rent_receiver.key {
+            return Err(DataAccountError::InvalidRentReceiver.into());
+        }
+
+        // ensure the user has co-signed if their consent is required
+        // nobody signs an expiry in place of the user, so it is checked against the data_account that never is the user
+        Self::check_user_consent(&account_metadata, data_account, accounts_iter)?;
+
+        // ensure the data_account is not paused by its freeze_authority
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+
+        // ensure a close locked data_account was unlocked at least the cool-down ago
+        if let Some(close_lock) = account_metadata.close_lock() {
+            if !close_lock.is_closable(Clock::get()?.slot) {
+                return Err(DataAccountError::CloseLocked.into());
+            }
+        }
+
+        // refund the unspent donations to the donors rather than the rent_receiver
+        Self::refund_donations(
+            program_id,
+            &mut account_metadata,
+            data_account,
+            rent_receiver,
+            accounts_iter,
+        )?;
+
+        DataAccountEvent::Closed {
+            data_account: *data_account.key,
+            rent_receiver: *rent_receiver.key,
+        }
+        .emit()?;
+        log_kv!(
+            args.debug,
+            "expire",
+            data_account = data_account.key,
+            metadata_account = metadata_account.key,
+            rent_receiver = rent_receiver.key,
+            refund = metadata_account.lamports() as u128 + data_account.lamports() as u128,
+        );
+
+        // transfer metadata_account and data_account lamports to rent_receiver and reset both
+        for account in [metadata_account, data_account] {
+            let curr_lamports = rent_receiver.lamports();
+            **rent_receiver.lamports.borrow_mut() = curr_lamports
+                .checked_add(account.lamports())
+                .ok_or(DataAccountError::Overflow)?;
+            **account.lamports.borrow_mut() = 0;
+            account.data.borrow_mut().fill(0);
+        }
+
+        Ok(())
+    }
+
+    fn process_set_notification_hint(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetNotificationHintArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetNotificationHint");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure notification hint is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        if args.debug.info() {
+            match &args.notification_hint {
+                Some(notification_hint) => msg!("notification hint set to {:?}", notification_hint),
+                None => msg!("notification hint cleared"),
+            }
+        }
+
+        account_metadata.set_notification_hint(args.notification_hint);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_resize_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ResizeDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ResizeDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being resized by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content, the hot region and the drop box messages are kept
+        let old_len = data_account.data_len();
+        let mut min_len = account_metadata.content_len() as usize;
+        if let Some(hot_region) = account_metadata.hot_region() {
+            min_len = min_len.max(hot_region.end());
+        }
+        if account_metadata.drop_box().is_some() {
+            min_len = min_len.max(old_len);
+        }
+
+        // ensure the data_account grows by at most the realloc limit of an instruction
+        let new_len = args.space as usize;
+        if new_len < min_len || new_len > old_len.saturating_add(MAX_PERMITTED_DATA_INCREASE) {
+            return Err(DataAccountError::InvalidResize.into());
+        }
+
+        // the authority funds the growth and is refunded the excess lamports on shrink
+        if new_len != old_len {
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        log_kv!(
+            args.debug,
+            "resize",
+            data_account = data_account.key,
+            old_len = old_len,
+            new_len = new_len,
+            lamports = data_account.lamports(),
+        );
+
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_update_data_account_compressed(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UpdateDataAccountCompressedArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("UpdateDataAccountCompressed");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, and metadata_account are writable
+        require_writable(&[authority, data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the payload expands within the bounded output size
+        check_compute(copy_cost(args.max_output_len as usize))?;
+        let data = inflate(&args.data, args.max_output_len as usize)?;
+
+        if args.debug.info() {
+            msg!("inflated {} bytes to {}", args.data.len(), data.len());
+        }
+
+        // ensure data_account is being written to by its authority, one of its writers, its unexpired delegate
+        // or a session key within its scope, like UpdateDataAccount
+        let end_len = args
+            .offset
+            .checked_add(data.len() as u64)
+            .ok_or(DataAccountError::Overflow)?;
+        let is_authority = account_metadata.authority() == authority.key;
+        let is_writer = Self::authorize_writer(
+            program_id,
+            &account_metadata,
+            authority,
+            data_account,
+            accounts_iter,
+            args.offset,
+            end_len,
+        )?;
+
+        // ensure a session key neither truncates nor changes how the content is interpreted
+        if !is_writer
+            && (args.truncate
+                || args.data_type != *account_metadata.data_type()
+                || *account_metadata.compression() != CompressionOption::NONE)
+        {
+            return Err(DataAccountError::InvalidSession.into());
+        }
+
+        // ensure the content has not been updated since the writer read the expected revision
+        Self::check_expected_revision(&account_metadata, args.expected_revision)?;
+
+        // ensure a retried or reordered write of a sequenced writer cannot regress the content
+        Self::advance_writer_sequence(&mut account_metadata, authority.key, args.sequence)?;
+
+        let old_len = data_account.data_len();
+        let content_len = Self::write_content(
+            &mut account_metadata,
+            authority,
+            data_account,
+            system_program,
+            &data,
+            args.offset,
+            args.truncate,
+        )?;
+
+        if args.debug.info() && data_account.data_len() != old_len {
+            msg!("realloc-ed {}", data_account.data_len());
+        }
+
+        log_kv!(
+            args.debug,
+            "update_compressed",
+            data_account = data_account.key,
+            offset = args.offset,
+            compressed_len = args.data.len(),
+            len = data.len(),
+            content_len = content_len,
+        );
+
+        // the expanded bytes are stored so the content is not compressed
+        account_metadata.set_data_type(args.data_type);
+        account_metadata.set_compression(CompressionOption::NONE);
+        if is_authority {
+            account_metadata.record_activity(Clock::get()?.slot);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_initialize_data_account_pda(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: InitializeDataAccountPdaArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("InitializeDataAccountPda");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let feepayer = next_account_info(accounts_iter)?;
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure feepayer and authority are signer
+        require_signer(&[feepayer, authority])?;
+
+        // ensure seed can be used as a seed
+        if args.seed.is_empty() || args.seed.len() > MAX_SEED_LEN {
+            return Err(DataAccountError::InvalidName.into());
+        }
+
+        // ensure the data_account is the pda derived from the authority and seed
+        let bump_seed = require_pda(
+            data_account,
+            &[DATA_PDA_SEED, authority.key.as_ref(), args.seed.as_bytes()],
+            program_id,
+        )?;
+
+        // create the data_account pda of given space
+        let space = args.space as usize;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            feepayer.key,
+            data_account.key,
+            rent_exemption_amount,
+            space as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[
+                feepayer.clone(),
+                data_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                DATA_PDA_SEED,
+                authority.key.as_ref(),
+                args.seed.as_bytes(),
+                &[bump_seed],
+            ]],
+        )?;
+
+        if args.debug.info() {
+            msg!("pda account of space: {} created", space);
+        }
+        log_kv!(
+            args.debug,
+            "create",
+            data_account = data_account.key,
+            space = space,
+            rent = rent_exemption_amount,
+        );
+
+        // create data_account pda to store metadata
+        let bump_seed = Self::create_metadata_account(
+            program_id,
+            feepayer,
+            data_account,
+            metadata_account,
+            system_program,
+        )?;
+
+        if args.debug.info() {
+            msg!("metadata pda created");
+        }
+
+        let mut account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            *authority.key,
+            args.is_dynamic,
+            DATA_VERSION,
+            DataTypeOption::CUSTOM,
+            bump_seed,
+        );
+        account_metadata.set_growth_policy(args.growth_policy);
+        account_metadata.set_append_only(args.append_only);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: *authority.key,
+            space: space as u64,
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_scrub_range(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ScrubRangeArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ScrubRange");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure data_account and metadata_account are writable
+        require_writable(&[data_account, metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being scrubbed by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the range is non-empty and within the content stored in the data_account
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(args.len as usize)
+            .ok_or(DataAccountError::InvalidScrubRange)?;
+        let content_len = (account_metadata.content_len() as usize).min(data_account.data_len());
+        if args.len == 0 || end_len > content_len {
+            return Err(DataAccountError::InvalidScrubRange.into());
+        }
+
+        // ensure the content is not sealed and the range is neither finalized nor append-only content
+        Self::check_content_write(&account_metadata, start, end_len)?;
+
+        // ensure enough compute is left to hash, fill and audit the range and checksum the content
+        let fill_cost = match args.fill {
+            ScrubFillOption::ZEROS => copy_cost(args.len as usize),
+            ScrubFillOption::RANDOM => (args.len / 32 + 1).saturating_mul(hash_cost(56)),
+        };
+        check_compute(
+            fill_cost
+                .saturating_add(2 * hash_cost(args.len as usize))
+                .saturating_add(checksum_cost(content_len)),
+        )?;
+
+        let before_hash = hash(&data_account.data.borrow()[start..end_len]).to_bytes();
+
+        // random bytes are expanded from the data_account, slot and offset so they are pseudo-random, not secret
+        let slot = Clock::get()?.slot;
+        {
+            let mut data = data_account.data.borrow_mut();
+            let range = &mut data[start..end_len];
+            match args.fill {
+                ScrubFillOption::ZEROS => range.fill(0),
+                ScrubFillOption::RANDOM => {
+                    for (block, chunk) in range.chunks_mut(32).enumerate() {
+                        let bytes = hashv(&[
+                            data_account.key.as_ref(),
+                            &slot.to_le_bytes(),
+                            &args.offset.to_le_bytes(),
+                            &(block as u64).to_le_bytes(),
+                        ])
+                        .to_bytes();
+                        chunk.copy_from_slice(&bytes[..chunk.len()]);
+                    }
+                }
+            }
+        }
+
+        // emit the same audit event as a write so the audit trail stays verifiable, and flag it as a scrub
+        sol_log_data(&[
+            b"audit",
+            &args.offset.to_le_bytes(),
+            &args.len.to_le_bytes(),
+            &before_hash,
+            &hash(&data_account.data.borrow()[start..end_len]).to_bytes(),
+        ]);
+        sol_log_data(&[
+            b"scrub",
+            &args.offset.to_le_bytes(),
+            &args.len.to_le_bytes(),
+        ]);
+
+        log_kv!(
+            args.debug,
+            "scrub",
+            data_account = data_account.key,
+            offset = args.offset,
+            len = args.len,
+            random = args.fill == ScrubFillOption::RANDOM,
+        );
+
+        // the previous content_hash (and keccak256 hash) no longer covers the content, but the
+        // checksum is recomputed over it like after an update
+        let checksum = crc32(&data_account.data.borrow()[..content_len]);
+        let content_len = account_metadata.content_len();
+        Self::finish_content_write(&mut account_metadata, None, content_len);
+        account_metadata.set_checksum(Some(checksum));
+        account_metadata.record_activity(slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        DataAccountEvent::Updated {
+            data_account: *data_account.key,
+            offset: args.offset,
+            len: args.len,
+            revision: account_metadata.revision(),
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_migrate_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: MigrateMetadataArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("MigrateMetadata");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority and metadata_account are writable
+        require_writable(&[authority, metadata_account])?;
+
+        // ensure data_account and metadata_account are owned by the data program
+        require_owner(data_account, program_id)?;
+        require_owner(metadata_account, program_id)?;
+
+        // ensure length is not 0
+        if metadata_account.data_is_empty() {
+            return Err(DataAccountError::NoAccountLength.into());
+        }
+
+        // ensure the metadata is of a known layout
+        let versioned_metadata = VersionedMetadata::unpack(&metadata_account.try_borrow_data()?)
+            .ok_or(DataAccountError::InvalidVersion)?;
+
+        // ensure data_account is being migrated by valid authority
+        if versioned_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the metadata_account corresponds to the data_account
+        require_pda_with_bump(
+            metadata_account,
+            &[
+                PDA_SEED,
+                data_account.key.as_ref(),
+                &[versioned_metadata.bump_seed()],
+            ],
+            program_id,
+        )?;
+
+        let from_version = versioned_metadata.version();
+        if from_version == DATA_VERSION {
+            log_kv!(
+                args.debug,
+                "metadata already current",
+                data_account = data_account.key,
+                version = from_version,
+            );
+            return Ok(());
+        }
+
+        let account_metadata = versioned_metadata.into_current(data_account.data_len() as u64);
+
+        // grow the metadata_account to fit the current layout
+        if metadata_account.data_len() < METADATA_SIZE {
+            Self::realloc_account(
+                authority,
+                metadata_account,
+                system_program,
+                METADATA_SIZE,
+                0,
+            )?;
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        log_kv!(
+            args.debug,
+            "migrate metadata",
+            data_account = data_account.key,
+            from_version = from_version,
+            to_version = DATA_VERSION,
+        );
+
+        Ok(())
+    }
+
+    fn process_attest(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: AttestArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("Attest");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let attester = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure attester is signer
+        require_signer(&[attester])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        // ensure data_account is owned by the data program
+        require_owner(data_account, program_id)?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the content is being attested by the authority or its automation delegate
+        // and only the authority changes how often it must be attested
+        let is_authority = account_metadata.authority() == attester.key;
+        let is_delegate = account_metadata
+            .automation()
+            .map_or(false, |automation| automation.delegate == *attester.key);
+        if !(is_authority || is_delegate) || (args.interval_slots.is_some() && !is_authority) {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure enough compute is left to hash the content
+        let content_len = (account_metadata.content_len() as usize).min(data_account.data_len());
+        check_compute(hash_cost(content_len))?;
+
+        // ensure the content is still what is being attested
+        if hash(&data_account.data.borrow()[..content_len]).to_bytes() != args.hash {
+            return Err(DataAccountError::AttestationMismatch.into());
+        }
+
+        let slot = Clock::get()?.slot;
+        let interval_slots = args.interval_slots.unwrap_or_else(|| {
+            account_metadata
+                .attestation()
+                .map_or(0, |attestation| attestation.interval_slots)
+        });
+        account_metadata.set_attestation(Some(Attestation {
+            hash: args.hash,
+            slot,
+            interval_slots,
+        }));
+        if is_authority {
+            account_metadata.record_activity(slot);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // emit an attest event so the attestation history can be audited from logs alone
+        sol_log_data(&[b"attest", &args.hash, &slot.to_le_bytes()]);
+
+        log_kv!(
+            args.debug,
+            "attest",
+            data_account = data_account.key,
+            attester = attester.key,
+            slot = slot,
+            interval_slots = interval_slots,
+        );
+
+        Ok(())
+    }
+
+    fn process_set_freeze_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetFreezeAuthorityArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetFreezeAuthority");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure freeze_authority is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the authority can't lift a freeze by replacing the freeze_authority
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+
+        log_kv!(
+            args.debug,
+            "set_freeze_authority",
+            data_account = data_account.key,
+            freeze_authority = args.freeze_authority.unwrap_or_default(),
+        );
+
+        account_metadata.set_freeze_authority(args.freeze_authority);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_freeze_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: FreezeDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("FreezeDataAccount");
+        }
+
+        Self::set_frozen(program_id, accounts, true, args.debug)
+    }
+
+    fn process_thaw_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: ThawDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("ThawDataAccount");
+        }
+
+        Self::set_frozen(program_id, accounts, false, args.debug)
+    }
+
+    /// Freezes or thaws the data_account on behalf of its freeze_authority
+    fn set_frozen(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        frozen: bool,
+        debug: LogLevel,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let freeze_authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure freeze_authority is signer
+        require_signer(&[freeze_authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the data_account is being frozen or thawed by its freeze_authority
+        if account_metadata.freeze_authority() != Some(freeze_authority.key) {
+            return Err(DataAccountError::InvalidFreezeAuthority.into());
+        }
+
+        log_kv!(
+            debug,
+            "set_frozen",
+            data_account = data_account.key,
+            freeze_authority = freeze_authority.key,
+            frozen = frozen,
+        );
+
+        account_metadata.set_frozen(frozen);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_clone_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CloneDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CloneDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let feepayer = next_account_info(accounts_iter)?;
+        let source_data_account = next_account_info(accounts_iter)?;
+        let source_metadata_account = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure feepayer and the target data_account are signer
+        require_signer(&[feepayer, data_account])?;
+
+        // ensure source data_account is owned by the data program
+        require_owner(source_data_account, program_id)?;
+
+        let source_metadata =
+            Self::load_metadata(program_id, source_data_account, source_metadata_account)?;
+
+        // ensure the content is not sealed since the copy would not carry its time lock
+        if source_metadata.time_lock().is_some() {
+            return Err(DataAccountError::Sealed.into());
+        }
+
+        // ensure enough compute is left to copy the content instead of aborting mid-clone
+        let content_len =
+            (source_metadata.content_len() as usize).min(source_data_account.data_len());
+        check_compute(copy_cost(content_len))?;
+
+        // create the target data_account of exactly the size of the content
+        let rent_exemption_amount = Rent::get()?.minimum_balance(content_len);
+        let create_account_ix = system_instruction::create_account(
+            feepayer.key,
+            data_account.key,
+            rent_exemption_amount,
+            content_len as u64,
+            program_id,
+        );
+        invoke(
+            &create_account_ix,
+            &[
+                feepayer.clone(),
+                data_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        log_kv!(
+            args.debug,
+            "create",
+            data_account = data_account.key,
+            space = content_len,
+            rent = rent_exemption_amount,
+        );
+
+        // create data_account pda to store metadata
+        let bump_seed = Self::create_metadata_account(
+            program_id,
+            feepayer,
+            data_account,
+            metadata_account,
+            system_program,
+        )?;
+
+        if args.debug.info() {
+            msg!("metadata pda created");
+        }
+
+        data_account
+            .data
+            .borrow_mut()
+            .copy_from_slice(&source_data_account.data.borrow()[..content_len]);
+
+        let mut account_metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            args.authority,
+            args.is_dynamic,
+            DATA_VERSION,
+            source_metadata.data_type().clone(),
+            bump_seed,
+        );
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        account_metadata.set_growth_policy(args.growth_policy);
+        account_metadata.set_compression(source_metadata.compression().clone());
+        account_metadata
+            .set_compression_dictionary(source_metadata.compression_dictionary().copied());
+        // the hashes of the source content also cover the copy
+        Self::finish_content_write(
+            &mut account_metadata,
+            source_metadata.content_hash().copied(),
+            content_len as u64,
+        );
+        account_metadata.set_content_keccak(source_metadata.content_keccak().copied());
+        account_metadata.set_checksum(source_metadata.checksum());
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        log_kv!(
+            args.debug,
+            "clone",
+            data_account = data_account.key,
+            source = source_data_account.key,
+            content_len = content_len,
+        );
+
+        DataAccountEvent::Initialized {
+            data_account: *data_account.key,
+            authority: args.authority,
+            space: content_len as u64,
+        }
+        .emit()?;
+        DataAccountEvent::Updated {
+            data_account: *data_account.key,
+            offset: 0,
+            len: content_len as u64,
+            revision: account_metadata.revision(),
+        }
+        .emit()?;
+
+        Ok(())
+    }
+
+    fn process_set_compression_dictionary(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: SetCompressionDictionaryArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("SetCompressionDictionary");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure compression_dictionary is being set by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the dictionary is a DICTIONARY data account of the same authority that is finalized
+        // so that the content compressed with it can always be decompressed
+        if let Some(compression_dictionary) = args.compression_dictionary.as_ref() {
+            let dictionary_account = next_account_info(accounts_iter)?;
+            let dictionary_metadata_account = next_account_info(accounts_iter)?;
+            if dictionary_account.key != compression_dictionary
+                || dictionary_account.key == data_account.key
+            {
+                return Err(DataAccountError::InvalidDictionary.into());
+            }
+            require_owner(dictionary_account, program_id)?;
+            let dictionary_metadata =
+                Self::load_metadata(program_id, dictionary_account, dictionary_metadata_account)?;
+            if dictionary_metadata.authority() != authority.key
+                || *dictionary_metadata.data_type() != DataTypeOption::DICTIONARY
+                || *dictionary_metadata.data_status() != DataStatusOption::FINALIZED
+            {
+                return Err(DataAccountError::InvalidDictionary.into());
+            }
+        }
+
+        log_kv!(
+            args.debug,
+            "set_compression_dictionary",
+            data_account = data_account.key,
+            compression_dictionary = args.compression_dictionary.unwrap_or_default(),
+        );
+
+        account_metadata.set_compression_dictionary(args.compression_dictionary);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_verify_data_step(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: VerifyDataStepArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("VerifyDataStep");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure there is a content_hash that covers the stored bytes (i.e., of uncompressed content)
+        let content_hash = *account_metadata
+            .content_hash()
+            .ok_or(DataAccountError::NotVerifiable)?;
+        if *account_metadata.compression() != CompressionOption::NONE {
+            return Err(DataAccountError::NotVerifiable.into());
+        }
+        let content_len = account_metadata.content_len() as usize;
+        if content_len > data_account.data_len() {
+            return Err(DataAccountError::NotVerifiable.into());
+        }
+
+        // ensure the window keeps the hashed bytes a multiple of the sha256 block length
+        let window = args.window as usize;
+        if window == 0 || window % BLOCK_LEN != 0 {
+            return Err(DataAccountError::InvalidVerificationWindow.into());
+        }
+
+        // continue the verification in progress, if any
+        let mut cursor = account_metadata
+            .verification_cursor()
+            .filter(|cursor| cursor.offset as usize <= content_len)
+            .cloned()
+            .unwrap_or(VerificationCursor {
+                offset: 0,
+                state: INITIAL_STATE,
+            });
+        let start = cursor.offset as usize;
+        let end = start.saturating_add(window);
+
+        let data = data_account.data.borrow();
+        if end < content_len {
+            // ensure enough compute is left to hash the window
+            check_compute(software_hash_cost(window))?;
+            sha256_update(&mut cursor.state, &data[start..end]);
+            cursor.offset = end as u64;
+
+            log_kv!(
+                args.debug,
+                "verify_step",
+                data_account = data_account.key,
+                offset = end,
+                content_len = content_len,
+            );
+
+            account_metadata.set_verification_cursor(Some(cursor));
+        } else {
+            // ensure enough compute is left to hash the rest of the content and its padding
+            check_compute(software_hash_cost(content_len - start + 2 * BLOCK_LEN))?;
+            let status =
+                if sha256_finalize(cursor.state, &data[start..content_len], content_len as u64)
+                    == content_hash
+                {
+                    SerializationStatusOption::VERIFIED
+                } else {
+                    SerializationStatusOption::FAILED
+                };
+
+            if args.debug.info() {
+                msg!("content {:?}", status);
+            }
+
+            account_metadata.set_serialization_status(status);
+            account_metadata.set_verification_cursor(None);
+        }
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_top_up_rent(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: TopUpRentArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("TopUpRent");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let payer = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure payer is signer
+        require_signer(&[payer])?;
+
+        // ensure payer and data_account are writable
+        require_writable(&[payer, data_account])?;
+
+        // ensure the data_account is initialized by the program
+        Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        let minimum_balance = Rent::get()?.minimum_balance(data_account.data_len());
+        let lamports_diff = minimum_balance.saturating_sub(data_account.lamports());
+        if lamports_diff > 0 {
+            let transfer_ix =
+                system_instruction::transfer(payer.key, data_account.key, lamports_diff);
+            invoke(
+                &transfer_ix,
+                &[payer.clone(), data_account.clone(), system_program.clone()],
+            )?;
+        }
+
+        log_kv!(
+            args.debug,
+            "top_up_rent",
+            data_account = data_account.key,
+            payer = payer.key,
+            lamports = lamports_diff,
+        );
+
+        Ok(())
+    }
+
+    fn process_write_staged(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: WriteStagedArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("WriteStaged");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let staged_buffer = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority and staged_buffer are writable
+        require_writable(&[authority, staged_buffer])?;
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data is being staged by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the staged_buffer corresponds to the data_account
+        let bump_seed = require_pda(
+            staged_buffer,
+            &[STAGED_BUFFER_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let start = args.offset as usize;
+        let end_len = start
+            .checked_add(args.data.len())
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the staged content could be committed, failing early instead of at CommitStaged
+        Self::check_content_write(&account_metadata, 0, end_len)?;
+
+        // ensure enough compute is left to copy the data instead of aborting mid-write
+        check_compute(copy_cost(args.data.len()))?;
+
+        // create the staged_buffer on the first write (or after it was committed), otherwise resize it to cover the write
+        // a reset starts the staged content over, ending at this write
+        let old_len = staged_buffer.data_len();
+        let new_len = if args.reset {
+            end_len
+        } else {
+            old_len.max(end_len)
+        };
+        if staged_buffer.lamports() == 0 {
+            let rent_exemption_amount = Rent::get()?.minimum_balance(new_len);
+            let create_staged_buffer_ix = system_instruction::create_account(
+                authority.key,
+                staged_buffer.key,
+                rent_exemption_amount,
+                new_len as u64,
+                program_id,
+            );
+            invoke_signed(
+                &create_staged_buffer_ix,
+                &[
+                    authority.clone(),
+                    staged_buffer.clone(),
+                    system_program.clone(),
+                ],
+                &[&[STAGED_BUFFER_SEED, data_account.key.as_ref(), &[bump_seed]]],
+            )?;
+        } else if new_len != old_len {
+            Self::realloc_account(authority, staged_buffer, system_program, new_len, 0)?;
+        }
+
+        {
+            let mut staged_data = staged_buffer.data.borrow_mut();
+            if args.reset {
+                // the bytes kept before the write no longer belong to the staged content
+                staged_data[..start.min(old_len)].fill(0);
+            }
+            staged_data[start..end_len].copy_from_slice(&args.data);
+        }
+
+        log_kv!(
+            args.debug,
+            "write_staged",
+            data_account = data_account.key,
+            offset = start,
+            len = args.data.len(),
+            staged_len = new_len,
+        );
+
+        Ok(())
+    }
+
+    fn process_commit_staged(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CommitStagedArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CommitStaged");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let staged_buffer = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure authority, data_account, metadata_account and staged_buffer are writable
+        require_writable(&[authority, data_account, metadata_account, staged_buffer])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data is being committed by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the content has not been updated since the staged content was based on the expected revision
+        Self::check_expected_revision(&account_metadata, args.expected_revision)?;
+
+        // ensure the staged_buffer corresponds to the data_account
+        require_pda(
+            staged_buffer,
+            &[STAGED_BUFFER_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+        if staged_buffer.data_is_empty() {
+            return Err(DataAccountError::EmptyStagedBuffer.into());
+        }
+        let staged_len = staged_buffer.data_len();
+
+        // ensure the content has not been finalized or sealed since the content was staged
+        Self::check_content_write(&account_metadata, 0, staged_len)?;
+
+        // ensure enough compute is left to swap in and audit the whole staged content
+        check_compute(copy_cost(staged_len).saturating_add(2 * hash_cost(staged_len)))?;
+
+        // ensure data_account has enough space by reallocing once if needed
+        let old_len = data_account.data_len();
+        if old_len < staged_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, staged_len);
+            Self::realloc_data_account(
+                &mut account_metadata,
+                authority,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug.info() {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        // update the metadata_account
+        let old_content_len = account_metadata.content_len() as usize;
+        Self::transition_data_status(&mut account_metadata, DataStatusOption::UPDATED)?;
+        Self::finish_content_write(&mut account_metadata, None, staged_len as u64);
+        account_metadata.set_record_count(0);
+        account_metadata.bump_generation();
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        // swap in the staged content, discarding the previous content beyond it
+        Self::copy_content(
+            data_account,
+            0,
+            old_len,
+            &staged_buffer.data.borrow(),
+            account_metadata.revision(),
+        )?;
+        Self::discard_content(data_account, staged_len, old_content_len);
+
+        log_kv!(
+            args.debug,
+            "commit_staged",
+            data_account = data_account.key,
+            len = staged_len,
+            generation = account_metadata.generation(),
+        );
+
+        // close the staged_buffer and return its lamports to the authority
+        let curr_lamports = authority.lamports();
+        **authority.lamports.borrow_mut() = curr_lamports
+            .checked_add(staged_buffer.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **staged_buffer.lamports.borrow_mut() = 0;
+        staged_buffer.realloc(0, false)?;
+
+        Ok(())
+    }
+
+    fn process_delegate_write_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: DelegateWriteAuthorityArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("DelegateWriteAuthority");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure write access is being delegated by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure the delegation does not expire right away
+        let current_slot = Clock::get()?.slot;
+        if args.expiry_slot <= current_slot {
+            return Err(DataAccountError::InvalidWriteDelegate.into());
+        }
+
+        log_kv!(
+            args.debug,
+            "delegate_write_authority",
+            data_account = data_account.key,
+            delegate = args.delegate,
+            expiry_slot = args.expiry_slot,
+        );
+
+        account_metadata.set_write_delegate(Some(WriteDelegate {
+            delegate: args.delegate,
+            expiry_slot: args.expiry_slot,
+        }));
+        account_metadata.record_activity(current_slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_revoke_delegate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RevokeDelegateArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("RevokeDelegate");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+
+        // ensure authority is signer
+        require_signer(&[authority])?;
+
+        // ensure metadata_account is writable
+        require_writable(&[metadata_account])?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure write access is being revoked by valid authority
+        if account_metadata.authority() != authority.key {
+            return Err(DataAccountError::InvalidAuthority.into());
+        }
+
+        // ensure there is a delegate to revoke, even if it has expired already
+        let write_delegate = account_metadata
+            .write_delegate()
+            .cloned()
+            .ok_or(DataAccountError::InvalidWriteDelegate)?;
+
+        log_kv!(
+            args.debug,
+            "revoke_delegate",
+            data_account = data_account.key,
+            delegate = write_delegate.delegate,
+        );
+
+        account_metadata.set_write_delegate(None);
+        account_metadata.record_activity(Clock::get()?.slot);
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_close_nft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CloseNftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CloseNftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let holder = next_account_info(accounts_iter)?;
+        let token_account = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let rent_receiver = next_account_info(accounts_iter)?;
+
+        // ensure holder is signer
+        require_signer(&[holder])?;
+
+        // ensure data_account, metadata_account and rent_receiver are writable
+        require_writable(&[data_account, metadata_account, rent_receiver])?;
+
+        // ensure the refund is not zeroed along with the closed accounts
+        if rent_receiver.key == data_account.key || rent_receiver.key == metadata_account.key {
+            return Err(DataAccountError::InvalidRentReceiver.into());
+        }
+
+        // ensure data_account is owned by the data program so its lamports can be moved
+        require_owner(data_account, program_id)?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is being closed by the current holder of its NFT
+        let mint = *account_metadata
+            .nft_mint()
+            .ok_or(DataAccountError::NotNftBound)?;
+        require_pda(data_account, &[NFT_SEED, mint.as_ref()], program_id)?;
+        if !is_token_holder(token_account, &mint, holder.key) {
+            return Err(DataAccountError::NotTokenHolder.into());
+        }
+
+        // ensure the data_account is not paused by its freeze_authority
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+
+        // ensure a close locked data_account was unlocked at least the cool-down ago
+        if let Some(close_lock) = account_metadata.close_lock() {
+            if !close_lock.is_closable(Clock::get()?.slot) {
+                return Err(DataAccountError::CloseLocked.into());
+            }
+        }
+
+        // refund the unspent donations to the donors rather than the rent_receiver
+        Self::refund_donations(
+            program_id,
+            &mut account_metadata,
+            data_account,
+            rent_receiver,
+            accounts_iter,
+        )?;
+
+        DataAccountEvent::Closed {
+            data_account: *data_account.key,
+            rent_receiver: *rent_receiver.key,
+        }
+        .emit()?;
+        log_kv!(
+            args.debug,
+            "nft_close",
+            data_account = data_account.key,
+            holder = holder.key,
+            rent_receiver = rent_receiver.key,
+            refund = metadata_account.lamports() as u128 + data_account.lamports() as u128,
+        );
+
+        // transfer metadata_account and data_account lamports to rent_receiver and reset both
+        for account in [metadata_account, data_account] {
+            let curr_lamports = rent_receiver.lamports();
+            **rent_receiver.lamports.borrow_mut() = curr_lamports
+                .checked_add(account.lamports())
+                .ok_or(DataAccountError::Overflow)?;
+            **account.lamports.borrow_mut() = 0;
+            account.data.borrow_mut().fill(0);
+        }
+
+        Ok(())
+    }
+
+    fn process_close_cnft_data_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: CloseCnftDataAccountArgs,
+    ) -> ProgramResult {
+        if args.debug.info() {
+            msg!("CloseCnftDataAccount");
+        }
+
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let merkle_tree = next_account_info(accounts_iter)?;
+        let compression_program = next_account_info(accounts_iter)?;
+        let data_account = next_account_info(accounts_iter)?;
+        let metadata_account = next_account_info(accounts_iter)?;
+        let rent_receiver = next_account_info(accounts_iter)?;
+
+        // ensure owner is signer
+        require_signer(&[owner])?;
+
+        // ensure data_account, metadata_account and rent_receiver are writable
+        require_writable(&[data_account, metadata_account, rent_receiver])?;
+
+        // ensure the refund is not zeroed along with the closed accounts
+        if rent_receiver.key == data_account.key || rent_receiver.key == metadata_account.key {
+            return Err(DataAccountError::InvalidRentReceiver.into());
+        }
+
+        // ensure data_account is owned by the data program so its lamports can be moved
+        require_owner(data_account, program_id)?;
+
+        let mut account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure the leaf is of the cNFT the data_account is bound to
+        let asset_id = *account_metadata
+            .nft_mint()
+            .ok_or(DataAccountError::NotNftBound)?;
+        if asset_id != cnft_asset_id(merkle_tree.key, args.leaf.nonce) {
+            return Err(DataAccountError::InvalidMerkleTree.into());
+        }
+        require_pda(data_account, &[CNFT_SEED, asset_id.as_ref()], program_id)?;
+
+        // refund the unspent donations to the donors rather than the rent_receiver
+        // the donations pda and the donors precede the proof nodes in the remaining accounts
+        Self::refund_donations(
+            program_id,
+            &mut account_metadata,
+            data_account,
+            rent_receiver,
+            accounts_iter,
+        )?;
+
+        // ensure data_account is being closed by the current owner of its cNFT
+        verify_owner(
+            compression_program,
+            merkle_tree,
+            accounts_iter.as_slice(),
+            &asset_id,
+            owner.key,
+            &args.leaf,
+        )?;
+
+        // ensure the data_account is not paused by its freeze_authority
+        if account_metadata.frozen() {
+            return Err(DataAccountError::Frozen.into());
+        }
+
+        // ensure a close locked data_account was unlocked at least the cool-down ago
+        if let Some(close_lock) = account_metadata.close_lock() {
+            if !close_lock.is_closable(Clock::get()?.slot) {
+                return Err(DataAccountError::CloseLocked.into());
+            }
+        }
+
+        DataAccountEvent::Closed {
+            data_account: *data_account.key,
+            rent_receiver: *rent_receiver.key,
+        }
+        .emit()?;
+        log_kv!(
+            args.debug,
+            "cnft_close",
+            data_account = data_account.key,
+            owner = owner.key,
+            rent_receiver = rent_receiver.key,
+            refund = metadata_account.lamports() as u128 + data_account.lamports() as u128,
+        );
+
+        // transfer metadata_account and data_account lamports to rent_receiver and reset both
+        for account in [metadata_account, data_account] {
+            let curr_lamports = rent_receiver.lamports();
+            **rent_receiver.lamports.borrow_mut() = curr_lamports
+                .checked_add(account.lamports())
+                .ok_or(DataAccountError::Overflow)?;
+            **account.lamports.borrow_mut() = 0;
+            account.data.borrow_mut().fill(0);
+        }
+
+        Ok(())
+    }
+
+    /// Copies data to offset of the data_account and emits the audit event of the write
+    /// (offset, length and sha256 hash of the overwritten range and sha256 hash of the data), so the history of
+    /// writes can be reconstructed and verified from logs alone, followed by the Updated event with the revision
+    /// of the content after the write
+    /// Only the part of the overwritten range within the old_len of the data_account (before any realloc) existed
+    fn copy_content(
+        data_account: &AccountInfo,
+        offset: usize,
+        old_len: usize,
+        data: &[u8],
+        revision: u64,
+    ) -> ProgramResult {
+        let end = offset + data.len();
+        let overwritten = offset.min(old_len)..end.min(old_len);
+        let overwritten_len = overwritten.len() as u64;
+        let before_hash = hash(&data_account.data.borrow()[overwritten]).to_bytes();
+
+        data_account.data.borrow_mut()[offset..end].copy_from_slice(data);
+
+        sol_log_data(&[
+            b"audit",
+            &(offset as u64).to_le_bytes(),
+            &overwritten_len.to_le_bytes(),
+            &before_hash,
+            &hash(data).to_bytes(),
+        ]);
+        DataAccountEvent::Updated {
+            data_account: *data_account.key,
+            offset: offset as u64,
+            len: data.len() as u64,
+            revision,
+        }
+        .emit()
+    }
+
+    /// Zeroes the content discarded between content_len and old_content_len that is still within the data_account
+    /// and emits the truncate event of the discarded length
+    fn discard_content(data_account: &AccountInfo, content_len: usize, old_content_len: usize) {
+        if old_content_len <= content_len {
+            return;
+        }
+        let tail_end = old_content_len.min(data_account.data_len());
+        if tail_end > content_len {
+            data_account.data.borrow_mut()[content_len..tail_end].fill(0);
+        }
+        sol_log_data(&[
+            b"truncate",
+            &((old_content_len - content_len) as u64).to_le_bytes(),
+        ]);
+    }
+
+    /// Writes data at offset of the data_account on behalf of the verified payer (e.g., the holder of its NFT),
+    /// who funds any growth, and returns the new content_len
+    /// It emits the same audit event as UpdateDataAccount and clears the content_hash
+    fn write_content<'a>(
+        account_metadata: &mut DataAccountMetadata,
+        holder: &AccountInfo<'a>,
+        data_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        data: &[u8],
+        offset: u64,
+        truncate: bool,
+    ) -> Result<usize, ProgramError> {
+        let start = offset as usize;
+        let end_len = start
+            .checked_add(data.len())
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure the content is neither finalized nor sealed and the drop box region is neither overwritten nor truncated
+        Self::check_content_write(account_metadata, start, end_len)?;
+        if truncate {
+            Self::check_content_write(
+                account_metadata,
+                end_len,
+                account_metadata.content_len() as usize,
+            )?;
+        }
+
+        // ensure enough compute is left to copy and audit the data instead of aborting mid-write
+        check_compute(copy_cost(data.len()).saturating_add(2 * hash_cost(data.len())))?;
+
+        // ensure data_account has enough space by reallocing if needed
+        let old_len = data_account.data_len();
+        if old_len < end_len {
+            if !account_metadata.dynamic() {
+                return Err(DataAccountError::InsufficientSpace.into());
+            }
+            let new_len = account_metadata.growth_policy().grow(old_len, end_len);
+            Self::realloc_data_account(
+                account_metadata,
+                holder,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+        }
+
+        // the content ends at the write only if it is explicitly truncated, otherwise it can only grow
+        let old_content_len = account_metadata.content_len() as usize;
+        let content_len = if truncate {
+            end_len
+        } else {
+            old_content_len.max(end_len)
+        };
+
+        // the previous content_hash no longer covers the content
+        Self::transition_data_status(account_metadata, DataStatusOption::UPDATED)?;
+        Self::finish_content_write(account_metadata, None, content_len as u64);
+
+        Self::copy_content(
+            data_account,
+            start,
+            old_len,
+            data,
+            account_metadata.revision(),
+        )?;
+
+        // discard the truncated content that is still within the data_account
+        if old_content_len > content_len {
+            Self::discard_content(data_account, content_len, old_content_len);
+            // the truncated content is no longer made of the appended records
+            account_metadata.set_record_count(0);
+        }
+
+        Ok(content_len)
+    }
+
+    /// Records the content_hash and content_len of the content just written and bumps the revision
+    /// Every instruction that changes the content goes through here so the revision never misses a write
+    fn finish_content_write(
+        account_metadata: &mut DataAccountMetadata,
+        content_hash: Option<[u8; 32]>,
+        content_len: u64,
+    ) {
+        account_metadata.set_content_hash(content_hash);
+        account_metadata.set_content_len(content_len);
+        account_metadata.bump_revision();
+    }
+
+    /// Deserializes the metadata of the metadata_account without checking the data_status
+    /// Ensures the metadata_account is owned by the data program and of the current layout
+    pub(crate) fn read_metadata(
+        program_id: &Pubkey,
+        metadata_account: &AccountInfo,
+    ) -> Result<DataAccountMetadata, ProgramError> {
+        // ensure length is not 0
+        if metadata_account.data_is_empty() {
+            return Err(DataAccountError::NoAccountLength.into());
+        }
+
+        // ensure metadata_account is owned by the data program
+        require_owner(metadata_account, program_id)?;
+
+        // ensure the metadata is not of a layout that needs to be migrated first
+        if VersionedMetadata::version_of(&metadata_account.try_borrow_data()?) < Some(DATA_VERSION)
+        {
+            return Err(DataAccountError::OutdatedMetadata.into());
+        }
+
+        Ok(try_from_slice_unchecked::<DataAccountMetadata>(
+            &metadata_account.try_borrow_data()?,
+        )?)
+    }
+
+    /// Deserializes the metadata of the data_account
+    /// Ensures the metadata_account is initialized and corresponds to the data_account
+    pub(crate) fn load_metadata(
+        program_id: &Pubkey,
+        data_account: &AccountInfo,
+        metadata_account: &AccountInfo,
+    ) -> Result<DataAccountMetadata, ProgramError> {
+        let account_metadata = Self::read_metadata(program_id, metadata_account)?;
+
+        // ensure data_account is initialized
+        if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
+            return Err(DataAccountError::NotInitialized.into());
+        }
+
+        // ensure data_account is not archived
+        if *account_metadata.data_status() == DataStatusOption::ARCHIVED {
+            return Err(DataAccountError::Archived.into());
+        }
+
+        // ensure the metadata_account corresponds to the data_account
+        require_pda_with_bump(
+            metadata_account,
+            &[
+                PDA_SEED,
+                data_account.key.as_ref(),
+                &[account_metadata.bump_seed()],
+            ],
+            program_id,
+        )?;
+
+        Ok(account_metadata)
+    }
+
+    /// Ensures the user of the data_account has co-signed when their consent is required
+    /// The user account is expected as the next account unless the user is the signer itself
+    fn check_user_consent<'a, 'b: 'a>(
+        account_metadata: &DataAccountMetadata,
+        signer: &AccountInfo<'b>,
+        accounts_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    ) -> ProgramResult {
+        if !account_metadata.require_user_consent() || account_metadata.user() == Some(signer.key) {
+            return Ok(());
+        }
+
+        let user = next_account_info(accounts_iter)?;
+        if account_metadata.user() != Some(user.key) {
+            return Err(DataAccountError::InvalidUser.into());
+        }
+        require_signer(&[user])?;
+
+        Ok(())
+    }
+
+    /// Refunds the unspent donated lamports of the data_account to its top donors pro rata and closes its donations pda
+    /// The donations pda and the top donors (in the order of the table) are expected as the next accounts
+    /// unless the data_account never received a donation
+    fn refund_donations<'a>(
+        program_id: &Pubkey,
+        account_metadata: &mut DataAccountMetadata,
+        data_account: &AccountInfo<'a>,
+        rent_receiver: &AccountInfo<'a>,
+        accounts_iter: &mut std::slice::Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        let donations_account = match accounts_iter.as_slice().first() {
+            Some(account) if account.owner == program_id => next_account_info(accounts_iter)?,
+            _ if account_metadata.donated_lamports() == 0 => return Ok(()),
+            _ => return Err(DataAccountError::UnrefundedDonations.into()),
+        };
+
+        // ensure the donations_account corresponds to the data_account and is writable
+        require_pda(
+            donations_account,
+            &[DONATIONS_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+        require_writable(&[donations_account])?;
+
+        let donations =
+            try_from_slice_unchecked::<DonationsTable>(&donations_account.try_borrow_data()?)?;
+
+        // split the unspent donations in proportion to the donations of the top donors
+        let refund = account_metadata
+            .donated_lamports()
+            .min(data_account.lamports());
+        let top_total = donations
+            .top
+            .iter()
+            .map(|donation| donation.amount as u128)
+            .sum::<u128>();
+        let mut remaining = refund;
+        for (i, donation) in donations.top.iter().enumerate() {
+            let donor = next_account_info(accounts_iter)?;
+            if *donor.key != donation.donor {
+                return Err(DataAccountError::UnrefundedDonations.into());
+            }
+            require_writable(&[donor])?;
+
+            // the last donor is refunded the rounding remainder
+            let amount = if i + 1 == donations.top.len() {
+                remaining
+            } else {
+                (refund as u128 * donation.amount as u128 / top_total) as u64
+            };
+            remaining -= amount;
+
+            let donor_lamports = donor.lamports();
+            **donor.lamports.borrow_mut() = donor_lamports
+                .checked_add(amount)
+                .ok_or(DataAccountError::Overflow)?;
+            let data_lamports = data_account.lamports();
+            **data_account.lamports.borrow_mut() = data_lamports - amount;
+        }
+        account_metadata.set_donated_lamports(0);
+
+        // transfer donations_account lamports to rent_receiver and reset donations_account
+        let curr_lamports = rent_receiver.lamports();
+        **rent_receiver.lamports.borrow_mut() = curr_lamports
+            .checked_add(donations_account.lamports())
+            .ok_or(DataAccountError::Overflow)?;
+        **donations_account.lamports.borrow_mut() = 0;
+        donations_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    /// Reallocs the data_account to new_len like realloc_account and records the realloc in its metadata
+    fn realloc_data_account<'a>(
+        account_metadata: &mut DataAccountMetadata,
+        payer: &AccountInfo<'a>,
+        data_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        new_len: usize,
+    ) -> ProgramResult {
+        let rent_paid = Self::realloc_account(
+            payer,
+            data_account,
+            system_program,
+            new_len,
+            account_metadata.donated_lamports(),
+        )?;
+        account_metadata.record_realloc(rent_paid);
+        // growth is paid out of the donated lamports first
+        let spare_lamports = data_account
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_len));
+        account_metadata
+            .set_donated_lamports(account_metadata.donated_lamports().min(spare_lamports));
+        Ok(())
+    }
+
+    /// Reallocs the account to new_len keeping it rent-exempt and returns the lamports of rent paid into it
+    /// The authority funds any growth and is refunded any excess lamports beyond the reserved_lamports on shrink
+    fn realloc_account<'a>(
+        authority: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        new_len: usize,
+        reserved_lamports: u64,
+    ) -> Result<u64, ProgramError> {
+        let old_len = account.data_len();
+        let new_minimum_balance = Rent::get()?.minimum_balance(new_len);
+        let lamports_diff = if old_len < new_len {
+            new_minimum_balance.saturating_sub(account.lamports())
+        } else {
+            account
+                .lamports()
+                .saturating_sub(new_minimum_balance.saturating_add(reserved_lamports))
+        };
+
+        if old_len < new_len {
+            let transfer_ix =
+                system_instruction::transfer(authority.key, account.key, lamports_diff);
+            invoke(
+                &transfer_ix,
+                &[authority.clone(), account.clone(), system_program.clone()],
+            )?;
+        } else {
+            let authority_lamports = authority.lamports();
+            **authority.lamports.borrow_mut() = authority_lamports
+                .checked_add(lamports_diff)
+                .ok_or(DataAccountError::Overflow)?;
+            let account_lamports = account.lamports();
+            **account.lamports.borrow_mut() = account_lamports - lamports_diff;
+        }
+
+        account.realloc(new_len, false)?;
+        Ok(if old_len < new_len { lamports_diff } else { 0 })
+    }
+
+    /// Creates the metadata pda of the data_account and returns its bump seed
+    fn create_metadata_account<'a>(
+        program_id: &Pubkey,
+        feepayer: &AccountInfo<'a>,
+        data_account: &AccountInfo<'a>,
+        metadata_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<u8, ProgramError> {
+        // ensure the pda is valid
+        let bump_seed = require_pda(
+            metadata_account,
+            &[PDA_SEED, data_account.key.as_ref()],
+            program_id,
+        )?;
+        // create pda account
+        let rent_exemption_amount = Rent::get()?.minimum_balance(METADATA_SIZE);
+        let create_pda_ix = system_instruction::create_account(
+            feepayer.key,
+            metadata_account.key,
+            rent_exemption_amount,
+            METADATA_SIZE as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_pda_ix,
+            &[
+                feepayer.clone(),
+                data_account.clone(),
+                metadata_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[PDA_SEED, data_account.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        Ok(bump_seed)
+    }
 }
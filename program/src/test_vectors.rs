@@ -0,0 +1,973 @@
+//! Canonical borsh encodings of every instruction and state struct of the program
+//! Independent clients can check their encoders and decoders against tests/vectors/golden.txt
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    cnft::CnftLeaf,
+    commit_reveal::Commitment,
+    compressed::MetadataTree,
+    crdt::{CounterEntry, CrdtDelta, CrdtKindOption, CrdtState},
+    events::DataAccountEvent,
+    instruction::DataAccountInstruction,
+    oracle::SnapshotHeader,
+    state::{
+        AddWriterArgs, AppendChunkArgs, AppendFramedRecordArgs, ApplyDeltaArgs,
+        ArchiveDataAccountArgs, ArchiveInfo, AttestArgs, Attestation, AutomatedUpdateArgs,
+        AutomationConfig, BeginUploadArgs, ClaimDormantAuthorityArgs, CloneDataAccountArgs,
+        CloseCnftDataAccountArgs, CloseCompressedDataAccountArgs, CloseDataAccountArgs, CloseLock,
+        CloseNftDataAccountArgs, CommitStagedArgs, CommitUploadArgs, CommitValueArgs,
+        CompressionOption, CreateSessionArgs, DataAccountMetadata, DataAccountMetadataV0,
+        DataStatusOption, DataTypeOption, DeadManSwitch, DelegateWriteAuthorityArgs, DonateArgs,
+        Donation, DonationsTable, DropBoxConfig, DropBoxMessage, ExpireCommitmentArgs,
+        ExpireDataAccountArgs, Expiry, FinalizeDataAccountArgs, FlushStagingArgs,
+        FreezeDataAccountArgs, GovernanceConfig, GrowthPolicyOption, HotRegion,
+        InitializeCnftDataAccountArgs, InitializeCompressedDataAccountArgs, InitializeCrdtArgs,
+        InitializeDataAccountArgs, InitializeDataAccountPdaArgs, InitializeMetadataTreeArgs,
+        InitializeNftDataAccountArgs, InitializeUserDataAccountArgs, LicenseOption, LockCloseArgs,
+        LogLevel, MigrateMetadataArgs, PostToDropBoxArgs, RemoveWriterArgs, ResizeDataAccountArgs,
+        RestoreFromArchiveArgs, RevealContentArgs, RevealValueArgs, RevisionTag,
+        RevokeDelegateArgs, RevokeSessionArgs, ScrubFillOption, ScrubRangeArgs, SealContentArgs,
+        SerializationStatusOption, Session, SessionInstructionOption, SessionRange, SessionScope,
+        SetAllowlistRootArgs, SetAutomationArgs, SetCompressionDictionaryArgs,
+        SetDeadManSwitchArgs, SetDropBoxArgs, SetExpiryArgs, SetFreezeAuthorityArgs,
+        SetGovernanceArgs, SetHotRegionArgs, SetLicenseArgs, SetNotificationHintArgs,
+        SnapshotPriceFeedArgs, StageUpdateArgs, StagedWrite, TagRevisionArgs, ThawDataAccountArgs,
+        TimeLock, TopUpRentArgs, UnlockCloseArgs, UpdateCnftDataAccountArgs,
+        UpdateCompressedDataAccountArgs, UpdateDataAccountArgs, UpdateDataAccountAuthorityArgs,
+        UpdateDataAccountCompressedArgs, UpdateNftDataAccountArgs, UpdateSegment, UploadProgress,
+        ValidateDataAccountArgs, VerificationCursor, VerifyAllowlistInclusionArgs,
+        VerifyDataAccountArgs, VerifyDataStepArgs, WriteDelegate, WriteStagedArgs, WriterSequence,
+        DATA_VERSION,
+    },
+};
+
+/// Named canonical encoding of a sample value
+#[derive(PartialEq, Debug, Clone)]
+pub struct TestVector {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl TestVector {
+    /// Get the golden file line of the vector (name followed by the lowercase hex encoding)
+    pub fn to_line(&self) -> String {
+        let hex: String = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("{} {}", self.name, hex)
+    }
+}
+
+/// Renders the vectors in the golden file format (one vector per line)
+pub fn to_golden(vectors: &[TestVector]) -> String {
+    vectors
+        .iter()
+        .map(|vector| vector.to_line() + "\n")
+        .collect()
+}
+
+/// Deterministic sample pubkey
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Encodes the value and ensures it decodes back to the same bytes
+fn vector<T: BorshSerialize + BorshDeserialize>(
+    name: &str,
+    value: &T,
+) -> Result<TestVector, std::io::Error> {
+    let bytes = value.try_to_vec()?;
+    if T::try_from_slice(&bytes)?.try_to_vec()? != bytes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} does not round-trip", name),
+        ));
+    }
+    Ok(TestVector {
+        name: name.to_string(),
+        bytes,
+    })
+}
+
+/// Metadata as written by InitializeDataAccount
+fn default_metadata() -> DataAccountMetadata {
+    DataAccountMetadata::new(
+        DataStatusOption::INITIALIZED,
+        SerializationStatusOption::UNVERIFIED,
+        key(1),
+        true,
+        DATA_VERSION,
+        DataTypeOption::CUSTOM,
+        255,
+    )
+}
+
+/// Metadata with every optional field set
+fn full_metadata() -> DataAccountMetadata {
+    let mut metadata = default_metadata();
+    metadata.set_data_status(DataStatusOption::COMMITTED);
+    metadata.set_data_type(DataTypeOption::JSON);
+    metadata.set_user(Some(key(2)));
+    metadata.set_require_user_consent(true);
+    metadata.set_compression(CompressionOption::DEFLATE);
+    metadata.set_content_hash(Some([3; 32]));
+    // set after the content_hash which resets it
+    metadata.set_serialization_status(SerializationStatusOption::VERIFIED);
+    metadata.set_content_len(1024);
+    metadata.set_growth_policy(GrowthPolicyOption::STEP(256));
+    metadata.set_hot_region(Some(HotRegion { offset: 8, len: 32 }));
+    metadata.set_archive(Some(archive_info()));
+    metadata.set_governance(Some(GovernanceConfig {
+        governance_program: key(5),
+        governance: key(6),
+    }));
+    metadata.set_automation(Some(AutomationConfig {
+        delegate: key(7),
+        min_interval_slots: 150,
+        last_heartbeat_slot: 1_000,
+    }));
+    metadata.set_dead_man_switch(Some(DeadManSwitch {
+        successor: key(8),
+        inactivity_timeout_slots: 216_000,
+        last_active_slot: 2_000,
+    }));
+    metadata.set_drop_box(Some(drop_box_config()));
+    metadata.set_time_lock(Some(TimeLock {
+        reveal_after_slot: 3_000,
+        plaintext_hash: Some([9; 32]),
+    }));
+    metadata.set_license(Some(LicenseOption::SPDX("CC-BY-4.0".to_string())));
+    metadata.set_close_lock(Some(CloseLock {
+        cooldown_slots: 432_000,
+        unlocked_at: Some(4_000),
+    }));
+    metadata.set_content_keccak(Some([10; 32]));
+    metadata.set_record_count(12);
+    metadata.set_upload(Some(UploadProgress {
+        total_len: 2_048,
+        checksum: [11; 32],
+        written: 1_024,
+    }));
+    metadata.set_append_only(true);
+    metadata.set_writer_sequences(vec![WriterSequence {
+        writer: key(30),
+        sequence: 3,
+    }]);
+    metadata.set_nft_mint(Some(key(31)));
+    metadata.set_writers(vec![key(34)]);
+    metadata.set_expiry(Some(Expiry {
+        expires_at: 1_800_000_000,
+        rent_receiver: key(40),
+    }));
+    metadata.set_notification_hint(Some([41; 32]));
+    metadata.set_checksum(Some(0xcbf4_3926));
+    metadata.set_attestation(Some(Attestation {
+        hash: [46; 32],
+        slot: 5_000,
+        interval_slots: 9_000,
+    }));
+    metadata.record_realloc(1_000_000);
+    metadata.record_realloc(0);
+    metadata.set_freeze_authority(Some(key(45)));
+    metadata.set_frozen(true);
+    metadata.set_compression_dictionary(Some(key(49)));
+    metadata.set_verification_cursor(Some(VerificationCursor {
+        offset: 512,
+        state: [50; 8],
+    }));
+    metadata.bump_generation();
+    metadata.bump_revision();
+    metadata.bump_revision();
+    metadata.set_write_delegate(Some(WriteDelegate {
+        delegate: key(51),
+        expiry_slot: 6_000,
+    }));
+    metadata.set_donated_lamports(2_000_000);
+    metadata
+}
+
+fn archive_info() -> ArchiveInfo {
+    ArchiveInfo {
+        hash: [4; 32],
+        size: 1024,
+        archived_at: 500,
+        pointer: Some("ar://archive".to_string()),
+    }
+}
+
+fn drop_box_config() -> DropBoxConfig {
+    DropBoxConfig {
+        start: 512,
+        fee_per_byte: 10,
+        max_message_len: 280,
+        min_interval_slots: 25,
+    }
+}
+
+fn session_scope() -> SessionScope {
+    SessionScope {
+        ranges: vec![
+            SessionRange { offset: 0, len: 64 },
+            SessionRange {
+                offset: 128,
+                len: 32,
+            },
+        ],
+        instructions: vec![
+            SessionInstructionOption::UPDATE,
+            SessionInstructionOption::STAGE,
+        ],
+    }
+}
+
+/// Canonical encodings of the state structs
+pub fn state_vectors() -> Result<Vec<TestVector>, std::io::Error> {
+    let mut tree = MetadataTree::new(key(10), 3);
+    tree.append([11; 32]);
+
+    Ok(vec![
+        vector("state/DataAccountMetadata/default", &default_metadata())?,
+        vector("state/DataAccountMetadata/full", &full_metadata())?,
+        vector("state/GrowthPolicyOption/EXACT", &GrowthPolicyOption::EXACT)?,
+        vector(
+            "state/GrowthPolicyOption/STEP",
+            &GrowthPolicyOption::STEP(256),
+        )?,
+        vector(
+            "state/GrowthPolicyOption/FACTOR",
+            &GrowthPolicyOption::FACTOR(150),
+        )?,
+        vector("state/LogLevel/VERBOSE", &LogLevel::VERBOSE)?,
+        vector(
+            "state/LicenseOption/POINTER",
+            &LicenseOption::POINTER("https://example.com/terms".to_string()),
+        )?,
+        vector("state/ArchiveInfo", &archive_info())?,
+        vector("state/DropBoxConfig", &drop_box_config())?,
+        vector(
+            "state/DropBoxMessage",
+            &DropBoxMessage {
+                sender: key(12),
+                slot: 4_000,
+                message: b"hello".to_vec(),
+            },
+        )?,
+        vector(
+            "state/DonationsTable",
+            &DonationsTable {
+                total: 3_000,
+                top: vec![
+                    Donation {
+                        donor: key(13),
+                        amount: 2_000,
+                    },
+                    Donation {
+                        donor: key(14),
+                        amount: 1_000,
+                    },
+                ],
+            },
+        )?,
+        vector(
+            "state/StagedWrite",
+            &StagedWrite {
+                offset: 16,
+                data: vec![1, 2, 3],
+            },
+        )?,
+        vector(
+            "state/RevisionTag",
+            &RevisionTag {
+                label: "v1.0.0".to_string(),
+                slot: 5_000,
+                content_len: 1024,
+                hash: [15; 32],
+            },
+        )?,
+        vector(
+            "state/SnapshotHeader",
+            &SnapshotHeader {
+                slot: 6_000,
+                unix_timestamp: 1_700_000_000,
+                feed: key(16),
+            },
+        )?,
+        vector(
+            "state/Commitment",
+            &Commitment {
+                committer: key(17),
+                hash: [18; 32],
+                reveal_deadline_slot: 7_000,
+                revealed: Some(b"secret".to_vec()),
+            },
+        )?,
+        vector("state/MetadataTree", &tree)?,
+        vector(
+            "state/Session",
+            &Session {
+                data_account: key(28),
+                session_key: key(27),
+                expiry_slot: 8_000,
+                scope: session_scope(),
+            },
+        )?,
+        vector(
+            "state/CrdtState/GSet",
+            &CrdtState::GSet(vec![b"alice".to_vec(), b"bob".to_vec()]),
+        )?,
+        vector(
+            "state/CrdtState/PNCounter",
+            &CrdtState::PNCounter(vec![CounterEntry {
+                writer: key(29),
+                increments: 5,
+                decrements: 2,
+            }]),
+        )?,
+        vector(
+            "state/DataAccountEvent/Updated",
+            &DataAccountEvent::Updated {
+                data_account: key(43),
+                offset: 8,
+                len: 3,
+                revision: 5,
+            },
+        )?,
+        vector(
+            "state/DataAccountMetadataV0",
+            &DataAccountMetadataV0 {
+                data_status: DataStatusOption::INITIALIZED,
+                serialization_status: SerializationStatusOption::UNVERIFIED,
+                authority: key(44),
+                is_dynamic: true,
+                data_version: 0,
+                data_type: DataTypeOption::JSON,
+                bump_seed: 254,
+            },
+        )?,
+    ])
+}
+
+/// Canonical encodings of the instructions
+pub fn instruction_vectors() -> Result<Vec<TestVector>, std::io::Error> {
+    use DataAccountInstruction::*;
+
+    let instructions = vec![
+        (
+            "InitializeDataAccount",
+            InitializeDataAccount(InitializeDataAccountArgs {
+                authority: key(1),
+                space: 1024,
+                is_dynamic: true,
+                is_created: false,
+                growth_policy: GrowthPolicyOption::FACTOR(150),
+                append_only: true,
+                idempotent: true,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UpdateDataAccount",
+            UpdateDataAccount(UpdateDataAccountArgs {
+                data_type: DataTypeOption::JSON,
+                data: b"{\"a\":1}".to_vec(),
+                offset: 0,
+                realloc_down: false,
+                truncate: true,
+                commit_flag: true,
+                verify_flag: true,
+                compression: CompressionOption::NONE,
+                content_hash: Some([3; 32]),
+                sequence: Some(7),
+                segments: vec![UpdateSegment {
+                    offset: 16,
+                    data: vec![9, 9],
+                }],
+                expected_revision: Some(4),
+                debug: LogLevel::INFO,
+            }),
+        ),
+        (
+            "UpdateDataAccountAuthority",
+            UpdateDataAccountAuthority(UpdateDataAccountAuthorityArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CloseDataAccount",
+            CloseDataAccount(CloseDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeUserDataAccount",
+            InitializeUserDataAccount(InitializeUserDataAccountArgs {
+                user: key(2),
+                name: "profile".to_string(),
+                space: 256,
+                is_dynamic: false,
+                require_user_consent: true,
+                growth_policy: GrowthPolicyOption::EXACT,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetAllowlistRoot",
+            SetAllowlistRoot(SetAllowlistRootArgs {
+                root: [19; 32],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "VerifyAllowlistInclusion",
+            VerifyAllowlistInclusion(VerifyAllowlistInclusionArgs {
+                entry: key(20).to_bytes().to_vec(),
+                proof: vec![[21; 32], [22; 32]],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SnapshotPriceFeed",
+            SnapshotPriceFeed(SnapshotPriceFeedArgs {
+                feed_offset: 208,
+                feed_len: 32,
+                offset: 0,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "StageUpdate",
+            StageUpdate(StageUpdateArgs {
+                offset: 16,
+                data: vec![1, 2, 3],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "FlushStaging",
+            FlushStaging(FlushStagingArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetHotRegion",
+            SetHotRegion(SetHotRegionArgs {
+                hot_region: Some(HotRegion { offset: 8, len: 32 }),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ArchiveDataAccount",
+            ArchiveDataAccount(ArchiveDataAccountArgs {
+                pointer: Some("ar://archive".to_string()),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RestoreFromArchive",
+            RestoreFromArchive(RestoreFromArchiveArgs {
+                offset: 0,
+                data: vec![4, 5, 6],
+                finalize: true,
+                seeds: None,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "TagRevision",
+            TagRevision(TagRevisionArgs {
+                label: "v1.0.0".to_string(),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetGovernance",
+            SetGovernance(SetGovernanceArgs {
+                governance: Some(GovernanceConfig {
+                    governance_program: key(5),
+                    governance: key(6),
+                }),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetAutomation",
+            SetAutomation(SetAutomationArgs {
+                delegate: Some(key(7)),
+                min_interval_slots: 150,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "AutomatedUpdate",
+            AutomatedUpdate(AutomatedUpdateArgs {
+                offset: 32,
+                data: vec![7, 8],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ValidateDataAccount",
+            ValidateDataAccount(ValidateDataAccountArgs {
+                authority: Some(key(1)),
+                data_version: DATA_VERSION,
+                require_committed: true,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetDeadManSwitch",
+            SetDeadManSwitch(SetDeadManSwitchArgs {
+                successor: Some(key(8)),
+                inactivity_timeout_slots: 216_000,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ClaimDormantAuthority",
+            ClaimDormantAuthority(ClaimDormantAuthorityArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetDropBox",
+            SetDropBox(SetDropBoxArgs {
+                drop_box: Some(drop_box_config()),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "PostToDropBox",
+            PostToDropBox(PostToDropBoxArgs {
+                message: b"hello".to_vec(),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "Donate",
+            Donate(DonateArgs {
+                amount: 1_000_000,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SealContent",
+            SealContent(SealContentArgs {
+                reveal_after_slot: 3_000,
+                plaintext_hash: Some([9; 32]),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RevealContent",
+            RevealContent(RevealContentArgs {
+                plaintext: b"plaintext".to_vec(),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CommitValue",
+            CommitValue(CommitValueArgs {
+                hash: [18; 32],
+                reveal_deadline_slot: 7_000,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RevealValue",
+            RevealValue(RevealValueArgs {
+                value: b"secret".to_vec(),
+                salt: [23; 32],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ExpireCommitment",
+            ExpireCommitment(ExpireCommitmentArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetLicense",
+            SetLicense(SetLicenseArgs {
+                license: Some(LicenseOption::SPDX("CC-BY-4.0".to_string())),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeMetadataTree",
+            InitializeMetadataTree(InitializeMetadataTreeArgs {
+                max_depth: 14,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeCompressedDataAccount",
+            InitializeCompressedDataAccount(InitializeCompressedDataAccountArgs {
+                authority: key(1),
+                space: 512,
+                is_dynamic: true,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UpdateCompressedDataAccount",
+            UpdateCompressedDataAccount(UpdateCompressedDataAccountArgs {
+                metadata: default_metadata(),
+                leaf_index: 0,
+                proof: vec![[24; 32], [25; 32], [26; 32]],
+                offset: 0,
+                data: vec![9, 10],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CloseCompressedDataAccount",
+            CloseCompressedDataAccount(CloseCompressedDataAccountArgs {
+                metadata: default_metadata(),
+                leaf_index: 0,
+                proof: vec![[24; 32], [25; 32], [26; 32]],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CreateSession",
+            CreateSession(CreateSessionArgs {
+                session_key: key(27),
+                expiry_slot: 8_000,
+                scope: session_scope(),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RevokeSession",
+            RevokeSession(RevokeSessionArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeCrdt",
+            InitializeCrdt(InitializeCrdtArgs {
+                kind: CrdtKindOption::PNCOUNTER,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ApplyDelta",
+            ApplyDelta(ApplyDeltaArgs {
+                delta: CrdtDelta::Add(vec![b"alice".to_vec(), b"bob".to_vec()]),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "LockClose",
+            LockClose(LockCloseArgs {
+                cooldown_slots: 432_000,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UnlockClose",
+            UnlockClose(UnlockCloseArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "FinalizeDataAccount",
+            FinalizeDataAccount(FinalizeDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "AppendFramedRecord",
+            AppendFramedRecord(AppendFramedRecordArgs {
+                record: b"log".to_vec(),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "BeginUpload",
+            BeginUpload(BeginUploadArgs {
+                total_len: 2_048,
+                checksum: [11; 32],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "AppendChunk",
+            AppendChunk(AppendChunkArgs {
+                offset: 1_024,
+                data: vec![12, 13],
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CommitUpload",
+            CommitUpload(CommitUploadArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "VerifyDataAccount",
+            VerifyDataAccount(VerifyDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeNftDataAccount",
+            InitializeNftDataAccount(InitializeNftDataAccountArgs {
+                space: 512,
+                is_dynamic: true,
+                growth_policy: GrowthPolicyOption::STEP(256),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UpdateNftDataAccount",
+            UpdateNftDataAccount(UpdateNftDataAccountArgs {
+                data: b"nft".to_vec(),
+                offset: 4,
+                truncate: true,
+                debug: LogLevel::INFO,
+            }),
+        ),
+        (
+            "AddWriter",
+            AddWriter(AddWriterArgs {
+                writer: key(32),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RemoveWriter",
+            RemoveWriter(RemoveWriterArgs {
+                writer: key(33),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeCnftDataAccount",
+            InitializeCnftDataAccount(InitializeCnftDataAccountArgs {
+                leaf: CnftLeaf {
+                    root: [35; 32],
+                    data_hash: [36; 32],
+                    creator_hash: [37; 32],
+                    nonce: 7,
+                    index: 7,
+                    delegate: key(38),
+                },
+                space: 512,
+                is_dynamic: true,
+                growth_policy: GrowthPolicyOption::STEP(256),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UpdateCnftDataAccount",
+            UpdateCnftDataAccount(UpdateCnftDataAccountArgs {
+                leaf: CnftLeaf {
+                    root: [35; 32],
+                    data_hash: [36; 32],
+                    creator_hash: [37; 32],
+                    nonce: 7,
+                    index: 7,
+                    delegate: key(38),
+                },
+                data: b"cnft".to_vec(),
+                offset: 4,
+                truncate: false,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetExpiry",
+            SetExpiry(SetExpiryArgs {
+                expiry: Some(Expiry {
+                    expires_at: 1_700_000_000,
+                    rent_receiver: key(39),
+                }),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ExpireDataAccount",
+            ExpireDataAccount(ExpireDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetNotificationHint",
+            SetNotificationHint(SetNotificationHintArgs {
+                notification_hint: Some([42; 32]),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ResizeDataAccount",
+            ResizeDataAccount(ResizeDataAccountArgs {
+                space: 4096,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "UpdateDataAccountCompressed",
+            UpdateDataAccountCompressed(UpdateDataAccountCompressedArgs {
+                data_type: DataTypeOption::JSON,
+                data: vec![1, 2, 3],
+                offset: 8,
+                max_output_len: 1024,
+                truncate: false,
+                sequence: Some(9),
+                expected_revision: Some(3),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "InitializeDataAccountPda",
+            InitializeDataAccountPda(InitializeDataAccountPdaArgs {
+                seed: "site".to_string(),
+                space: 1024,
+                is_dynamic: true,
+                growth_policy: GrowthPolicyOption::EXACT,
+                append_only: false,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ScrubRange",
+            ScrubRange(ScrubRangeArgs {
+                offset: 8,
+                len: 4,
+                fill: ScrubFillOption::RANDOM,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "MigrateMetadata",
+            MigrateMetadata(MigrateMetadataArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "Attest",
+            Attest(AttestArgs {
+                hash: [45; 32],
+                interval_slots: Some(9_000),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetFreezeAuthority",
+            SetFreezeAuthority(SetFreezeAuthorityArgs {
+                freeze_authority: Some(key(46)),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "FreezeDataAccount",
+            FreezeDataAccount(FreezeDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "ThawDataAccount",
+            ThawDataAccount(ThawDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CloneDataAccount",
+            CloneDataAccount(CloneDataAccountArgs {
+                authority: key(47),
+                is_dynamic: true,
+                growth_policy: GrowthPolicyOption::EXACT,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "SetCompressionDictionary",
+            SetCompressionDictionary(SetCompressionDictionaryArgs {
+                compression_dictionary: Some(key(48)),
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "VerifyDataStep",
+            VerifyDataStep(VerifyDataStepArgs {
+                window: 4096,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "TopUpRent",
+            TopUpRent(TopUpRentArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "WriteStaged",
+            WriteStaged(WriteStagedArgs {
+                offset: 256,
+                data: vec![51; 4],
+                reset: true,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CommitStaged",
+            CommitStaged(CommitStagedArgs {
+                expected_revision: None,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "DelegateWriteAuthority",
+            DelegateWriteAuthority(DelegateWriteAuthorityArgs {
+                delegate: key(52),
+                expiry_slot: 7_000,
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "RevokeDelegate",
+            RevokeDelegate(RevokeDelegateArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CloseNftDataAccount",
+            CloseNftDataAccount(CloseNftDataAccountArgs {
+                debug: LogLevel::QUIET,
+            }),
+        ),
+        (
+            "CloseCnftDataAccount",
+            CloseCnftDataAccount(CloseCnftDataAccountArgs {
+                leaf: CnftLeaf {
+                    root: [35; 32],
+                    data_hash: [36; 32],
+                    creator_hash: [37; 32],
+                    nonce: 7,
+                    index: 7,
+                    delegate: key(38),
+                },
+                debug: LogLevel::QUIET,
+            }),
+        ),
+    ];
+
+    instructions
+        .iter()
+        .map(|(name, instruction)| vector(&format!("instruction/{}", name), instruction))
+        .collect()
+}
+
+/// Canonical encodings of every instruction and state struct in golden file order
+pub fn test_vectors() -> Result<Vec<TestVector>, std::io::Error> {
+    let mut vectors = instruction_vectors()?;
+    vectors.extend(state_vectors()?);
+    Ok(vectors)
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{
+    initialize_args, initialize_ix_with_args, metadata_key, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    state::{DataAccountMetadata, InitializeDataAccountArgs, UpdateDataAccountArgs},
+};
+use solana_program::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::{tokio, BanksClient, BanksClientError};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn assert_append_only_error(result: Result<(), BanksClientError>) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::AppendOnly as u32)
+        )
+    );
+}
+
+fn initialize_ix(program_id: &Pubkey, payer: &Pubkey, data_account: &Pubkey) -> Instruction {
+    let args = InitializeDataAccountArgs {
+        append_only: true,
+        ..initialize_args(payer)
+    };
+    initialize_ix_with_args(program_id, payer, data_account, args)
+}
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    offset: u64,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        offset,
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+async fn content_of(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+) -> Vec<u8> {
+    let metadata = banks_client
+        .get_account(metadata_key(program_id, data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    let account = banks_client
+        .get_account(*data_account)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data[..metadata.content_len() as usize].to_vec()
+}
+
+#[tokio::test]
+async fn test_append_only() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+        0,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"world".to_vec(),
+        5,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // overwriting the last byte of the content is rejected
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"d!".to_vec(),
+        9,
+    );
+    assert_append_only_error(try_send(&mut banks_client, &payer, ix, &[&payer]).await);
+
+    assert_eq!(
+        content_of(&mut banks_client, &program_id, &data_account.pubkey()).await,
+        b"helloworld"
+    );
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{
+    assert_error, initialize_ix, metadata_key, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        AttestArgs, Attestation, DataAccountMetadata, LogLevel, SetAutomationArgs,
+        UpdateDataAccountArgs,
+    },
+};
+use solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_test::{tokio, BanksClient};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    offset: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        offset,
+        content_hash: Some([1; 32]),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn attest_ix(
+    program_id: &Pubkey,
+    attester: &Pubkey,
+    data_account: &Pubkey,
+    hash: [u8; 32],
+    interval_slots: Option<u64>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*attester, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::Attest(AttestArgs {
+            hash,
+            interval_slots,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn set_automation_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    delegate: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::SetAutomation(SetAutomationArgs {
+            delegate: Some(*delegate),
+            min_interval_slots: 0,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+async fn attestation(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+) -> Option<Attestation> {
+    let metadata = banks_client
+        .get_account(metadata_key(program_id, data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    DataAccountMetadata::deserialize(&mut &metadata.data[..])
+        .unwrap()
+        .attestation()
+        .cloned()
+}
+
+#[test]
+fn test_attestation_lapses_after_interval() {
+    let attestation = Attestation {
+        hash: [0; 32],
+        slot: 100,
+        interval_slots: 50,
+    };
+    assert!(!attestation.is_lapsed(100));
+    assert!(!attestation.is_lapsed(150));
+    assert!(attestation.is_lapsed(151));
+
+    let attestation = Attestation {
+        interval_slots: 0,
+        ..attestation
+    };
+    assert!(!attestation.is_lapsed(u64::MAX));
+}
+
+#[tokio::test]
+async fn test_attest() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let content = b"published".to_vec();
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, 0, content.clone());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the attested hash must be the hash of the content
+    let ix = attest_ix(&program_id, &authority, &data_key, [0; 32], Some(100));
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::AttestationMismatch,
+    );
+
+    let content_hash = hash(&content).to_bytes();
+    let ix = attest_ix(&program_id, &authority, &data_key, content_hash, Some(100));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let first = attestation(&mut banks_client, &program_id, &data_key)
+        .await
+        .unwrap();
+    assert_eq!(first.hash, content_hash);
+    assert_eq!(first.interval_slots, 100);
+
+    // only the authority or its automation delegate can attest
+    let watcher = Keypair::new();
+    let ix = attest_ix(
+        &program_id,
+        &watcher.pubkey(),
+        &data_key,
+        content_hash,
+        None,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &watcher]).await,
+        DataAccountError::InvalidAuthority,
+    );
+
+    let ix = set_automation_ix(&program_id, &authority, &data_key, &watcher.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the delegate keeps the interval set by the authority and cannot change it
+    let ix = attest_ix(
+        &program_id,
+        &watcher.pubkey(),
+        &data_key,
+        content_hash,
+        Some(0),
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &watcher]).await,
+        DataAccountError::InvalidAuthority,
+    );
+    let ix = attest_ix(
+        &program_id,
+        &watcher.pubkey(),
+        &data_key,
+        content_hash,
+        None,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &watcher])
+        .await
+        .unwrap();
+    let second = attestation(&mut banks_client, &program_id, &data_key)
+        .await
+        .unwrap();
+    assert_eq!(second.interval_slots, 100);
+    assert!(second.slot >= first.slot);
+}
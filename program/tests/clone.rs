@@ -0,0 +1,121 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    content_and_metadata, initialize_ix, metadata_key, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    instruction::DataAccountInstruction,
+    state::{
+        CloneDataAccountArgs, DataStatusOption, DataTypeOption, GrowthPolicyOption, LogLevel,
+        UpdateDataAccountArgs,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data_type: DataTypeOption,
+    data: Vec<u8>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        data_type,
+        content_hash: Some([7; 32]),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn clone_ix(
+    program_id: &Pubkey,
+    feepayer: &Pubkey,
+    source: &Pubkey,
+    data_account: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*feepayer, true),
+            AccountMeta::new_readonly(*source, false),
+            AccountMeta::new_readonly(metadata_key(program_id, source), false),
+            AccountMeta::new(*data_account, true),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::CloneDataAccount(CloneDataAccountArgs {
+            authority: *feepayer,
+            is_dynamic: true,
+            growth_policy: GrowthPolicyOption::EXACT,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_clone_forks_the_content_of_a_template() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let template = Keypair::new();
+    let fork = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &template.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &template])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &template.pubkey(),
+        DataTypeOption::HTML,
+        b"<p>template</p>".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = clone_ix(
+        &program_id,
+        &payer.pubkey(),
+        &template.pubkey(),
+        &fork.pubkey(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &fork])
+        .await
+        .unwrap();
+    let (content, metadata) =
+        content_and_metadata(&mut banks_client, &program_id, &fork.pubkey()).await;
+    assert_eq!(content, b"<p>template</p>");
+    assert_eq!(*metadata.data_type(), DataTypeOption::HTML);
+    assert_eq!(*metadata.data_status(), DataStatusOption::UPDATED);
+    assert_eq!(metadata.content_len(), 15);
+    assert_eq!(metadata.content_hash(), Some(&[7; 32]));
+    assert_eq!(metadata.authority(), &payer.pubkey());
+
+    // the fork evolves independently of the template
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &fork.pubkey(),
+        DataTypeOption::HTML,
+        b"<p>forked</p>".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, _) =
+        content_and_metadata(&mut banks_client, &program_id, &template.pubkey()).await;
+    assert_eq!(content, b"<p>template</p>");
+}
@@ -0,0 +1,100 @@
+use dataaccount::{
+    cnft::{
+        cnft_asset_id, cnft_data_address, leaf_hash, verify_owner, CnftLeaf,
+        ACCOUNT_COMPRESSION_PROGRAM_ID,
+    },
+    error::DataAccountError,
+    nft::nft_data_address,
+};
+use solana_program::{account_info::AccountInfo, keccak, pubkey::Pubkey};
+
+fn leaf(nonce: u64) -> CnftLeaf {
+    CnftLeaf {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+        nonce,
+        index: nonce as u32,
+        delegate: Pubkey::new_from_array([4; 32]),
+    }
+}
+
+#[test]
+fn test_leaf_hash_matches_bubblegum_leaf_schema() {
+    let asset_id = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let leaf = leaf(7);
+
+    let expected = keccak::hashv(&[
+        &[1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        leaf.delegate.as_ref(),
+        &7u64.to_le_bytes(),
+        &[2; 32],
+        &[3; 32],
+    ])
+    .to_bytes();
+    assert_eq!(leaf_hash(&asset_id, &owner, &leaf), expected);
+
+    // the leaf of any other owner doesn't match
+    assert_ne!(leaf_hash(&asset_id, &Pubkey::new_unique(), &leaf), expected);
+}
+
+#[test]
+fn test_cnft_data_address_is_distinct_per_asset() {
+    let program_id = Pubkey::new_unique();
+    let merkle_tree = Pubkey::new_unique();
+    let asset_id = cnft_asset_id(&merkle_tree, 0);
+
+    assert_ne!(asset_id, cnft_asset_id(&merkle_tree, 1));
+    assert_ne!(
+        cnft_data_address(&asset_id, &program_id),
+        cnft_data_address(&cnft_asset_id(&merkle_tree, 1), &program_id)
+    );
+    assert_ne!(
+        cnft_data_address(&asset_id, &program_id),
+        nft_data_address(&asset_id, &program_id)
+    );
+}
+
+#[test]
+fn test_verify_owner_rejects_tree_not_owned_by_account_compression() {
+    let merkle_tree_key = Pubkey::new_unique();
+    let not_compression = Pubkey::new_unique();
+    let (mut tree_lamports, mut program_lamports) = (1, 1);
+    let (mut tree_data, mut program_data) = (vec![0; 8], vec![]);
+    let merkle_tree = AccountInfo::new(
+        &merkle_tree_key,
+        false,
+        false,
+        &mut tree_lamports,
+        &mut tree_data,
+        &not_compression,
+        false,
+        0,
+    );
+    let compression_program = AccountInfo::new(
+        &ACCOUNT_COMPRESSION_PROGRAM_ID,
+        false,
+        false,
+        &mut program_lamports,
+        &mut program_data,
+        &not_compression,
+        true,
+        0,
+    );
+
+    assert_eq!(
+        verify_owner(
+            &compression_program,
+            &merkle_tree,
+            &[],
+            &cnft_asset_id(&merkle_tree_key, 0),
+            &Pubkey::new_unique(),
+            &leaf(0),
+        )
+        .unwrap_err(),
+        DataAccountError::InvalidMerkleTree.into()
+    );
+}
@@ -0,0 +1,257 @@
+//! Fixtures shared by the BPF tests, so a change of the instruction args only touches this module
+//! Each test file uses a subset of them
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    processor::Processor,
+    state::{
+        ArchiveDataAccountArgs, CloseDataAccountArgs, CompressionOption, DataAccountMetadata,
+        DataTypeOption, FinalizeDataAccountArgs, GrowthPolicyOption, InitializeDataAccountArgs,
+        LogLevel, ScrubFillOption, ScrubRangeArgs, UpdateDataAccountArgs, PDA_SEED,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    system_program,
+};
+use solana_program_test::{processor, BanksClient, BanksClientError, ProgramTest};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+pub fn program_test(program_id: Pubkey) -> ProgramTest {
+    ProgramTest::new(
+        "dataaccount",
+        program_id,
+        processor!(Processor::process_instruction),
+    )
+}
+
+pub fn metadata_key(program_id: &Pubkey, data_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], program_id).0
+}
+
+pub async fn try_send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.sign(signers, recent_blockhash);
+    banks_client.process_transaction(transaction).await
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, error: DataAccountError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    );
+}
+
+/// Args of an empty, dynamic data_account of the authority
+pub fn initialize_args(authority: &Pubkey) -> InitializeDataAccountArgs {
+    InitializeDataAccountArgs {
+        authority: *authority,
+        space: 0,
+        is_dynamic: true,
+        is_created: false,
+        growth_policy: GrowthPolicyOption::EXACT,
+        append_only: false,
+        idempotent: false,
+        debug: LogLevel::QUIET,
+    }
+}
+
+pub fn initialize_ix_with_args(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    data_account: &Pubkey,
+    args: InitializeDataAccountArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*data_account, true),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::InitializeDataAccount(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn initialize_ix(program_id: &Pubkey, payer: &Pubkey, data_account: &Pubkey) -> Instruction {
+    initialize_ix_with_args(program_id, payer, data_account, initialize_args(payer))
+}
+
+/// Args of a plain write of data at offset 0
+pub fn update_args(data: Vec<u8>) -> UpdateDataAccountArgs {
+    UpdateDataAccountArgs {
+        data_type: DataTypeOption::CUSTOM,
+        data,
+        offset: 0,
+        realloc_down: false,
+        truncate: false,
+        commit_flag: false,
+        verify_flag: false,
+        compression: CompressionOption::NONE,
+        content_hash: None,
+        sequence: None,
+        segments: Vec::new(),
+        expected_revision: None,
+        debug: LogLevel::QUIET,
+    }
+}
+
+pub fn update_ix_with_args(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    args: UpdateDataAccountArgs,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::UpdateDataAccount(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    update_ix_with_args(program_id, authority, data_account, update_args(data))
+}
+
+pub fn close_ix_to(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    rent_receiver: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new(*rent_receiver, false),
+        ],
+        data: DataAccountInstruction::CloseDataAccount(CloseDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Closes the data_account refunding its authority
+pub fn close_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    close_ix_to(program_id, authority, data_account, authority)
+}
+
+pub fn archive_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::ArchiveDataAccount(ArchiveDataAccountArgs {
+            pointer: None,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn finalize_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::FinalizeDataAccount(FinalizeDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub fn scrub_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    offset: u64,
+    len: u64,
+    fill: ScrubFillOption,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::ScrubRange(ScrubRangeArgs {
+            offset,
+            len,
+            fill,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+pub async fn metadata(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+) -> DataAccountMetadata {
+    let metadata = banks_client
+        .get_account(metadata_key(program_id, data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap()
+}
+
+pub async fn content_and_metadata(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+) -> (Vec<u8>, DataAccountMetadata) {
+    let data = banks_client
+        .get_account(*data_account)
+        .await
+        .unwrap()
+        .unwrap();
+    (
+        data.data,
+        metadata(banks_client, program_id, data_account).await,
+    )
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{
+    finalize_ix, initialize_ix, metadata_key, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, DataTypeOption, LogLevel, SetCompressionDictionaryArgs,
+        UpdateDataAccountArgs, ZSTD_DICTIONARY_MAGIC,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data_type: DataTypeOption,
+    data: Vec<u8>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        data_type,
+        content_hash: Some([7; 32]),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn set_dictionary_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    dictionary: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(*dictionary, false),
+            AccountMeta::new_readonly(metadata_key(program_id, dictionary), false),
+        ],
+        data: DataAccountInstruction::SetCompressionDictionary(SetCompressionDictionaryArgs {
+            compression_dictionary: Some(*dictionary),
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_set_compression_dictionary_requires_a_finalized_dictionary() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let dictionary = Keypair::new();
+    let data_account = Keypair::new();
+
+    for account in [&dictionary, &data_account] {
+        let ix = initialize_ix(&program_id, &payer.pubkey(), &account.pubkey());
+        try_send(&mut banks_client, &payer, ix, &[&payer, account])
+            .await
+            .unwrap();
+    }
+    let mut content = ZSTD_DICTIONARY_MAGIC.to_vec();
+    content.extend_from_slice(b"trained dictionary");
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &dictionary.pubkey(),
+        DataTypeOption::DICTIONARY,
+        content,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // a dictionary that can still change can't be referenced
+    let ix = set_dictionary_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &dictionary.pubkey(),
+    );
+    assert_eq!(
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::InvalidDictionary as u32)
+        )
+    );
+
+    let ix = finalize_ix(&program_id, &payer.pubkey(), &dictionary.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = set_dictionary_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &dictionary.pubkey(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account.pubkey()))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(
+        metadata.compression_dictionary(),
+        Some(&dictionary.pubkey())
+    );
+}
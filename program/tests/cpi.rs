@@ -0,0 +1,122 @@
+use borsh::BorshSerialize;
+use dataaccount::{
+    cpi::verify_and_read,
+    error::DataAccountError,
+    state::{
+        DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption,
+        DATA_VERSION, PDA_SEED,
+    },
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// Serialized metadata of a data account holding content_len bytes of content
+fn metadata(program_id: &Pubkey, data_account: &Pubkey, version: u8, content_len: u64) -> Vec<u8> {
+    let (_, bump_seed) =
+        Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], program_id);
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::INITIALIZED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        version,
+        DataTypeOption::CUSTOM,
+        bump_seed,
+    );
+    metadata.set_content_len(content_len);
+    metadata.try_to_vec().unwrap()
+}
+
+/// Calls verify_and_read on the accounts and copies the slice it returns
+fn read(
+    program_id: &Pubkey,
+    data_key: &Pubkey,
+    data: &mut [u8],
+    metadata_key: &Pubkey,
+    metadata: &mut [u8],
+) -> Result<Vec<u8>, ProgramError> {
+    let (mut data_lamports, mut metadata_lamports) = (1, 1);
+    let data_account = AccountInfo::new(
+        data_key,
+        false,
+        false,
+        &mut data_lamports,
+        data,
+        program_id,
+        false,
+        0,
+    );
+    let metadata_account = AccountInfo::new(
+        metadata_key,
+        false,
+        false,
+        &mut metadata_lamports,
+        metadata,
+        program_id,
+        false,
+        0,
+    );
+    let content = verify_and_read(&data_account, &metadata_account, program_id)?;
+    Ok(content.to_vec())
+}
+
+#[test]
+fn test_verify_and_read_returns_content() {
+    let program_id = Pubkey::new_unique();
+    let data_key = Pubkey::new_unique();
+    let metadata_key = Pubkey::find_program_address(&[PDA_SEED, data_key.as_ref()], &program_id).0;
+    let mut data = b"hello\0\0\0".to_vec();
+    let mut metadata = metadata(&program_id, &data_key, DATA_VERSION, 5);
+
+    assert_eq!(
+        read(
+            &program_id,
+            &data_key,
+            &mut data,
+            &metadata_key,
+            &mut metadata
+        )
+        .unwrap(),
+        b"hello"
+    );
+}
+
+#[test]
+fn test_verify_and_read_rejects_wrong_pda() {
+    let program_id = Pubkey::new_unique();
+    let data_key = Pubkey::new_unique();
+    let mut data = b"hello".to_vec();
+    let mut metadata = metadata(&program_id, &data_key, DATA_VERSION, 5);
+
+    assert_eq!(
+        read(
+            &program_id,
+            &data_key,
+            &mut data,
+            &Pubkey::new_unique(),
+            &mut metadata
+        )
+        .unwrap_err(),
+        DataAccountError::InvalidPDA.into()
+    );
+}
+
+#[test]
+fn test_verify_and_read_rejects_other_version() {
+    let program_id = Pubkey::new_unique();
+    let data_key = Pubkey::new_unique();
+    let metadata_key = Pubkey::find_program_address(&[PDA_SEED, data_key.as_ref()], &program_id).0;
+    let mut data = b"hello".to_vec();
+    let mut metadata = metadata(&program_id, &data_key, DATA_VERSION + 1, 5);
+
+    assert_eq!(
+        read(
+            &program_id,
+            &data_key,
+            &mut data,
+            &metadata_key,
+            &mut metadata
+        )
+        .unwrap_err(),
+        DataAccountError::InvalidVersion.into()
+    );
+}
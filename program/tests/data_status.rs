@@ -0,0 +1,49 @@
+use dataaccount::state::DataStatusOption::{self, *};
+
+/// Every allowed transition; every other pair must be rejected
+const ALLOWED: &[(DataStatusOption, DataStatusOption)] = &[
+    (UNINITIALIZED, INITIALIZED),
+    (INITIALIZED, UPDATED),
+    (INITIALIZED, COMMITTED),
+    (INITIALIZED, ARCHIVED),
+    (INITIALIZED, FINALIZED),
+    (UPDATED, UPDATED),
+    (UPDATED, COMMITTED),
+    (UPDATED, ARCHIVED),
+    (UPDATED, FINALIZED),
+    (COMMITTED, UPDATED),
+    (COMMITTED, COMMITTED),
+    (COMMITTED, ARCHIVED),
+    (COMMITTED, FINALIZED),
+    (ARCHIVED, UPDATED),
+];
+
+#[test]
+fn test_data_status_transitions() {
+    for from in DataStatusOption::ALL.iter() {
+        for to in DataStatusOption::ALL.iter() {
+            let expected = ALLOWED.iter().any(|(a, b)| a == from && b == to);
+            assert_eq!(
+                from.can_transition_to(to),
+                expected,
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
+
+#[test]
+fn test_finalized_is_terminal() {
+    assert!(DataStatusOption::ALL
+        .iter()
+        .all(|to| !FINALIZED.can_transition_to(to)));
+}
+
+#[test]
+fn test_no_transition_back_to_uninitialized() {
+    assert!(DataStatusOption::ALL
+        .iter()
+        .all(|from| !from.can_transition_to(&UNINITIALIZED)));
+}
@@ -0,0 +1,42 @@
+use dataaccount::{
+    deflate::{inflate, MAX_DECOMPRESSED_LEN},
+    error::DataAccountError,
+};
+
+/// Raw deflate (zlib.compressobj(9, DEFLATED, -15)) of {"hello":"hello hello hello"}
+const COMPRESSED: [u8; 17] = [
+    171, 86, 202, 72, 205, 201, 201, 87, 178, 130, 208, 10, 72, 164, 82, 45, 0,
+];
+const EXPANDED: &[u8] = br#"{"hello":"hello hello hello"}"#;
+
+#[test]
+fn test_inflate_expands_raw_deflate() {
+    assert_eq!(inflate(&COMPRESSED, 1024).unwrap(), EXPANDED);
+    assert_eq!(inflate(&COMPRESSED, EXPANDED.len()).unwrap(), EXPANDED);
+}
+
+#[test]
+fn test_inflate_rejects_output_beyond_max_len() {
+    assert_eq!(
+        inflate(&COMPRESSED, EXPANDED.len() - 1).unwrap_err(),
+        DataAccountError::InvalidCompressedData
+    );
+    assert_eq!(
+        inflate(&COMPRESSED, MAX_DECOMPRESSED_LEN + 1).unwrap_err(),
+        DataAccountError::InvalidCompressedData
+    );
+}
+
+#[test]
+fn test_inflate_rejects_truncated_or_trailing_data() {
+    assert_eq!(
+        inflate(&COMPRESSED[..8], 1024).unwrap_err(),
+        DataAccountError::InvalidCompressedData
+    );
+    let mut trailing = COMPRESSED.to_vec();
+    trailing.push(0);
+    assert_eq!(
+        inflate(&trailing, 1024).unwrap_err(),
+        DataAccountError::InvalidCompressedData
+    );
+}
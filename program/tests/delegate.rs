@@ -0,0 +1,205 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{assert_error, initialize_ix, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{DelegateWriteAuthorityArgs, LogLevel, RevokeDelegateArgs},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn delegate_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    instruction: DataAccountInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_delegate_can_update_until_revoked() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let delegate = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = delegate_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::DelegateWriteAuthority(DelegateWriteAuthorityArgs {
+            delegate: delegate.pubkey(),
+            expiry_slot: 1_000_000,
+            debug: LogLevel::QUIET,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the delegate pays for the growth of the data_account
+    let ix = system_instruction::transfer(&payer.pubkey(), &delegate.pubkey(), 1_000_000_000);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &delegate.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &delegate])
+        .await
+        .unwrap();
+
+    let ix = delegate_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::RevokeDelegate(RevokeDelegateArgs {
+            debug: LogLevel::QUIET,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &delegate.pubkey(),
+        &data_account.pubkey(),
+        b"world".to_vec(),
+    );
+    assert!(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &delegate])
+            .await
+            .is_err()
+    );
+
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data, b"hello");
+}
+
+#[tokio::test]
+async fn test_expired_delegate_cannot_update() {
+    let program_id = Pubkey::new_unique();
+    let mut context = program_test(program_id).start_with_context().await;
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let data_account = Keypair::new();
+    let delegate = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(
+        &mut context.banks_client,
+        &payer,
+        ix,
+        &[&payer, &data_account],
+    )
+    .await
+    .unwrap();
+
+    // a delegation expiring at the current slot is rejected
+    let ix = delegate_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::DelegateWriteAuthority(DelegateWriteAuthorityArgs {
+            delegate: delegate.pubkey(),
+            expiry_slot: 0,
+            debug: LogLevel::QUIET,
+        }),
+    );
+    assert_error(
+        try_send(&mut context.banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidWriteDelegate,
+    );
+
+    let ix = delegate_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::DelegateWriteAuthority(DelegateWriteAuthorityArgs {
+            delegate: delegate.pubkey(),
+            expiry_slot: 100,
+            debug: LogLevel::QUIET,
+        }),
+    );
+    try_send(&mut context.banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = system_instruction::transfer(&payer.pubkey(), &delegate.pubkey(), 1_000_000_000);
+    try_send(&mut context.banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    context.warp_to_slot(101).unwrap();
+    let ix = update_ix(
+        &program_id,
+        &delegate.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+    );
+    assert!(
+        try_send(&mut context.banks_client, &payer, ix, &[&payer, &delegate])
+            .await
+            .is_err()
+    );
+
+    // the expired delegate can still be revoked, after which there is nothing left to revoke
+    // (the second revocation logs so its transaction differs from the first one)
+    let revoke_ix = |debug| {
+        delegate_ix(
+            &program_id,
+            &payer.pubkey(),
+            &data_account.pubkey(),
+            DataAccountInstruction::RevokeDelegate(RevokeDelegateArgs { debug }),
+        )
+    };
+    try_send(
+        &mut context.banks_client,
+        &payer,
+        revoke_ix(LogLevel::QUIET),
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    assert_error(
+        try_send(
+            &mut context.banks_client,
+            &payer,
+            revoke_ix(LogLevel::INFO),
+            &[&payer],
+        )
+        .await,
+        DataAccountError::InvalidWriteDelegate,
+    );
+}
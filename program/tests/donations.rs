@@ -0,0 +1,195 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    assert_error, close_ix, initialize_ix, metadata, metadata_key, program_test, try_send,
+    update_args, update_ix, update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{DonateArgs, LogLevel, UpdateDataAccountArgs, DONATIONS_SEED},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+};
+
+const DONATION: u64 = 1_000_000;
+
+fn donations_key(program_id: &Pubkey, data_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DONATIONS_SEED, data_account.as_ref()], program_id).0
+}
+
+fn donate_ix(
+    program_id: &Pubkey,
+    donor: &Pubkey,
+    data_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*donor, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new(donations_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::Donate(DonateArgs {
+            amount,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_shrink_and_close_refund_donations_to_donors() {
+    let program_id = Pubkey::new_unique();
+    let donor = Keypair::new();
+    let mut program_test = program_test(program_id);
+    program_test.add_account(
+        donor.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL,
+            ..Account::default()
+        },
+    );
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let data_account = Keypair::new();
+    let rent = banks_client.get_sysvar::<Rent>().await.unwrap();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        vec![1; 1024],
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = donate_ix(
+        &program_id,
+        &donor.pubkey(),
+        &data_account.pubkey(),
+        DONATION,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &donor])
+        .await
+        .unwrap();
+    let donor_balance = banks_client.get_balance(donor.pubkey()).await.unwrap();
+
+    // shrinking refunds the authority the excess rent but not the donation
+    let args = UpdateDataAccountArgs {
+        truncate: true,
+        realloc_down: true,
+        ..update_args(b"hello".to_vec())
+    };
+    let ix = update_ix_with_args(&program_id, &payer.pubkey(), &data_account.pubkey(), args);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(
+        banks_client
+            .get_balance(data_account.pubkey())
+            .await
+            .unwrap(),
+        rent.minimum_balance(5) + DONATION
+    );
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_account.pubkey()).await;
+    assert_eq!(account_metadata.donated_lamports(), DONATION);
+
+    // closing requires the donations pda and the donors to refund
+    let mut ix = close_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    assert_error(
+        try_send(&mut banks_client, &payer, ix.clone(), &[&payer]).await,
+        DataAccountError::UnrefundedDonations,
+    );
+
+    let donations = donations_key(&program_id, &data_account.pubkey());
+    ix.accounts.push(AccountMeta::new(donations, false));
+    ix.accounts.push(AccountMeta::new(donor.pubkey(), false));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(
+        banks_client.get_balance(donor.pubkey()).await.unwrap(),
+        donor_balance + DONATION
+    );
+    assert!(banks_client.get_account(donations).await.unwrap().is_none());
+    assert!(banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_growth_spends_donations() {
+    let program_id = Pubkey::new_unique();
+    let donor = Keypair::new();
+    let mut program_test = program_test(program_id);
+    program_test.add_account(
+        donor.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL,
+            ..Account::default()
+        },
+    );
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = donate_ix(
+        &program_id,
+        &donor.pubkey(),
+        &data_account.pubkey(),
+        DONATION,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &donor])
+        .await
+        .unwrap();
+
+    // the growth draws on the donation before the payer
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        vec![1; 1024],
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_account.pubkey()).await;
+    assert_eq!(account_metadata.donated_lamports(), 0);
+
+    // nothing is left to refund but the donations pda is still closed
+    let donations = donations_key(&program_id, &data_account.pubkey());
+    let mut ix = close_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    ix.accounts.push(AccountMeta::new(donations, false));
+    ix.accounts.push(AccountMeta::new(donor.pubkey(), false));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert!(banks_client.get_account(donations).await.unwrap().is_none());
+}
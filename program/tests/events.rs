@@ -0,0 +1,49 @@
+use borsh::BorshSerialize;
+use dataaccount::events::{DataAccountEvent, EVENT_TAG};
+use solana_program::pubkey::Pubkey;
+
+#[test]
+fn test_decode_event_fields() {
+    let events = [
+        DataAccountEvent::Initialized {
+            data_account: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            space: 1024,
+        },
+        DataAccountEvent::Updated {
+            data_account: Pubkey::new_unique(),
+            offset: 8,
+            len: 3,
+            revision: 1,
+        },
+        DataAccountEvent::Finalized {
+            data_account: Pubkey::new_unique(),
+        },
+        DataAccountEvent::Closed {
+            data_account: Pubkey::new_unique(),
+            rent_receiver: Pubkey::new_unique(),
+        },
+        DataAccountEvent::AuthorityChanged {
+            data_account: Pubkey::new_unique(),
+            old_authority: Pubkey::new_unique(),
+            new_authority: Pubkey::new_unique(),
+        },
+    ];
+    for event in events {
+        let encoded = event.try_to_vec().unwrap();
+        assert_eq!(
+            DataAccountEvent::decode(&[EVENT_TAG, &encoded]),
+            Some(event)
+        );
+    }
+}
+
+#[test]
+fn test_decode_ignores_other_logs() {
+    // the audit event of a write
+    let audit: [&[u8]; 5] = [b"audit", &[0; 8], &[0; 8], &[1; 32], &[2; 32]];
+    assert_eq!(DataAccountEvent::decode(&audit), None);
+    assert_eq!(DataAccountEvent::decode(&[b"truncate", &[0; 8]]), None);
+    assert_eq!(DataAccountEvent::decode(&[EVENT_TAG, &[0xff]]), None);
+    assert_eq!(DataAccountEvent::decode(&[]), None);
+}
@@ -0,0 +1,241 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{assert_error, initialize_ix, metadata_key, program_test, try_send};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        ExpireDataAccountArgs, Expiry, GrowthPolicyOption, InitializeUserDataAccountArgs, LogLevel,
+        SetExpiryArgs,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction, system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn initialize_user_ix(
+    program_id: &Pubkey,
+    app_authority: &Pubkey,
+    user: &Pubkey,
+    name: &str,
+) -> (Pubkey, Instruction) {
+    let data_account = Pubkey::find_program_address(
+        &[app_authority.as_ref(), user.as_ref(), name.as_bytes()],
+        program_id,
+    )
+    .0;
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*app_authority, true),
+            AccountMeta::new_readonly(*app_authority, true),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_key(program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::InitializeUserDataAccount(InitializeUserDataAccountArgs {
+            user: *user,
+            name: name.to_string(),
+            space: 0,
+            is_dynamic: true,
+            require_user_consent: true,
+            growth_policy: GrowthPolicyOption::EXACT,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    };
+    (data_account, instruction)
+}
+
+fn set_expiry_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    expiry: Option<Expiry>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::SetExpiry(SetExpiryArgs {
+            expiry,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn expire_ix(program_id: &Pubkey, data_account: &Pubkey, rent_receiver: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new(*rent_receiver, false),
+        ],
+        data: DataAccountInstruction::ExpireDataAccount(ExpireDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_anyone_can_expire_after_expiry() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let treasury = Pubkey::new_unique();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // no expiry yet
+    let ix = expire_ix(&program_id, &data_account.pubkey(), &treasury);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::ExpiryNotReached,
+    );
+
+    let ix = set_expiry_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        Some(Expiry {
+            expires_at: 0,
+            rent_receiver: treasury,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let metadata = metadata_key(&program_id, &data_account.pubkey());
+    let refund = banks_client.get_balance(metadata).await.unwrap()
+        + banks_client
+            .get_balance(data_account.pubkey())
+            .await
+            .unwrap();
+
+    // only the pre-configured rent_receiver can be refunded
+    let ix = expire_ix(&program_id, &data_account.pubkey(), &payer.pubkey());
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidRentReceiver,
+    );
+
+    // the fee payer is neither the authority nor the rent_receiver
+    let cranker = Keypair::new();
+    let ix = system_instruction::transfer(&payer.pubkey(), &cranker.pubkey(), 1_000_000_000);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = expire_ix(&program_id, &data_account.pubkey(), &treasury);
+    try_send(&mut banks_client, &cranker, ix, &[&cranker])
+        .await
+        .unwrap();
+
+    assert_eq!(banks_client.get_balance(treasury).await.unwrap(), refund);
+    assert_eq!(banks_client.get_balance(metadata).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_expire_rejects_future_expiry() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let treasury = Pubkey::new_unique();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = set_expiry_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        Some(Expiry {
+            expires_at: i64::MAX,
+            rent_receiver: treasury,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = expire_ix(&program_id, &data_account.pubkey(), &treasury);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::ExpiryNotReached,
+    );
+}
+
+#[tokio::test]
+async fn test_expire_requires_user_consent() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let user = Keypair::new();
+    let other = Keypair::new();
+    let treasury = Pubkey::new_unique();
+
+    let (data_account, ix) =
+        initialize_user_ix(&program_id, &payer.pubkey(), &user.pubkey(), "profile");
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = set_expiry_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account,
+        Some(Expiry {
+            expires_at: 0,
+            rent_receiver: treasury,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the expiry fails without the user co-signing
+    let ix = expire_ix(&program_id, &data_account, &treasury);
+    assert!(try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .is_err());
+    let mut ix = expire_ix(&program_id, &data_account, &treasury);
+    ix.accounts
+        .push(AccountMeta::new_readonly(other.pubkey(), true));
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &other]).await,
+        DataAccountError::InvalidUser,
+    );
+
+    let mut ix = expire_ix(&program_id, &data_account, &treasury);
+    ix.accounts
+        .push(AccountMeta::new_readonly(user.pubkey(), true));
+    try_send(&mut banks_client, &payer, ix, &[&payer, &user])
+        .await
+        .unwrap();
+    assert_eq!(
+        banks_client
+            .get_balance(metadata_key(&program_id, &data_account))
+            .await
+            .unwrap(),
+        0
+    );
+}
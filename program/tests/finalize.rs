@@ -0,0 +1,135 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{initialize_ix, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{DataAccountMetadata, DataStatusOption, FinalizeDataAccountArgs, LogLevel},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::{tokio, BanksClient, BanksClientError};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn assert_finalized_error(result: Result<(), BanksClientError>) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::Finalized as u32)
+        )
+    );
+}
+
+fn finalize_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    debug: LogLevel,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::FinalizeDataAccount(FinalizeDataAccountArgs { debug })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Initializes a data account with some content and finalizes it
+async fn finalized_data_account(
+    program_id: &Pubkey,
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+) -> Keypair {
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(banks_client, payer, ix, &[payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+    );
+    try_send(banks_client, payer, ix, &[payer]).await.unwrap();
+
+    let ix = finalize_ix(
+        program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        LogLevel::QUIET,
+    );
+    try_send(banks_client, payer, ix, &[payer]).await.unwrap();
+
+    data_account
+}
+
+#[tokio::test]
+async fn test_finalize_data_account() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = finalized_data_account(&program_id, &mut banks_client, &payer).await;
+
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account.pubkey()))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(*metadata.data_status(), DataStatusOption::FINALIZED);
+    assert_eq!(metadata.content_len(), 5);
+}
+
+#[tokio::test]
+async fn test_refinalize_data_account() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = finalized_data_account(&program_id, &mut banks_client, &payer).await;
+
+    // a different log level keeps the transaction from being deduplicated
+    let ix = finalize_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        LogLevel::INFO,
+    );
+    assert_finalized_error(try_send(&mut banks_client, &payer, ix, &[&payer]).await);
+}
+
+#[tokio::test]
+async fn test_write_after_finalize() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = finalized_data_account(&program_id, &mut banks_client, &payer).await;
+
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"world".to_vec(),
+    );
+    assert_finalized_error(try_send(&mut banks_client, &payer, ix, &[&payer]).await);
+
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data, b"hello");
+}
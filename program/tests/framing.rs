@@ -0,0 +1,30 @@
+use dataaccount::framing::{framed_len, is_valid_framing, records, write_record};
+
+#[test]
+fn test_records_round_trip() {
+    let mut content = vec![0; framed_len(3) + framed_len(0) + framed_len(5)];
+    let mut offset = 0;
+    for record in [&b"abc"[..], b"", b"hello"] {
+        offset += write_record(&mut content[offset..], record).unwrap();
+    }
+    assert_eq!(offset, content.len());
+    assert_eq!(
+        records(&content).collect::<Vec<_>>(),
+        vec![&b"abc"[..], b"", b"hello"]
+    );
+    assert!(is_valid_framing(&content));
+}
+
+#[test]
+fn test_records_stop_at_incomplete_record() {
+    let mut content = vec![0; framed_len(3)];
+    write_record(&mut content, b"abc").unwrap();
+    assert!(write_record(&mut content[1..], b"abc").is_none());
+
+    content.extend_from_slice(&[5, 0, 0, 0, 1]);
+    let mut iter = records(&content);
+    assert_eq!(iter.next(), Some(&b"abc"[..]));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remainder(), &[5, 0, 0, 0, 1]);
+    assert!(!is_valid_framing(&content));
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    archive_ix, assert_error, close_ix, initialize_ix, metadata_key, program_test, try_send,
+    update_ix,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{FreezeDataAccountArgs, LogLevel, SetFreezeAuthorityArgs, ThawDataAccountArgs},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_freeze_authority_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    freeze_authority: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::SetFreezeAuthority(SetFreezeAuthorityArgs {
+            freeze_authority,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn freeze_ix(
+    program_id: &Pubkey,
+    freeze_authority: &Pubkey,
+    data_account: &Pubkey,
+    frozen: bool,
+) -> Instruction {
+    let instruction = if frozen {
+        DataAccountInstruction::FreezeDataAccount(FreezeDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+    } else {
+        DataAccountInstruction::ThawDataAccount(ThawDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+    };
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*freeze_authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_freeze_pauses_updates_and_close() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let operations = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // nobody can freeze before a freeze_authority is set
+    let ix = freeze_ix(&program_id, &operations.pubkey(), &data_key, true);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &operations]).await,
+        DataAccountError::InvalidFreezeAuthority,
+    );
+
+    let ix = set_freeze_authority_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        Some(operations.pubkey()),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = freeze_ix(&program_id, &operations.pubkey(), &data_key, true);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &operations])
+        .await
+        .unwrap();
+
+    // while frozen, the authority can neither write, close, archive nor replace the freeze_authority
+    let ix = update_ix(&program_id, &authority, &data_key, b"hello".to_vec());
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::Frozen,
+    );
+    let ix = close_ix(&program_id, &authority, &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::Frozen,
+    );
+    let ix = archive_ix(&program_id, &authority, &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::Frozen,
+    );
+    let ix = set_freeze_authority_ix(&program_id, &authority, &data_key, None);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::Frozen,
+    );
+
+    // only the freeze_authority can thaw
+    let ix = freeze_ix(&program_id, &authority, &data_key, false);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidFreezeAuthority,
+    );
+    let ix = freeze_ix(&program_id, &operations.pubkey(), &data_key, false);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &operations])
+        .await
+        .unwrap();
+
+    let ix = update_ix(&program_id, &authority, &data_key, b"hello".to_vec());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = close_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{initialize_args, initialize_ix_with_args, metadata_key, program_test, try_send};
+use dataaccount::{
+    error::DataAccountError,
+    state::{DataAccountMetadata, InitializeDataAccountArgs},
+};
+use solana_program::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn initialize_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    data_account: &Pubkey,
+    authority: &Pubkey,
+    space: u64,
+    idempotent: bool,
+) -> Instruction {
+    let args = InitializeDataAccountArgs {
+        space,
+        is_dynamic: false,
+        idempotent,
+        ..initialize_args(authority)
+    };
+    initialize_ix_with_args(program_id, payer, data_account, args)
+}
+
+#[tokio::test]
+async fn test_idempotent_initialize() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (payer_key, data_key) = (payer.pubkey(), data_account.pubkey());
+    let signers = [&payer, &data_account];
+
+    // an idempotent initialize of a new data_account initializes it
+    let ix = initialize_ix(&program_id, &payer_key, &data_key, &payer_key, 8, true);
+    try_send(&mut banks_client, &payer, ix, &signers)
+        .await
+        .unwrap();
+
+    // rerunning it with the same authority is a no-op, even with other args
+    let ix = initialize_ix(&program_id, &payer_key, &data_key, &payer_key, 16, true);
+    try_send(&mut banks_client, &payer, ix, &signers)
+        .await
+        .unwrap();
+    let data = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(data.data.len(), 8);
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_key))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(*metadata.authority(), payer_key);
+
+    // rerunning it with another authority is rejected
+    let other = Pubkey::new_unique();
+    let ix = initialize_ix(&program_id, &payer_key, &data_key, &other, 8, true);
+    assert_eq!(
+        try_send(&mut banks_client, &payer, ix, &signers)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::AlreadyInitialized as u32)
+        )
+    );
+
+    // without idempotent, initializing it again still fails
+    let ix = initialize_ix(&program_id, &payer_key, &data_key, &payer_key, 24, false);
+    assert!(try_send(&mut banks_client, &payer, ix, &signers)
+        .await
+        .is_err());
+}
@@ -0,0 +1,330 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    assert_error, close_ix, finalize_ix, initialize_args, initialize_ix_with_args, metadata,
+    metadata_key, program_test, try_send, update_args, update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    state::{DataStatusOption, InitializeDataAccountArgs, UpdateDataAccountArgs},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::{tokio, BanksClient};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn initialize_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    data_account: &Pubkey,
+    space: u64,
+    is_dynamic: bool,
+) -> Instruction {
+    let args = InitializeDataAccountArgs {
+        space,
+        is_dynamic,
+        ..initialize_args(payer)
+    };
+    initialize_ix_with_args(program_id, payer, data_account, args)
+}
+
+/// Writes the data at the offset, truncating the content and releasing the space beyond it if shrink is set
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    metadata_account: &Pubkey,
+    offset: u64,
+    data: Vec<u8>,
+    shrink: bool,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        offset,
+        realloc_down: shrink,
+        truncate: shrink,
+        ..update_args(data)
+    };
+    let mut ix = update_ix_with_args(program_id, authority, data_account, args);
+    ix.accounts[2] = AccountMeta::new(*metadata_account, false);
+    ix
+}
+
+async fn data(banks_client: &mut BanksClient, data_account: &Pubkey) -> Vec<u8> {
+    banks_client
+        .get_account(*data_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+}
+
+#[tokio::test]
+async fn test_full_lifecycle() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let metadata_account = metadata_key(&program_id, &data_key);
+
+    let ix = initialize_ix(&program_id, &authority, &data_key, 0, true);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(
+        *account_metadata.data_status(),
+        DataStatusOption::INITIALIZED
+    );
+    assert_eq!(*account_metadata.authority(), authority);
+    assert!(data(&mut banks_client, &data_key).await.is_empty());
+
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        0,
+        b"hello".to_vec(),
+        false,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(data(&mut banks_client, &data_key).await, b"hello");
+
+    // realloc up to fit a write beyond the content
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        100,
+        vec![7; 100],
+        false,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let content = data(&mut banks_client, &data_key).await;
+    assert_eq!(content.len(), 200);
+    assert_eq!(&content[..5], b"hello");
+    assert_eq!(&content[100..], &[7; 100][..]);
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(*account_metadata.data_status(), DataStatusOption::UPDATED);
+    assert_eq!(account_metadata.content_len(), 200);
+
+    // realloc down to release the space beyond a truncated content
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        0,
+        b"bye".to_vec(),
+        true,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(data(&mut banks_client, &data_key).await, b"bye");
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.content_len(), 3);
+
+    let ix = finalize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(*account_metadata.data_status(), DataStatusOption::FINALIZED);
+
+    // the finalized content can no longer be updated
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        0,
+        b"again".to_vec(),
+        false,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::Finalized,
+    );
+
+    // closing returns the rent of both accounts to the authority
+    let rent = banks_client
+        .get_account(data_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports
+        + banks_client
+            .get_account(metadata_account)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports;
+    let balance = banks_client.get_balance(authority).await.unwrap();
+    let ix = close_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert!(banks_client.get_account(data_key).await.unwrap().is_none());
+    assert!(banks_client
+        .get_account(metadata_account)
+        .await
+        .unwrap()
+        .is_none());
+    // the authority also paid the fee of the close transaction
+    assert!(banks_client.get_balance(authority).await.unwrap() > balance + rent - 10_000);
+}
+
+#[tokio::test]
+async fn test_update_with_invalid_pda_fails() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let (data_account, other_data_account) = (Keypair::new(), Keypair::new());
+    let authority = payer.pubkey();
+
+    for data_account in [&data_account, &other_data_account] {
+        let ix = initialize_ix(&program_id, &authority, &data_account.pubkey(), 0, true);
+        try_send(&mut banks_client, &payer, ix, &[&payer, data_account])
+            .await
+            .unwrap();
+    }
+
+    // the metadata account of another data account is not the pda of this one
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_account.pubkey(),
+        &metadata_key(&program_id, &other_data_account.pubkey()),
+        0,
+        b"hello".to_vec(),
+        false,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidPDA,
+    );
+    assert!(data(&mut banks_client, &data_account.pubkey())
+        .await
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_wrong_authority_fails() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let metadata_account = metadata_key(&program_id, &data_key);
+
+    let ix = initialize_ix(&program_id, &authority, &data_key, 0, true);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        0,
+        b"hello".to_vec(),
+        false,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let other = Keypair::new();
+    let ix = system_instruction::transfer(&authority, &other.pubkey(), 1_000_000_000);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // neither the authority nor a writer
+    let ix = update_ix(
+        &program_id,
+        &other.pubkey(),
+        &data_key,
+        &metadata_account,
+        0,
+        b"world".to_vec(),
+        false,
+    );
+    assert!(try_send(&mut banks_client, &payer, ix, &[&payer, &other])
+        .await
+        .is_err());
+
+    let ix = finalize_ix(&program_id, &other.pubkey(), &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &other]).await,
+        DataAccountError::InvalidAuthority,
+    );
+
+    let ix = close_ix(&program_id, &other.pubkey(), &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &other]).await,
+        DataAccountError::InvalidAuthority,
+    );
+
+    assert_eq!(data(&mut banks_client, &data_key).await, b"hello");
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(*account_metadata.data_status(), DataStatusOption::UPDATED);
+}
+
+#[tokio::test]
+async fn test_static_data_account_with_insufficient_space_fails() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let metadata_account = metadata_key(&program_id, &data_key);
+
+    let ix = initialize_ix(&program_id, &authority, &data_key, 8, false);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // a write within the space succeeds without reallocing
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        0,
+        b"hello".to_vec(),
+        false,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // a write past the space is rejected instead of growing the static data account
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        &metadata_account,
+        4,
+        b"world".to_vec(),
+        false,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InsufficientSpace,
+    );
+
+    let mut expected = b"hello".to_vec();
+    expected.resize(8, 0);
+    assert_eq!(data(&mut banks_client, &data_key).await, expected);
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.content_len(), 5);
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{assert_error, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, DataAccountMetadataV0, DataStatusOption, DataTypeOption, LogLevel,
+        MigrateMetadataArgs, ScrubFillOption, SerializationStatusOption, DATA_VERSION,
+        METADATA_SIZE, METADATA_V0_SIZE, PDA_SEED,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+};
+
+/// Adds a data account with its metadata stored in the layout of data_version 0
+fn add_v0_data_account(
+    program_test: &mut ProgramTest,
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    content: &[u8],
+) {
+    let (metadata_account, bump_seed) =
+        Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], program_id);
+    let metadata = DataAccountMetadataV0 {
+        data_status: DataStatusOption::INITIALIZED,
+        serialization_status: SerializationStatusOption::UNVERIFIED,
+        authority: *authority,
+        is_dynamic: true,
+        data_version: 0,
+        data_type: DataTypeOption::JSON,
+        bump_seed,
+    };
+    let metadata = metadata.try_to_vec().unwrap();
+    assert_eq!(metadata.len(), METADATA_V0_SIZE);
+
+    let rent = Rent::default();
+    program_test.add_account(
+        *data_account,
+        Account {
+            lamports: rent.minimum_balance(content.len()),
+            data: content.to_vec(),
+            owner: *program_id,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        metadata_account,
+        Account {
+            lamports: rent.minimum_balance(metadata.len()),
+            data: metadata,
+            owner: *program_id,
+            ..Account::default()
+        },
+    );
+}
+
+fn migrate_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::MigrateMetadata(MigrateMetadataArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn scrub_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    common::scrub_ix(
+        program_id,
+        authority,
+        data_account,
+        0,
+        1,
+        ScrubFillOption::ZEROS,
+    )
+}
+
+#[tokio::test]
+async fn test_migrate_v0_metadata() {
+    let program_id = Pubkey::new_unique();
+    let authority = Keypair::new();
+    let data_key = Pubkey::new_unique();
+    let mut program_test = program_test(program_id);
+    add_v0_data_account(
+        &mut program_test,
+        &program_id,
+        &authority.pubkey(),
+        &data_key,
+        br#"{"a":1}"#,
+    );
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let ix = solana_sdk::system_instruction::transfer(
+        &payer.pubkey(),
+        &authority.pubkey(),
+        1_000_000_000,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // metadata of an older layout must be migrated before it is used
+    let ix = scrub_ix(&program_id, &authority.pubkey(), &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &authority]).await,
+        DataAccountError::OutdatedMetadata,
+    );
+    let ix = update_ix(&program_id, &authority.pubkey(), &data_key, b"{}".to_vec());
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &authority]).await,
+        DataAccountError::OutdatedMetadata,
+    );
+
+    let ix = migrate_ix(&program_id, &authority.pubkey(), &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &authority])
+        .await
+        .unwrap();
+    let metadata_account = banks_client
+        .get_account(metadata_key(&program_id, &data_key))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(metadata_account.data.len(), METADATA_SIZE);
+    assert_eq!(
+        metadata_account.lamports,
+        Rent::default().minimum_balance(METADATA_SIZE)
+    );
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata_account.data[..]).unwrap();
+    assert_eq!(metadata.version(), DATA_VERSION);
+    assert_eq!(metadata.authority(), &authority.pubkey());
+    assert_eq!(metadata.data_type(), &DataTypeOption::JSON);
+    assert_eq!(metadata.content_len(), 7);
+
+    // the migrated data account is usable and migrating it again does nothing
+    let ix = scrub_ix(&program_id, &authority.pubkey(), &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &authority])
+        .await
+        .unwrap();
+    let ix = migrate_ix(&program_id, &authority.pubkey(), &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &authority])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_migrate_metadata_requires_authority() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let data_key = Pubkey::new_unique();
+    let mut program_test = program_test(program_id);
+    add_v0_data_account(&mut program_test, &program_id, &authority, &data_key, b"hi");
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let ix = migrate_ix(&program_id, &payer.pubkey(), &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidAuthority,
+    );
+}
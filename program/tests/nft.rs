@@ -0,0 +1,199 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{assert_error, metadata_key, program_test, try_send};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    nft::NFT_SEED,
+    state::{CloseNftDataAccountArgs, GrowthPolicyOption, InitializeNftDataAccountArgs, LogLevel},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+fn nft_data_key(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[NFT_SEED, mint.as_ref()], program_id).0
+}
+
+/// Adds an NFT mint along with token accounts of the given owners holding the given amounts of it
+fn add_nft(program_test: &mut ProgramTest, mint: &Pubkey, holdings: &[(Pubkey, Pubkey, u64)]) {
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(
+        Mint {
+            mint_authority: COption::None,
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut data,
+    )
+    .unwrap();
+    program_test.add_account(
+        *mint,
+        Account {
+            lamports: Rent::default().minimum_balance(Mint::LEN),
+            data,
+            owner: spl_token::id(),
+            ..Account::default()
+        },
+    );
+
+    for (token_account, owner, amount) in holdings {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(
+            TokenAccount {
+                mint: *mint,
+                owner: *owner,
+                amount: *amount,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        program_test.add_account(
+            *token_account,
+            Account {
+                lamports: Rent::default().minimum_balance(TokenAccount::LEN),
+                data,
+                owner: spl_token::id(),
+                ..Account::default()
+            },
+        );
+    }
+}
+
+fn initialize_nft_ix(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let data_account = nft_data_key(program_id, mint);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_key(program_id, &data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::InitializeNftDataAccount(InitializeNftDataAccountArgs {
+            space: 64,
+            is_dynamic: true,
+            growth_policy: GrowthPolicyOption::EXACT,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn close_nft_ix(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    rent_receiver: &Pubkey,
+) -> Instruction {
+    let data_account = nft_data_key(program_id, mint);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*holder, true),
+            AccountMeta::new_readonly(*token_account, false),
+            AccountMeta::new(data_account, false),
+            AccountMeta::new(metadata_key(program_id, &data_account), false),
+            AccountMeta::new(*rent_receiver, false),
+        ],
+        data: DataAccountInstruction::CloseNftDataAccount(CloseNftDataAccountArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_only_the_holder_can_close_the_nft_data_account() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = program_test(program_id);
+    let (holder, other) = (Keypair::new(), Keypair::new());
+    let (mint, holder_tokens, other_tokens) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    add_nft(
+        &mut program_test,
+        &mint,
+        &[
+            (holder_tokens, holder.pubkey(), 1),
+            (other_tokens, other.pubkey(), 0),
+        ],
+    );
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let treasury = Pubkey::new_unique();
+
+    // the holder funds the data_account
+    let ix = system_instruction::transfer(&payer.pubkey(), &holder.pubkey(), 1_000_000_000);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = initialize_nft_ix(&program_id, &holder.pubkey(), &holder_tokens, &mint);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &holder])
+        .await
+        .unwrap();
+
+    let data_account = nft_data_key(&program_id, &mint);
+    let metadata_account = metadata_key(&program_id, &data_account);
+    let refund = banks_client.get_balance(data_account).await.unwrap()
+        + banks_client.get_balance(metadata_account).await.unwrap();
+
+    // the data_account is its own authority, so only the holder of the NFT can close it
+    let ix = close_nft_ix(
+        &program_id,
+        &other.pubkey(),
+        &other_tokens,
+        &mint,
+        &treasury,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &other]).await,
+        DataAccountError::NotTokenHolder,
+    );
+
+    let ix = close_nft_ix(
+        &program_id,
+        &holder.pubkey(),
+        &holder_tokens,
+        &mint,
+        &treasury,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &holder])
+        .await
+        .unwrap();
+    assert_eq!(banks_client.get_balance(treasury).await.unwrap(), refund);
+    assert_eq!(banks_client.get_balance(data_account).await.unwrap(), 0);
+    assert_eq!(banks_client.get_balance(metadata_account).await.unwrap(), 0);
+}
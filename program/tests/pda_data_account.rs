@@ -0,0 +1,225 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{archive_ix, assert_error, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, DataStatusOption, GrowthPolicyOption, InitializeDataAccountPdaArgs,
+        LogLevel, RestoreFromArchiveArgs, DATA_PDA_SEED,
+    },
+};
+use solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+fn data_key(program_id: &Pubkey, authority: &Pubkey, seed: &str) -> Pubkey {
+    Pubkey::find_program_address(
+        &[DATA_PDA_SEED, authority.as_ref(), seed.as_bytes()],
+        program_id,
+    )
+    .0
+}
+
+fn initialize_pda_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    seed: &str,
+    space: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::InitializeDataAccountPda(InitializeDataAccountPdaArgs {
+            seed: seed.to_string(),
+            space,
+            is_dynamic: true,
+            growth_policy: GrowthPolicyOption::EXACT,
+            append_only: false,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn restore_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    seeds: Vec<Vec<u8>>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::RestoreFromArchive(RestoreFromArchiveArgs {
+            offset: 0,
+            data,
+            finalize: true,
+            seeds: Some(seeds),
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_initialize_data_account_pda() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let authority = payer.pubkey();
+    let data_account = data_key(&program_id, &authority, "site");
+
+    // no keypair of the data_account signs
+    let ix = initialize_pda_ix(&program_id, &authority, &data_account, "site", 8);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let data = banks_client
+        .get_account(data_account)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.owner, program_id);
+    assert_eq!(data.data.len(), 8);
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(*metadata.authority(), authority);
+    assert!(metadata.dynamic());
+
+    // the same authority and seed can't be initialized twice
+    let ix = initialize_pda_ix(&program_id, &authority, &data_account, "site", 16);
+    assert!(try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_initialize_data_account_pda_rejects_invalid_address_or_seed() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let authority = payer.pubkey();
+
+    // the data_account of another seed
+    let data_account = data_key(&program_id, &authority, "other");
+    let ix = initialize_pda_ix(&program_id, &authority, &data_account, "site", 8);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidPDA,
+    );
+
+    // a seed that is too long to derive a pda from
+    let seed = "s".repeat(33);
+    let ix = initialize_pda_ix(&program_id, &authority, &data_account, &seed, 8);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidName,
+    );
+}
+
+#[tokio::test]
+async fn test_restore_data_account_pda_from_archive() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let authority = payer.pubkey();
+    let data_account = data_key(&program_id, &authority, "site");
+    let content = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+    let ix = initialize_pda_ix(&program_id, &authority, &data_account, "site", 8);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_account, content.clone());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = archive_ix(&program_id, &authority, &data_account);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert!(banks_client
+        .get_account(data_account)
+        .await
+        .unwrap()
+        .is_none());
+
+    // the seeds of another data_account pda can't recreate it
+    let seeds = vec![
+        DATA_PDA_SEED.to_vec(),
+        authority.to_bytes().to_vec(),
+        b"other".to_vec(),
+    ];
+    let ix = restore_ix(
+        &program_id,
+        &authority,
+        &data_account,
+        content.clone(),
+        seeds,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidPDA,
+    );
+
+    // the program signs for the data_account pda in place of its keypair
+    let seeds = vec![
+        DATA_PDA_SEED.to_vec(),
+        authority.to_bytes().to_vec(),
+        b"site".to_vec(),
+    ];
+    let ix = restore_ix(
+        &program_id,
+        &authority,
+        &data_account,
+        content.clone(),
+        seeds,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let data = banks_client
+        .get_account(data_account)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.owner, program_id);
+    assert_eq!(data.data, content);
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(*metadata.data_status(), DataStatusOption::UPDATED);
+    assert!(metadata.archive().is_none());
+    assert_eq!(metadata.content_len(), content.len() as u64);
+    assert_eq!(metadata.content_hash(), Some(&hash(&content).to_bytes()));
+}
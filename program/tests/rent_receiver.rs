@@ -0,0 +1,75 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{close_ix_to, initialize_ix, metadata_key, program_test, try_send};
+use dataaccount::error::DataAccountError;
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+#[tokio::test]
+async fn test_close_refunds_rent_receiver() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let treasury = Pubkey::new_unique();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let metadata = metadata_key(&program_id, &data_account.pubkey());
+    let refund = banks_client.get_balance(metadata).await.unwrap()
+        + banks_client
+            .get_balance(data_account.pubkey())
+            .await
+            .unwrap();
+
+    let ix = close_ix_to(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &treasury,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    assert_eq!(banks_client.get_balance(treasury).await.unwrap(), refund);
+    assert_eq!(banks_client.get_balance(metadata).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_close_rejects_closed_account_as_rent_receiver() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let metadata = metadata_key(&program_id, &data_account.pubkey());
+    let ix = close_ix_to(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &metadata,
+    );
+    assert_eq!(
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::InvalidRentReceiver as u32)
+        )
+    );
+}
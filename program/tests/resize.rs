@@ -0,0 +1,144 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{initialize_ix, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{DataAccountMetadata, LogLevel, ResizeDataAccountArgs},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn resize_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    space: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::ResizeDataAccount(ResizeDataAccountArgs {
+            space,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_resize_grows_and_shrinks_to_exact_space() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let rent = banks_client.get_sysvar::<Rent>().await.unwrap();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // pre-allocate before writing
+    let ix = resize_ix(&program_id, &payer.pubkey(), &data_account.pubkey(), 1024);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), 1024);
+    assert_eq!(account.lamports, rent.minimum_balance(1024));
+
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the content can't be cut off
+    let ix = resize_ix(&program_id, &payer.pubkey(), &data_account.pubkey(), 2);
+    assert_eq!(
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::InvalidResize as u32)
+        )
+    );
+
+    // reclaim the rent of the unused space
+    let ix = resize_ix(&program_id, &payer.pubkey(), &data_account.pubkey(), 5);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data, b"hello");
+    assert_eq!(account.lamports, rent.minimum_balance(5));
+}
+
+#[tokio::test]
+async fn test_resize_records_realloc_statistics() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let rent = banks_client.get_sysvar::<Rent>().await.unwrap();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // only growing pays rent into the data_account, though every realloc is counted
+    for new_len in [512, 1024, 5] {
+        let ix = resize_ix(
+            &program_id,
+            &payer.pubkey(),
+            &data_account.pubkey(),
+            new_len,
+        );
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap();
+    }
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account.pubkey()))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(metadata.realloc_count(), 3);
+    assert_eq!(
+        metadata.rent_paid(),
+        rent.minimum_balance(1024) - rent.minimum_balance(0)
+    );
+}
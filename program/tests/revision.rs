@@ -0,0 +1,174 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    assert_error, initialize_ix, metadata, program_test, try_send, update_args, update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    state::{ScrubFillOption, UpdateDataAccountArgs},
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    expected_revision: Option<u64>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        expected_revision,
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn scrub_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    common::scrub_ix(
+        program_id,
+        authority,
+        data_account,
+        0,
+        10,
+        ScrubFillOption::ZEROS,
+    )
+}
+
+#[tokio::test]
+async fn test_every_successful_update_bumps_the_revision() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.revision(), 0);
+
+    for (revision, content) in [vec![1; 100], vec![2; 50]].into_iter().enumerate() {
+        let ix = update_ix(&program_id, &authority, &data_key, content, None);
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap();
+        let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+        assert_eq!(account_metadata.revision(), revision as u64 + 1);
+    }
+
+    // a rejected update (e.g., of a signer that is neither a writer nor a session key) leaves the revision as is
+    let other = Keypair::new();
+    let ix = update_ix(&program_id, &other.pubkey(), &data_key, vec![3; 10], None);
+    assert!(try_send(&mut banks_client, &payer, ix, &[&payer, &other])
+        .await
+        .is_err());
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.revision(), 2);
+}
+
+#[tokio::test]
+async fn test_content_writes_other_than_update_bump_the_revision() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![1; 100], None);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = scrub_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.revision(), 2);
+}
+
+#[tokio::test]
+async fn test_update_with_a_stale_expected_revision_fails() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![1; 100], Some(0));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // two writers read revision 1 and the second one to update is rejected instead of clobbering the first
+    let ix = update_ix(&program_id, &authority, &data_key, vec![2; 100], Some(1));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![3; 100], Some(1));
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::StaleRevision,
+    );
+    let account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(account.data, vec![2; 100]);
+
+    // it succeeds once retried with the current revision
+    let ix = update_ix(&program_id, &authority, &data_key, vec![3; 100], Some(2));
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.revision(), 3);
+}
+
+#[tokio::test]
+async fn test_expected_revision_detects_a_write_by_another_instruction() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![1; 100], None);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let revision = metadata(&mut banks_client, &program_id, &data_key)
+        .await
+        .revision();
+
+    // the content is scrubbed after the writer read the revision, so its update is rejected
+    let ix = scrub_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        vec![2; 100],
+        Some(revision),
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::StaleRevision,
+    );
+    let account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(&account.data[..10], &[0; 10]);
+    assert_eq!(&account.data[10..], &[1; 90]);
+}
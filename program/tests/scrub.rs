@@ -0,0 +1,144 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    assert_error, content_and_metadata, initialize_ix, program_test, scrub_ix, try_send,
+    update_args, update_ix_with_args,
+};
+use dataaccount::{
+    checksum::crc32,
+    error::DataAccountError,
+    state::{ScrubFillOption, UpdateDataAccountArgs},
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        content_hash: Some([1; 32]),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+#[tokio::test]
+async fn test_scrub_range() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        b"name=alice;ssn=123456789".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // redact the ssn with zeros
+    let ix = scrub_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        15,
+        9,
+        ScrubFillOption::ZEROS,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, metadata) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(&content[..15], b"name=alice;ssn=");
+    assert_eq!(&content[15..], &[0; 9]);
+    assert_eq!(metadata.content_len(), 24);
+    assert_eq!(metadata.content_hash(), None);
+    assert_eq!(metadata.checksum(), Some(crc32(&content)));
+
+    // redact the name with pseudo-random bytes
+    let ix = scrub_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        5,
+        5,
+        ScrubFillOption::RANDOM,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, metadata) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_ne!(&content[5..10], b"alice");
+    assert_eq!(&content[10..15], b";ssn=");
+    assert_eq!(metadata.checksum(), Some(crc32(&content)));
+}
+
+#[tokio::test]
+async fn test_scrub_range_rejects_range_outside_content() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, b"hello".to_vec());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = scrub_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        3,
+        3,
+        ScrubFillOption::ZEROS,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidScrubRange,
+    );
+    let ix = scrub_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        0,
+        0,
+        ScrubFillOption::ZEROS,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::InvalidScrubRange,
+    );
+
+    // only the authority can scrub
+    let other = Keypair::new();
+    let ix = scrub_ix(
+        &program_id,
+        &other.pubkey(),
+        &data_key,
+        0,
+        1,
+        ScrubFillOption::ZEROS,
+    );
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer, &other]).await,
+        DataAccountError::InvalidAuthority,
+    );
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    assert_error, initialize_ix, metadata, metadata_key, program_test, try_send, update_ix,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{CommitStagedArgs, LogLevel, WriteStagedArgs, STAGED_BUFFER_SEED},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn staged_buffer_key(program_id: &Pubkey, data_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STAGED_BUFFER_SEED, data_account.as_ref()], program_id).0
+}
+
+fn write_staged_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    offset: u64,
+    data: Vec<u8>,
+    reset: bool,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new_readonly(metadata_key(program_id, data_account), false),
+            AccountMeta::new(staged_buffer_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::WriteStaged(WriteStagedArgs {
+            offset,
+            data,
+            reset,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+fn commit_staged_ix(program_id: &Pubkey, authority: &Pubkey, data_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new(staged_buffer_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::CommitStaged(CommitStagedArgs {
+            expected_revision: None,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_staged_content_is_swapped_in_on_commit() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![1; 300]);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // readers keep seeing the previous content while the next one is staged
+    let staged: Vec<u8> = (0..700).map(|i| (i * 3) as u8).collect();
+    for (offset, chunk) in staged.chunks(400).enumerate() {
+        let ix = write_staged_ix(
+            &program_id,
+            &authority,
+            &data_key,
+            (offset * 400) as u64,
+            chunk.to_vec(),
+            false,
+        );
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap();
+    }
+    let account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(account.data, vec![1; 300]);
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.generation(), 0);
+
+    let ix = commit_staged_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(account.data, staged);
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.content_len(), staged.len() as u64);
+    assert_eq!(account_metadata.generation(), 1);
+    assert_eq!(account_metadata.revision(), 2);
+    assert!(banks_client
+        .get_account(staged_buffer_key(&program_id, &data_key))
+        .await
+        .unwrap()
+        .is_none());
+
+    // the staged buffer is gone once committed
+    let ix = commit_staged_ix(&program_id, &authority, &data_key);
+    assert_error(
+        try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+        DataAccountError::EmptyStagedBuffer,
+    );
+}
+
+#[tokio::test]
+async fn test_reset_starts_the_staged_content_over() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(&program_id, &authority, &data_key, vec![1; 300]);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = write_staged_ix(&program_id, &authority, &data_key, 0, vec![2; 200], false);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = write_staged_ix(&program_id, &authority, &data_key, 50, vec![3; 50], true);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the shorter staged content replaces the whole previous content
+    let ix = commit_staged_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    let mut expected = vec![0; 300];
+    expected[50..100].fill(3);
+    assert_eq!(account.data, expected);
+    let account_metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(account_metadata.content_len(), 100);
+    assert_eq!(account_metadata.generation(), 1);
+}
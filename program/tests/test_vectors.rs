@@ -0,0 +1,60 @@
+use dataaccount::{
+    inspect::{parse_instruction, INSTRUCTIONS},
+    test_vectors::{test_vectors, to_golden},
+};
+
+const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/golden.txt");
+
+/// Regenerate the golden file after an intentional wire format change with
+/// UPDATE_GOLDEN=1 cargo test --test test_vectors
+#[test]
+fn test_vectors_match_golden_file() {
+    let vectors = test_vectors().unwrap();
+    let golden = to_golden(&vectors);
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        std::fs::write(GOLDEN_PATH, &golden).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(GOLDEN_PATH).unwrap();
+    for (line, expected_line) in golden.lines().zip(expected.lines()) {
+        assert_eq!(line, expected_line);
+    }
+    assert_eq!(golden.lines().count(), expected.lines().count());
+}
+
+#[test]
+fn test_instruction_vectors_start_with_their_index() {
+    let vectors = test_vectors().unwrap();
+    let instructions = vectors
+        .iter()
+        .filter(|vector| vector.name.starts_with("instruction/"));
+    for (index, vector) in instructions.enumerate() {
+        assert_eq!(vector.bytes[0] as usize, index, "{}", vector.name);
+    }
+}
+
+#[test]
+fn test_instruction_vectors_parse_with_their_name() {
+    let vectors = test_vectors().unwrap();
+    let instructions: Vec<_> = vectors
+        .iter()
+        .filter(|vector| vector.name.starts_with("instruction/"))
+        .collect();
+    assert_eq!(instructions.len(), INSTRUCTIONS.len());
+    for vector in instructions {
+        let parsed = parse_instruction(&vector.bytes).unwrap();
+        assert_eq!(
+            format!("instruction/{}", parsed.name()),
+            vector.name,
+            "{}",
+            parsed
+        );
+    }
+}
+
+#[test]
+fn test_parse_instruction_rejects_invalid_data() {
+    assert!(parse_instruction(&[]).is_err());
+    assert!(parse_instruction(&[INSTRUCTIONS.len() as u8]).is_err());
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{metadata_key, program_test, try_send};
+use dataaccount::{
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, DataStatusOption, DataTypeOption, LogLevel, SerializationStatusOption,
+        TopUpRentArgs, DATA_VERSION, METADATA_SIZE, PDA_SEED,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{tokio, ProgramTest};
+use solana_sdk::{account::Account, signature::Signer};
+
+/// Adds a data account holding only lamports of its rent-exempt minimum
+fn add_underfunded_data_account(
+    program_test: &mut ProgramTest,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+    content: &[u8],
+    lamports: u64,
+) {
+    let (metadata_account, bump_seed) =
+        Pubkey::find_program_address(&[PDA_SEED, data_account.as_ref()], program_id);
+    let mut metadata = DataAccountMetadata::new(
+        DataStatusOption::UPDATED,
+        SerializationStatusOption::UNVERIFIED,
+        Pubkey::new_unique(),
+        true,
+        DATA_VERSION,
+        DataTypeOption::CUSTOM,
+        bump_seed,
+    );
+    metadata.set_content_len(content.len() as u64);
+    let mut metadata = metadata.try_to_vec().unwrap();
+    metadata.resize(METADATA_SIZE, 0);
+
+    program_test.add_account(
+        *data_account,
+        Account {
+            lamports,
+            data: content.to_vec(),
+            owner: *program_id,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        metadata_account,
+        Account {
+            lamports: Rent::default().minimum_balance(METADATA_SIZE),
+            data: metadata,
+            owner: *program_id,
+            ..Account::default()
+        },
+    );
+}
+
+fn top_up_rent_ix(program_id: &Pubkey, payer: &Pubkey, data_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*data_account, false),
+            AccountMeta::new_readonly(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: DataAccountInstruction::TopUpRent(TopUpRentArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_anyone_can_top_up_rent() {
+    let program_id = Pubkey::new_unique();
+    let data_key = Pubkey::new_unique();
+    let content = vec![7; 256];
+    let minimum_balance = Rent::default().minimum_balance(content.len());
+    let mut program_test = program_test(program_id);
+    add_underfunded_data_account(
+        &mut program_test,
+        &program_id,
+        &data_key,
+        &content,
+        minimum_balance / 2,
+    );
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    // the payer is neither the authority nor a writer
+    let ix = top_up_rent_ix(&program_id, &payer.pubkey(), &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let data_account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(data_account.lamports, minimum_balance);
+    assert_eq!(data_account.data, content);
+
+    // a rent-exempt data account is left as is
+    let ix = top_up_rent_ix(&program_id, &payer.pubkey(), &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let data_account = banks_client.get_account(data_key).await.unwrap().unwrap();
+    assert_eq!(data_account.lamports, minimum_balance);
+}
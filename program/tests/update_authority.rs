@@ -0,0 +1,133 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{initialize_args, initialize_ix_with_args, metadata_key, program_test, try_send};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{
+        DataAccountMetadata, InitializeDataAccountArgs, LogLevel, UpdateDataAccountAuthorityArgs,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::{tokio, BanksClient};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn initialize_ix(program_id: &Pubkey, payer: &Pubkey, data_account: &Pubkey) -> Instruction {
+    let args = InitializeDataAccountArgs {
+        is_dynamic: false,
+        ..initialize_args(payer)
+    };
+    initialize_ix_with_args(program_id, payer, data_account, args)
+}
+
+fn update_authority_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    new_authority: &Pubkey,
+    new_authority_signs: bool,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+            AccountMeta::new_readonly(*new_authority, new_authority_signs),
+        ],
+        data: DataAccountInstruction::UpdateDataAccountAuthority(UpdateDataAccountAuthorityArgs {
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+async fn authority_of(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    data_account: &Pubkey,
+) -> Pubkey {
+    let metadata = banks_client
+        .get_account(metadata_key(program_id, data_account))
+        .await
+        .unwrap()
+        .unwrap();
+    *DataAccountMetadata::deserialize(&mut &metadata.data[..])
+        .unwrap()
+        .authority()
+}
+
+#[tokio::test]
+async fn test_update_authority() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let new_authority = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = update_authority_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &new_authority.pubkey(),
+        true,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &new_authority])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        authority_of(&mut banks_client, &program_id, &data_account.pubkey()).await,
+        new_authority.pubkey()
+    );
+}
+
+#[tokio::test]
+async fn test_update_authority_requires_new_authority_signature() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let new_authority = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = update_authority_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        &new_authority.pubkey(),
+        false,
+    );
+    assert_eq!(
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::NotSigner as u32)
+        )
+    );
+
+    assert_eq!(
+        authority_of(&mut banks_client, &program_id, &data_account.pubkey()).await,
+        payer.pubkey()
+    );
+}
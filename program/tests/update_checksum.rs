@@ -0,0 +1,82 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    content_and_metadata, initialize_ix, program_test, scrub_ix, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    checksum::crc32,
+    state::{ScrubFillOption, UpdateDataAccountArgs},
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    offset: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        offset,
+        content_hash: Some([1; 32]),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+#[tokio::test]
+async fn test_update_stores_content_checksum() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    // the checksum covers the entire content after a write, an append and an overwrite
+    for (offset, data) in [
+        (0, b"hello".to_vec()),
+        (5, b" world".to_vec()),
+        (0, b"HELLO".to_vec()),
+    ] {
+        let ix = update_ix(&program_id, &authority, &data_key, offset, data);
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap();
+        let (content, metadata) =
+            content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+        assert_eq!(metadata.checksum(), Some(crc32(&content)));
+    }
+
+    // any other write clears the checksum
+    let ix = scrub_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        0,
+        5,
+        ScrubFillOption::ZEROS,
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (_, metadata) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(metadata.checksum(), None);
+
+    // and the next update recomputes it over the entire content
+    let ix = update_ix(&program_id, &authority, &data_key, 11, b"!".to_vec());
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, metadata) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(content, b"\0\0\0\0\0 world!");
+    assert_eq!(metadata.checksum(), Some(crc32(&content)));
+}
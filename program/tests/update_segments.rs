@@ -0,0 +1,134 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    assert_error, content_and_metadata, initialize_ix, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    state::{UpdateDataAccountArgs, UpdateSegment, MAX_UPDATE_SEGMENTS},
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    offset: u64,
+    data: Vec<u8>,
+    segments: Vec<UpdateSegment>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        offset,
+        content_hash: Some([1; 32]),
+        segments,
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn segment(offset: u64, data: &[u8]) -> UpdateSegment {
+    UpdateSegment {
+        offset,
+        data: data.to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn test_update_segments() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        0,
+        b"0123456789".to_vec(),
+        Vec::new(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // patch scattered fields and grow the content in a single instruction
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        1,
+        b"a".to_vec(),
+        vec![segment(4, b"bc"), segment(12, b"de")],
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, metadata) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(content, b"0a23bc6789\0\0de");
+    assert_eq!(metadata.content_len(), 14);
+}
+
+#[tokio::test]
+async fn test_update_segments_are_validated() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let invalid_segments = [
+        // overlapping the data
+        vec![segment(3, b"xy")],
+        // overlapping each other
+        vec![segment(8, b"xyz"), segment(10, b"z")],
+        // empty
+        vec![segment(8, b"")],
+        // too many
+        (0..=MAX_UPDATE_SEGMENTS as u64)
+            .map(|i| segment(8 + i, b"x"))
+            .collect(),
+    ];
+    for segments in invalid_segments {
+        let ix = update_ix(
+            &program_id,
+            &authority,
+            &data_key,
+            0,
+            b"0123".to_vec(),
+            segments,
+        );
+        assert_error(
+            try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+            DataAccountError::InvalidSegments,
+        );
+    }
+
+    // adjacent writes do not overlap
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        0,
+        b"0123".to_vec(),
+        vec![segment(4, b"45")],
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let (content, _) = content_and_metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(content, b"012345");
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    assert_error, initialize_ix, metadata, metadata_key, program_test, try_send, update_args,
+    update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    instruction::DataAccountInstruction,
+    state::{LogLevel, SerializationStatusOption, UpdateDataAccountArgs, VerifyDataStepArgs},
+};
+use solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    content_hash: [u8; 32],
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        content_hash: Some(content_hash),
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+fn verify_step_ix(program_id: &Pubkey, data_account: &Pubkey, window: u64) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: DataAccountInstruction::VerifyDataStep(VerifyDataStepArgs {
+            window,
+            debug: LogLevel::QUIET,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_verify_step_covers_the_content_over_many_instructions() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let content: Vec<u8> = (0..600).map(|i| (i * 7) as u8).collect();
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        content.clone(),
+        hash(&content).to_bytes(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the window must keep the hashed bytes a multiple of the sha256 block length
+    for window in [0, 100] {
+        let ix = verify_step_ix(&program_id, &data_key, window);
+        assert_error(
+            try_send(&mut banks_client, &payer, ix, &[&payer]).await,
+            DataAccountError::InvalidVerificationWindow,
+        );
+    }
+
+    for offset in [128, 256, 384, 512] {
+        let ix = verify_step_ix(&program_id, &data_key, 128);
+        try_send(&mut banks_client, &payer, ix, &[&payer])
+            .await
+            .unwrap();
+        let metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+        assert_eq!(metadata.verification_cursor().unwrap().offset, offset);
+        assert_eq!(
+            *metadata.serialization_status(),
+            SerializationStatusOption::UNVERIFIED
+        );
+    }
+
+    // the last window hashes the remaining 88 bytes and sets the serialization_status
+    let ix = verify_step_ix(&program_id, &data_key, 128);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let metadata = metadata(&mut banks_client, &program_id, &data_key).await;
+    assert_eq!(metadata.verification_cursor(), None);
+    assert_eq!(
+        *metadata.serialization_status(),
+        SerializationStatusOption::VERIFIED
+    );
+}
+
+#[tokio::test]
+async fn test_verify_step_restarts_after_a_write() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let (authority, data_key) = (payer.pubkey(), data_account.pubkey());
+    let content = vec![1; 300];
+
+    let ix = initialize_ix(&program_id, &authority, &data_key);
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        content.clone(),
+        hash(&content).to_bytes(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    let ix = verify_step_ix(&program_id, &data_key, 256);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert!(metadata(&mut banks_client, &program_id, &data_key)
+        .await
+        .verification_cursor()
+        .is_some());
+
+    // a write drops the progress so the already hashed bytes can't be stale
+    let ix = update_ix(
+        &program_id,
+        &authority,
+        &data_key,
+        vec![2; 300],
+        hash(&content).to_bytes(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(
+        metadata(&mut banks_client, &program_id, &data_key)
+            .await
+            .verification_cursor(),
+        None
+    );
+
+    // the content no longer matches the content_hash
+    let ix = verify_step_ix(&program_id, &data_key, 4_096);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+    assert_eq!(
+        *metadata(&mut banks_client, &program_id, &data_key)
+            .await
+            .serialization_status(),
+        SerializationStatusOption::FAILED
+    );
+}
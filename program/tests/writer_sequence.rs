@@ -0,0 +1,98 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{
+    initialize_ix, metadata_key, program_test, try_send, update_args, update_ix_with_args,
+};
+use dataaccount::{
+    error::DataAccountError,
+    state::{DataAccountMetadata, UpdateDataAccountArgs},
+};
+use solana_program::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+fn assert_stale_sequence_error(result: Result<(), BanksClientError>) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(DataAccountError::StaleSequence as u32)
+        )
+    );
+}
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    sequence: Option<u64>,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        sequence,
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+#[tokio::test]
+async fn test_writer_sequence() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"two".to_vec(),
+        Some(2),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // a retried earlier write cannot regress the content
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"one".to_vec(),
+        Some(1),
+    );
+    assert_stale_sequence_error(try_send(&mut banks_client, &payer, ix, &[&payer]).await);
+
+    // nor can an unsequenced write once the writer is sequenced
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"any".to_vec(),
+        None,
+    );
+    assert_stale_sequence_error(try_send(&mut banks_client, &payer, ix, &[&payer]).await);
+
+    let metadata = banks_client
+        .get_account(metadata_key(&program_id, &data_account.pubkey()))
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = DataAccountMetadata::deserialize(&mut &metadata.data[..]).unwrap();
+    assert_eq!(metadata.writer_sequences().len(), 1);
+    assert_eq!(metadata.writer_sequences()[0].writer, payer.pubkey());
+    assert_eq!(metadata.writer_sequences()[0].sequence, 2);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{initialize_ix, metadata_key, program_test, try_send, update_ix};
+use dataaccount::{
+    instruction::DataAccountInstruction,
+    state::{AddWriterArgs, LogLevel, RemoveWriterArgs},
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn writer_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    instruction: DataAccountInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*data_account, false),
+            AccountMeta::new(metadata_key(program_id, data_account), false),
+        ],
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_listed_writer_can_update() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+    let writer = Keypair::new();
+
+    let ix = initialize_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    try_send(&mut banks_client, &payer, ix, &[&payer, &data_account])
+        .await
+        .unwrap();
+
+    let ix = writer_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::AddWriter(AddWriterArgs {
+            writer: writer.pubkey(),
+            debug: LogLevel::QUIET,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    // the writer pays for the growth of the data_account
+    let ix = system_instruction::transfer(&payer.pubkey(), &writer.pubkey(), 1_000_000_000);
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &writer.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer, &writer])
+        .await
+        .unwrap();
+
+    let ix = writer_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        DataAccountInstruction::RemoveWriter(RemoveWriterArgs {
+            writer: writer.pubkey(),
+            debug: LogLevel::QUIET,
+        }),
+    );
+    try_send(&mut banks_client, &payer, ix, &[&payer])
+        .await
+        .unwrap();
+
+    let ix = update_ix(
+        &program_id,
+        &writer.pubkey(),
+        &data_account.pubkey(),
+        b"world".to_vec(),
+    );
+    assert!(try_send(&mut banks_client, &payer, ix, &[&payer, &writer])
+        .await
+        .is_err());
+
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data, b"hello");
+}
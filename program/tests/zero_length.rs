@@ -0,0 +1,140 @@
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::{
+    close_ix, initialize_args, initialize_ix_with_args, metadata_key, program_test, update_args,
+    update_ix_with_args,
+};
+use dataaccount::state::{InitializeDataAccountArgs, UpdateDataAccountArgs};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::{tokio, BanksClient};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    transaction.sign(signers, recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+fn initialize_ix(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    data_account: &Pubkey,
+    space: u64,
+    is_dynamic: bool,
+) -> Instruction {
+    let args = InitializeDataAccountArgs {
+        space,
+        is_dynamic,
+        ..initialize_args(payer)
+    };
+    initialize_ix_with_args(program_id, payer, data_account, args)
+}
+
+fn update_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    data_account: &Pubkey,
+    data: Vec<u8>,
+    truncate: bool,
+) -> Instruction {
+    let args = UpdateDataAccountArgs {
+        realloc_down: truncate,
+        truncate,
+        ..update_args(data)
+    };
+    update_ix_with_args(program_id, authority, data_account, args)
+}
+
+#[tokio::test]
+async fn test_close_zero_length_data_account() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        0,
+        false,
+    );
+    send(&mut banks_client, &payer, ix, &[&payer, &data_account]).await;
+
+    let ix = close_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    send(&mut banks_client, &payer, ix, &[&payer]).await;
+
+    let metadata = metadata_key(&program_id, &data_account.pubkey());
+    assert!(banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    assert!(banks_client.get_account(metadata).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_update_and_close_zero_length_dynamic_data_account() {
+    let program_id = Pubkey::new_unique();
+    let (mut banks_client, payer, _) = program_test(program_id).start().await;
+    let data_account = Keypair::new();
+
+    let ix = initialize_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        0,
+        true,
+    );
+    send(&mut banks_client, &payer, ix, &[&payer, &data_account]).await;
+
+    // grow the zero-length data account
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        b"hello".to_vec(),
+        false,
+    );
+    send(&mut banks_client, &payer, ix, &[&payer]).await;
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data, b"hello");
+
+    // shrink it back to zero-length
+    let ix = update_ix(
+        &program_id,
+        &payer.pubkey(),
+        &data_account.pubkey(),
+        vec![],
+        true,
+    );
+    send(&mut banks_client, &payer, ix, &[&payer]).await;
+    let account = banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(account.data.is_empty());
+
+    let ix = close_ix(&program_id, &payer.pubkey(), &data_account.pubkey());
+    send(&mut banks_client, &payer, ix, &[&payer]).await;
+    assert!(banks_client
+        .get_account(data_account.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}